@@ -0,0 +1,66 @@
+//! Feeds a steady stream of rows to a pipeline with a fixed cost per batch, like a round trip to
+//! a database, and prints the batch size `AdaptiveBatched` picks as it converges under a 2ms
+//! latency target, compared against the throughput of fixed batch sizes. Run with
+//! `cargo bench --bench adaptive_batch`.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use chain_link::*;
+
+const ROWS: u64 = 200_000;
+const TARGET: Duration = Duration::from_millis(2);
+
+fn spin(duration: Duration) {
+    let start = Instant::now();
+    while start.elapsed() < duration {
+        std::hint::spin_loop();
+    }
+}
+
+/// 500µs per batch plus 10µs per row.
+struct Insert;
+
+impl Chain<0> for Insert {
+    type In<'a> = Vec<u64>;
+    type Out<'a> = Vec<u64>;
+
+    fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+        spin(Duration::from_micros(500 + 10 * input.len() as u64));
+        input.into_iter().map(black_box).collect()
+    }
+}
+
+impl Length for Insert {
+    type Len = L<1>;
+}
+
+fn throughput(rows: u64, elapsed: Duration) -> f64 {
+    rows as f64 / elapsed.as_secs_f64()
+}
+
+fn main() {
+    let mut batcher = AdaptiveBatched::<Insert, _, _>::new(TARGET);
+    let start = Instant::now();
+    let mut batches = 0;
+    for row in 0..ROWS {
+        if batcher.push(row).is_some() {
+            batches += 1;
+            if batches <= 12 || batches % 500 == 0 {
+                println!("batch {batches:>5}: next size {}", batcher.batch_size());
+            }
+        }
+    }
+    batcher.flush();
+    let adaptive = throughput(ROWS, start.elapsed());
+    println!("adaptive: {adaptive:.0} rows/s, settled at {}", batcher.batch_size());
+
+    for size in [1, 16, batcher.batch_size()] {
+        let rows = (size as u64 * 200).min(ROWS);
+        let start = Instant::now();
+        for batch in (0..rows).collect::<Vec<_>>().chunks(size) {
+            black_box(Insert::cascade(batch.to_vec()));
+        }
+        println!("fixed {size:>5}: {:.0} rows/s", throughput(rows, start.elapsed()));
+    }
+}
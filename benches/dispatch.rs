@@ -0,0 +1,37 @@
+//! Compares running the same stages through a `DynCascade` of boxed closures against the
+//! flattened `FlatCascade` of `fn` pointers. Run with `cargo bench --bench dispatch`.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use chain_link::*;
+
+const STAGES: usize = 64;
+const RUNS: u64 = 200_000;
+
+fn stage(n: u64) -> u64 {
+    n.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407)
+}
+
+fn time(name: &str, run: impl Fn(u64) -> u64) -> Duration {
+    let start = Instant::now();
+    for n in 0..RUNS {
+        black_box(run(black_box(n)));
+    }
+    let elapsed = start.elapsed();
+    println!("{name:>8}: {:?} per cascade", elapsed / RUNS as u32);
+    elapsed
+}
+
+fn main() {
+    let mut boxed = DynCascade::new();
+    for _ in 0..STAGES {
+        boxed.push(stage);
+    }
+    let flat: FlatCascade<u64> = (0..STAGES).map(|_| stage as fn(u64) -> u64).collect();
+    assert_eq!(boxed.cascade(7), flat.cascade(7));
+
+    let boxed = time("boxed", |n| boxed.cascade(n));
+    let flat = time("flat", |n| flat.cascade(n));
+    println!("speedup: {:.2}x", boxed.as_secs_f64() / flat.as_secs_f64());
+}
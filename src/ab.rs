@@ -0,0 +1,63 @@
+use std::collections::hash_map::{DefaultHasher, RandomState};
+use std::hash::{BuildHasher, Hash, Hasher};
+
+use crate::CascadeOf;
+
+/// Which pipeline of an A/B split handled an input.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Variant {
+    A,
+    B,
+}
+
+impl Variant {
+    /// `A` for the first `ratio` of the `[0, 1)` range, `B` for the rest.
+    fn pick(hash: u64, ratio: f64) -> Self {
+        assert!((0.0..=1.0).contains(&ratio), "an A/B split sends a fraction between 0 and 1 to `A`, not {ratio}");
+        let point = (hash >> 11) as f64 / (1u64 << 53) as f64;
+        match point < ratio {
+            true => Variant::A,
+            false => Variant::B,
+        }
+    }
+}
+
+fn route<A, B, I, O>(variant: Variant, input: I) -> (O, Variant)
+where
+    A: CascadeOf<I, O>,
+    B: CascadeOf<I, O>,
+{
+    match variant {
+        Variant::A => (A::cascade(input), variant),
+        Variant::B => (B::cascade(input), variant),
+    }
+}
+
+/// Sends `input` through `A` with probability `ratio`, or `B` otherwise, and tags the output
+/// with which one it went through.
+///
+/// Panics if `ratio` isn't between 0 and 1.
+pub fn cascade_ab<A, B, I, O>(ratio: f64, input: I) -> (O, Variant)
+where
+    A: CascadeOf<I, O>,
+    B: CascadeOf<I, O>,
+{
+    // every `RandomState` gets its own keys, so this is a fresh draw each call
+    let variant = Variant::pick(RandomState::new().hash_one(ratio.to_bits()), ratio);
+    route::<A, B, I, O>(variant, input)
+}
+
+/// Same as `cascade_ab`, but the choice only depends on `seed` and the input itself, so equal
+/// inputs always land in the same variant. That holds across processes too, as long as they're
+/// built with the same Rust version, since std doesn't promise `DefaultHasher` won't change.
+pub fn cascade_ab_seeded<A, B, I, O>(seed: u64, ratio: f64, input: I) -> (O, Variant)
+where
+    A: CascadeOf<I, O>,
+    B: CascadeOf<I, O>,
+    I: Hash,
+{
+    let mut hasher = DefaultHasher::new();
+    seed.hash(&mut hasher);
+    input.hash(&mut hasher);
+    route::<A, B, I, O>(Variant::pick(hasher.finish(), ratio), input)
+}
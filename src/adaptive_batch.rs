@@ -0,0 +1,90 @@
+use std::marker::PhantomData;
+use std::mem;
+use std::time::Duration;
+
+use crate::{CascadeOf, Clock, SystemClock};
+
+/// Feeds inputs to a pipeline `P` that processes a whole `Vec` of them at once, picking the batch
+/// size as it goes: each batch is timed, and the next one is scaled by how far that time was from
+/// the latency `target`. With the usual fixed cost per batch plus a cost per input, this settles on
+/// the largest batch that still finishes within the target, which is also the one with the best
+/// throughput, and follows the load as it changes. Growth is capped at doubling per batch.
+pub struct AdaptiveBatched<P, I, O, C = SystemClock> {
+    target: Duration,
+    min: usize,
+    max: usize,
+    size: usize,
+    pending: Vec<I>,
+    clock: C,
+    _pipeline: PhantomData<(P, O)>,
+}
+
+impl<P: CascadeOf<Vec<I>, Vec<O>>, I, O> AdaptiveBatched<P, I, O> {
+    /// Starts from batches of 1, growing up to 1024, timed on the system clock.
+    pub fn new(target: Duration) -> Self {
+        Self { target, min: 1, max: 1024, size: 1, pending: Vec::new(), clock: SystemClock, _pipeline: PhantomData }
+    }
+}
+
+impl<P: CascadeOf<Vec<I>, Vec<O>>, I, O, C: Clock> AdaptiveBatched<P, I, O, C> {
+    /// Keeps the batch size within `min..=max`. Panics if `min` is 0 or more than `max`.
+    pub fn with_limits(self, min: usize, max: usize) -> Self {
+        assert!(min > 0 && min <= max, "AdaptiveBatched limits must satisfy 0 < min <= max");
+        Self { min, max, size: self.size.clamp(min, max), ..self }
+    }
+
+    pub fn with_clock<D: Clock>(self, clock: D) -> AdaptiveBatched<P, I, O, D> {
+        AdaptiveBatched {
+            target: self.target,
+            min: self.min,
+            max: self.max,
+            size: self.size,
+            pending: self.pending,
+            clock,
+            _pipeline: PhantomData,
+        }
+    }
+
+    /// The size the next batch will be cascaded at.
+    pub fn batch_size(&self) -> usize {
+        self.size
+    }
+
+    /// Number of inputs waiting for the current batch to fill up.
+    pub fn pending(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Adds `input` to the current batch, cascading it and returning the outputs once it's full.
+    pub fn push(&mut self, input: I) -> Option<Vec<O>> {
+        self.pending.push(input);
+        (self.pending.len() >= self.size).then(|| self.flush())
+    }
+
+    /// Cascades whatever is pending regardless of the batch size, e.g. on shutdown. A partial
+    /// batch only changes the size if it still went over the target.
+    pub fn flush(&mut self) -> Vec<O> {
+        if self.pending.is_empty() {
+            return Vec::new();
+        }
+        let len = self.pending.len();
+        // sized like the last batch rather than the next, which may be far too big to allocate
+        let batch = mem::replace(&mut self.pending, Vec::with_capacity(len));
+        let started = self.clock.now();
+        let out = P::cascade(batch);
+        self.adjust(len, self.clock.now().saturating_sub(started));
+        out
+    }
+
+    fn adjust(&mut self, len: usize, elapsed: Duration) {
+        // a short batch that made the target says nothing about how much bigger a full one can be
+        if len < self.size && elapsed <= self.target {
+            return;
+        }
+        let scaled = match elapsed.is_zero() {
+            true => len.saturating_mul(2),
+            false => (len as f64 * self.target.as_secs_f64() / elapsed.as_secs_f64()) as usize,
+        };
+        self.size = scaled.min(self.size.saturating_mul(2)).clamp(self.min, self.max);
+    }
+}
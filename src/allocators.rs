@@ -0,0 +1,49 @@
+use bumpalo::Bump;
+
+use crate::ContextCascade;
+
+pub use bumpalo;
+
+/// Context giving every link its own bump allocator for scratch space, like temporary strings
+/// built up while parsing. Allocating is just a pointer bump and nothing is freed one by one,
+/// instead everything a cascade allocated is freed together once it's done.
+///
+/// A link's output can't borrow from its allocator, so anything that has to outlive the link
+/// still goes on the regular heap.
+pub struct Allocators {
+    bumps: Vec<Bump>,
+}
+
+impl Allocators {
+    /// One allocator for each of `stages` links.
+    pub fn new(stages: usize) -> Self {
+        Self { bumps: (0..stages).map(|_| Bump::new()).collect() }
+    }
+
+    /// The allocator for link `stage`. Panics if there's no such link.
+    pub fn bump(&self, stage: usize) -> &Bump {
+        &self.bumps[stage]
+    }
+
+    /// Bytes currently allocated for link `stage`, not counting capacity kept around for reuse.
+    pub fn used(&mut self, stage: usize) -> usize {
+        self.bumps[stage].iter_allocated_chunks().map(<[_]>::len).sum()
+    }
+
+    /// Frees everything allocated for every link at once, keeping capacity for the next cascade.
+    pub fn reset(&mut self) {
+        self.bumps.iter_mut().for_each(Bump::reset);
+    }
+}
+
+pub trait AllocatorCascade: ContextCascade<Context = Allocators> {
+    /// Cascades `input` with every link allocating scratch space from its own allocator in
+    /// `allocators`, then frees all of it together.
+    fn cascade_with_allocators<'a>(allocators: &mut Allocators, input: Self::In<'a>) -> Self::Out<'a> {
+        let out = Self::cascade_with(allocators, input);
+        allocators.reset();
+        out
+    }
+}
+
+impl<T: ContextCascade<Context = Allocators>> AllocatorCascade for T {}
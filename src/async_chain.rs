@@ -0,0 +1,70 @@
+use std::future::Future;
+
+use seq_macro::seq;
+
+use crate::{InRange, Length, L};
+
+/// Async counterpart to `Chain<N>`, where each link returns a future instead of its output.
+/// The futures have to be `Send`, so that cascades can run on multi-threaded executors and be
+/// served by the likes of axum and hyper.
+pub trait AsyncChain<const N: usize>
+where
+    Self: InRange<N, <Self as Length>::Len>,
+{
+    type In<'a>;
+    type Out<'a>;
+
+    fn chain(input: Self::In<'_>) -> impl Future<Output = Self::Out<'_>> + Send;
+}
+
+pub trait AsyncLink<const N: usize> {
+    type In<'a>;
+    type Out<'a>;
+
+    fn link(input: Self::In<'_>) -> impl Future<Output = Self::Out<'_>> + Send;
+}
+
+impl<T: AsyncChain<0>> AsyncLink<1> for T {
+    type In<'a> = <T as AsyncChain<0>>::In<'a>;
+    type Out<'a> = <T as AsyncChain<0>>::Out<'a>;
+
+    fn link(input: Self::In<'_>) -> impl Future<Output = Self::Out<'_>> + Send {
+        <T as AsyncChain<0>>::chain(input)
+    }
+}
+
+// same type gymnastics as `Link<N>`, except each link awaits the previous one
+seq!(N in 2..=32 {
+    impl<T> AsyncLink<N> for T
+    where
+        T: AsyncChain<0>,
+        for<'a> T: AsyncLink<{N - 1}, In<'a> = <T as AsyncChain<0>>::In<'a>>,
+        for<'a> T: AsyncChain<{N - 1}, In<'a> = <T as AsyncLink<{N - 1}>>::Out<'a>>,
+    {
+        type In<'a> = <T as AsyncChain<0>>::In<'a>;
+        type Out<'a> = <T as AsyncChain<{N - 1}>>::Out<'a>;
+
+        fn link(input: Self::In<'_>) -> impl Future<Output = Self::Out<'_>> + Send {
+            // starting the previous links up front keeps `input` out of this future, so it's
+            // `Send` by way of the futures it awaits alone
+            let previous = <T as AsyncLink<{N - 1}>>::link(input);
+            async move { <T as AsyncChain<{N - 1}>>::chain(previous.await).await }
+        }
+    }
+});
+
+pub trait AsyncCascade {
+    type In<'a>;
+    type Out<'a>;
+
+    fn async_cascade(input: Self::In<'_>) -> impl Future<Output = Self::Out<'_>> + Send;
+}
+
+impl<const N: usize, T: AsyncLink<N> + Length<Len = L<N>>> AsyncCascade for T {
+    type In<'a> = T::In<'a>;
+    type Out<'a> = T::Out<'a>;
+
+    fn async_cascade(input: Self::In<'_>) -> impl Future<Output = Self::Out<'_>> + Send {
+        <T as AsyncLink<N>>::link(input)
+    }
+}
@@ -0,0 +1,144 @@
+use std::future::Future;
+use std::marker::PhantomData;
+use std::pin::Pin;
+
+use seq_macro::seq;
+
+use crate::{AsyncCascade, AsyncChain, AsyncLink, Codec, DecodeError, InRange, Length, L};
+
+type DurableStep = fn(Vec<u8>) -> Pin<Box<dyn Future<Output = Result<Vec<u8>, DecodeError>>>>;
+
+fn durable<const N: usize, T>(state: Vec<u8>) -> Pin<Box<dyn Future<Output = Result<Vec<u8>, DecodeError>>>>
+where
+    T: AsyncChain<N> + InRange<N, <T as Length>::Len> + 'static,
+    T::In<'static>: Codec,
+    T::Out<'static>: Codec,
+{
+    Box::pin(async move {
+        let input = T::In::<'static>::from_bytes(&state)?;
+        Ok(T::chain(input).await.to_bytes())
+    })
+}
+
+/// Collects every async link from 0..N as a step from the encoded input of a link to its encoded
+/// output, so the state in between links is always something that can be saved. Needs every
+/// link's input and output to be `'static` and a `Codec`.
+pub trait DurableLink<const N: usize>: AsyncLink<N> {
+    fn durable_link() -> Vec<DurableStep>;
+}
+
+impl<T: AsyncChain<0> + 'static> DurableLink<1> for T
+where
+    <T as AsyncChain<0>>::In<'static>: Codec,
+    <T as AsyncChain<0>>::Out<'static>: Codec,
+{
+    fn durable_link() -> Vec<DurableStep> {
+        vec![durable::<0, T>]
+    }
+}
+
+seq!(N in 2..=32 {
+    impl<T> DurableLink<N> for T
+    where
+        T: AsyncChain<0> + 'static,
+        for<'a> T: DurableLink<{N - 1}, In<'a> = <T as AsyncChain<0>>::In<'a>>,
+        for<'a> T: AsyncChain<{N - 1}, In<'a> = <T as AsyncLink<{N - 1}>>::Out<'a>>,
+        <T as AsyncChain<{N - 1}>>::In<'static>: Codec,
+        <T as AsyncChain<{N - 1}>>::Out<'static>: Codec,
+    {
+        fn durable_link() -> Vec<DurableStep> {
+            let mut steps = <T as DurableLink<{N - 1}>>::durable_link();
+            steps.push(durable::<{N - 1}, T>);
+            steps
+        }
+    }
+});
+
+/// An async cascade that can be stopped between any two links, saved with `to_bytes`, and picked
+/// up again with `from_bytes`, e.g. by another process after a restart. Links already run aren't
+/// run again, and a link that was interrupted mid-flight starts over from its saved input.
+pub struct AsyncCheckpoint<T> {
+    steps: Vec<DurableStep>,
+    next: usize,
+    state: Vec<u8>,
+    _pipeline: PhantomData<T>,
+}
+
+impl<T: DurableCascade> AsyncCheckpoint<T> {
+    /// Runs the next link, returning its index, or `None` if every link has already run.
+    pub async fn resume(&mut self) -> Result<Option<usize>, DecodeError> {
+        let Some(step) = self.steps.get(self.next) else {
+            return Ok(None);
+        };
+        self.state = step(std::mem::take(&mut self.state)).await?;
+        self.next += 1;
+        Ok(Some(self.next - 1))
+    }
+
+    /// How many links have run so far.
+    pub fn completed(&self) -> usize {
+        self.next
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.next == self.steps.len()
+    }
+
+    /// Everything needed to pick the cascade up again: how many links have run, and the encoded
+    /// value handed to the next one.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        (self.next, self.state.clone()).to_bytes()
+    }
+
+    /// Restores a cascade saved by `to_bytes`. Fails if it was saved further along than `T` has
+    /// links, though the saved value itself is only checked once the next link decodes it.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, DecodeError> {
+        let (next, state) = <(usize, Vec<u8>)>::from_bytes(bytes)?;
+        let steps = T::durable_steps();
+        if next > steps.len() {
+            return Err(DecodeError);
+        }
+        Ok(Self { steps, next, state, _pipeline: PhantomData })
+    }
+
+    /// Runs whatever links are left and decodes the cascade's output.
+    pub async fn finish(mut self) -> Result<T::Out<'static>, DecodeError>
+    where
+        T::Out<'static>: Codec,
+    {
+        while self.resume().await?.is_some() {}
+        T::Out::<'static>::from_bytes(&self.state)
+    }
+}
+
+pub trait DurableCascade: AsyncCascade + Sized {
+    /// Sets up a cascade of `input` without running any of it, for the caller to step through
+    /// and save along the way.
+    fn into_async_checkpoint(input: Self::In<'static>) -> AsyncCheckpoint<Self>
+    where
+        Self::In<'static>: Codec;
+
+    /// Every link as a step between encoded values, in order.
+    fn durable_steps() -> Vec<DurableStep>;
+}
+
+impl<const N: usize, T> DurableCascade for T
+where
+    T: DurableLink<N> + Length<Len = L<N>>,
+{
+    fn into_async_checkpoint(input: Self::In<'static>) -> AsyncCheckpoint<Self>
+    where
+        Self::In<'static>: Codec,
+    {
+        AsyncCheckpoint {
+            steps: <T as DurableLink<N>>::durable_link(),
+            next: 0,
+            state: input.to_bytes(),
+            _pipeline: PhantomData,
+        }
+    }
+
+    fn durable_steps() -> Vec<DurableStep> {
+        <T as DurableLink<N>>::durable_link()
+    }
+}
@@ -0,0 +1,87 @@
+use seq_macro::seq;
+
+use crate::{Chain, InRange, Length};
+
+/// Wraps a chain whose adjacent links don't quite line up, where a link outputs something that
+/// only converts `Into` what the next one takes, like an `i32` into an `i64`. Every link of the
+/// wrapper takes the previous link's output as is and converts it before handing it on, so
+/// reusable links compose without an adapter link in between.
+///
+/// Links whose types can't be converted still don't cascade:
+/// ```compile_fail
+/// use chain_link::*;
+///
+/// struct Narrowing;
+///
+/// impl Chain<0> for Narrowing {
+///     type In<'a> = i64;
+///     type Out<'a> = i64;
+///
+///     fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+///         input
+///     }
+/// }
+///
+/// impl Chain<1> for Narrowing {
+///     type In<'a> = i32;
+///     type Out<'a> = i32;
+///
+///     fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+///         input
+///     }
+/// }
+///
+/// impl Length for Narrowing {
+///     type Len = L<2>;
+/// }
+///
+/// AutoConvert::<Narrowing>::cascade(1);
+/// ```
+pub struct AutoConvert<T>(T);
+
+impl<T: Length> Length for AutoConvert<T> {
+    type Len = T::Len;
+}
+
+impl<T: Chain<0>> Chain<0> for AutoConvert<T>
+where
+    Self: InRange<0, Self::Len>,
+{
+    type In<'a> = <T as Chain<0>>::In<'a>;
+    type Out<'a> = <T as Chain<0>>::Out<'a>;
+
+    fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+        <T as Chain<0>>::chain(input)
+    }
+}
+
+// spelled out for `M = 1`, since `{M - 1}` there trips up clippy
+impl<T> Chain<1> for AutoConvert<T>
+where
+    T: Chain<0> + Chain<1>,
+    for<'a> <T as Chain<0>>::Out<'a>: Into<<T as Chain<1>>::In<'a>>,
+    Self: InRange<1, Self::Len>,
+{
+    type In<'a> = <T as Chain<0>>::Out<'a>;
+    type Out<'a> = <T as Chain<1>>::Out<'a>;
+
+    fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+        <T as Chain<1>>::chain(input.into())
+    }
+}
+
+seq!(M in 2..32 {
+    impl<T> Chain<M> for AutoConvert<T>
+    where
+        T: Chain<{M - 1}> + Chain<M>,
+        for<'a> <T as Chain<{M - 1}>>::Out<'a>: Into<<T as Chain<M>>::In<'a>>,
+        Self: InRange<M, Self::Len>,
+    {
+        type In<'a> = <T as Chain<{M - 1}>>::Out<'a>;
+        type Out<'a> = <T as Chain<M>>::Out<'a>;
+
+        fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+            <T as Chain<M>>::chain(input.into())
+        }
+    }
+});
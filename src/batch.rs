@@ -0,0 +1,73 @@
+use std::mem;
+use std::time::Duration;
+
+use crate::{Cascade, Clock};
+
+/// Push-based sink that cascades every input and collects the outputs into batches, handing a
+/// batch back once it reaches `size` outputs or its oldest output is `interval` old, whichever
+/// happens first.
+pub struct BatchSink<'a, T: Cascade, C: Clock> {
+    size: usize,
+    interval: Duration,
+    batch: Vec<T::Out<'a>>,
+    started: Duration,
+    clock: C,
+}
+
+impl<'a, T: Cascade, C: Clock> BatchSink<'a, T, C> {
+    /// Panics if `size` is 0.
+    pub fn new(size: usize, interval: Duration, clock: C) -> Self {
+        assert!(size > 0, "BatchSink size must be at least 1");
+        Self { size, interval, batch: Vec::with_capacity(size), started: Duration::ZERO, clock }
+    }
+
+    /// Cascades `input` into the current batch, returning the batch if that filled it up or it
+    /// was already due.
+    pub fn push(&mut self, input: T::In<'a>) -> Option<Vec<T::Out<'a>>> {
+        if self.batch.is_empty() {
+            self.started = self.clock.now();
+        }
+        self.batch.push(T::cascade(input));
+        match self.batch.len() >= self.size {
+            true => Some(self.flush()),
+            false => self.tick(),
+        }
+    }
+
+    /// Returns the current batch if its interval has elapsed, for callers checking in between
+    /// pushes.
+    pub fn tick(&mut self) -> Option<Vec<T::Out<'a>>> {
+        self.is_due().then(|| self.flush())
+    }
+
+    /// Waits out whatever is left of the current batch's interval on the clock, then returns it.
+    /// `None` if the batch is empty.
+    pub async fn flushed(&mut self) -> Option<Vec<T::Out<'a>>> {
+        if self.batch.is_empty() {
+            return None;
+        }
+        let deadline = self.started + self.interval;
+        let now = self.clock.now();
+        if deadline > now {
+            self.clock.sleep(deadline - now).await;
+        }
+        Some(self.flush())
+    }
+
+    /// Returns the current batch regardless of size or age, e.g. on shutdown.
+    pub fn flush(&mut self) -> Vec<T::Out<'a>> {
+        mem::replace(&mut self.batch, Vec::with_capacity(self.size))
+    }
+
+    pub fn len(&self) -> usize {
+        self.batch.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.batch.is_empty()
+    }
+
+    fn is_due(&self) -> bool {
+        !self.batch.is_empty() && self.clock.now() >= self.started + self.interval
+    }
+}
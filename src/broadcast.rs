@@ -0,0 +1,22 @@
+use tokio::sync::broadcast::Sender;
+
+use crate::Sequential;
+
+pub trait BroadcastCascade: Sequential {
+    /// Runs every step on `input`, sending a copy of each step's output on `sender` as soon as
+    /// it's produced, so subscribers can watch the cascade live. Nobody listening isn't an
+    /// error, and subscribers that fall behind miss values the way `broadcast` always handles
+    /// lagging receivers, without ever holding the cascade up.
+    fn cascade_broadcast(input: Self::Item, sender: &Sender<Self::Item>) -> Self::Item
+    where
+        Self::Item: Clone,
+    {
+        Self::steps().into_iter().fold(input, |item, step| {
+            let item = step(item);
+            let _ = sender.send(item.clone());
+            item
+        })
+    }
+}
+
+impl<T: Sequential> BroadcastCascade for T {}
@@ -0,0 +1,20 @@
+use futures::stream::{self, Stream, StreamExt};
+
+use crate::AsyncCascade;
+
+pub trait BufferedCascade: AsyncCascade {
+    /// Lazily cascades every input, running up to `concurrency` cascades at once. Inputs are only
+    /// pulled while there's room, so a slow cascade holds back the rest instead of letting them
+    /// pile up, and the outputs come out in the same order as the inputs.
+    ///
+    /// Panics if `concurrency` is zero, since the stream would never make progress.
+    fn async_cascade_buffered<'a>(
+        inputs: impl IntoIterator<Item = Self::In<'a>>,
+        concurrency: usize,
+    ) -> impl Stream<Item = Self::Out<'a>> {
+        assert!(concurrency > 0, "buffered cascades need a concurrency of at least one");
+        stream::iter(inputs).map(Self::async_cascade).buffered(concurrency)
+    }
+}
+
+impl<T: AsyncCascade> BufferedCascade for T {}
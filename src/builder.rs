@@ -0,0 +1,173 @@
+use std::marker::PhantomData;
+
+use crate::{CascadeOf, Chain, Length, L};
+
+/// Fluent builder for a pipeline out of closures, whose type tracks the current output type `B`
+/// so that every stage has to accept what the previous one returned:
+/// ```
+/// use chain_link::*;
+///
+/// let pipeline = PipelineBuilder::<&str>::new()
+///     .stage(|s| s.len())
+///     .stage(|n| n * 2)
+///     .build();
+/// assert_eq!(pipeline.cascade("four"), 8);
+/// ```
+///
+/// Mis-wired stages don't compile:
+/// ```compile_fail
+/// use chain_link::*;
+///
+/// let pipeline = PipelineBuilder::<&str>::new()
+///     .stage(|s| s.len())
+///     .stage(|s: &str| s.to_uppercase())
+///     .build();
+/// ```
+///
+/// A pipeline built only out of `Cascade`s with `then` is a `Cascade` itself, so it can be passed
+/// wherever one is expected:
+/// ```
+/// use chain_link::*;
+///
+/// struct Len;
+///
+/// impl Chain<0> for Len {
+///     type In<'a> = String;
+///     type Out<'a> = usize;
+///
+///     fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+///         input.len()
+///     }
+/// }
+///
+/// impl Length for Len {
+///     type Len = L<1>;
+/// }
+///
+/// fn run<P: CascadeOf<String, usize>>(_: &P, input: &str) -> usize {
+///     P::cascade(input.to_owned())
+/// }
+///
+/// let pipeline = PipelineBuilder::<String>::new().then::<Len, _>().build();
+/// assert_eq!(run(&pipeline, "four"), 4);
+/// ```
+///
+/// Closures passed to `stage` are values though, so once there's one the result can only be
+/// called through `BuiltPipeline::cascade`.
+pub struct PipelineBuilder<A, B = A, F = Identity> {
+    run: F,
+    shape: PhantomData<fn(A) -> B>,
+}
+
+impl<A> PipelineBuilder<A, A, Identity> {
+    pub fn new() -> Self {
+        Self { run: Identity, shape: PhantomData }
+    }
+}
+
+impl<A> Default for PipelineBuilder<A, A, Identity> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<A, B, F: Stages<A, B>> PipelineBuilder<A, B, F> {
+    /// Adds a stage taking the current output.
+    pub fn stage<C>(self, stage: impl Fn(B) -> C) -> PipelineBuilder<A, C, impl Fn(A) -> C> {
+        let run = self.run;
+        PipelineBuilder { run: move |input| stage(run.run(input)), shape: PhantomData }
+    }
+
+    /// Adds whichever stage of a `StageVariant` group `variant` selects.
+    pub fn variant<V>(self, variant: V) -> PipelineBuilder<A, V::Out, impl Fn(A) -> V::Out>
+    where
+        V: StageVariant<In = B>,
+    {
+        self.stage(move |input| variant.run(input))
+    }
+
+    /// Adds every link of `P` as a single stage.
+    pub fn then<P: CascadeOf<B, C>, C>(self) -> PipelineBuilder<A, C, Then<F, P, B>> {
+        PipelineBuilder { run: Then(self.run, PhantomData), shape: PhantomData }
+    }
+
+    pub fn build(self) -> BuiltPipeline<A, B, F> {
+        BuiltPipeline { run: self.run, shape: PhantomData }
+    }
+}
+
+/// A group of interchangeable stages of the same shape, like several ways of decoding the same
+/// input, usually an enum with one variant per stage. Exactly one of them is picked when the
+/// pipeline is built with `PipelineBuilder::variant`, while the stages around it stay the same.
+pub trait StageVariant: Sized + 'static {
+    type In;
+    type Out;
+
+    /// Every stage in the group.
+    const VARIANTS: &'static [Self];
+
+    fn run(&self, input: Self::In) -> Self::Out;
+}
+
+/// The stages a `PipelineBuilder` has put together so far, taking `A` to `B`. Either closures,
+/// or the `Identity` it starts with followed by `Cascade`s added with `then`, which don't hold
+/// any values and so implement `Default`.
+pub trait Stages<A, B> {
+    fn run(&self, input: A) -> B;
+}
+
+impl<A, B, F: Fn(A) -> B> Stages<A, B> for F {
+    fn run(&self, input: A) -> B {
+        self(input)
+    }
+}
+
+/// The stages of a `PipelineBuilder` before any have been added.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Identity;
+
+impl<A> Stages<A, A> for Identity {
+    fn run(&self, input: A) -> A {
+        input
+    }
+}
+
+/// The stages `F` followed by the cascade `P`, which takes their output `B`.
+pub struct Then<F, P, B>(F, PhantomData<fn(B) -> P>);
+
+impl<F: Default, P, B> Default for Then<F, P, B> {
+    fn default() -> Self {
+        Self(F::default(), PhantomData)
+    }
+}
+
+impl<A, B, C, F: Stages<A, B>, P: CascadeOf<B, C>> Stages<A, C> for Then<F, P, B> {
+    fn run(&self, input: A) -> C {
+        P::cascade(self.0.run(input))
+    }
+}
+
+/// The pipeline put together by a `PipelineBuilder`.
+pub struct BuiltPipeline<A, B, F> {
+    run: F,
+    shape: PhantomData<fn(A) -> B>,
+}
+
+impl<A, B, F: Stages<A, B>> BuiltPipeline<A, B, F> {
+    pub fn cascade(&self, input: A) -> B {
+        self.run.run(input)
+    }
+}
+
+impl<A, B, F> Length for BuiltPipeline<A, B, F> {
+    type Len = L<1>;
+}
+
+impl<A, B, F: Stages<A, B> + Default> Chain<0> for BuiltPipeline<A, B, F> {
+    type In<'a> = A;
+    type Out<'a> = B;
+
+    fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+        F::default().run(input)
+    }
+}
@@ -0,0 +1,61 @@
+use std::marker::PhantomData;
+
+use crate::{CascadeOf, Chain, Length, L};
+
+/// One of the fixed-length pipelines a `Categorized` pipeline can pick from.
+pub struct SubPipeline<I, O> {
+    cascade: fn(I) -> O,
+    len: usize,
+}
+
+impl<I, O> SubPipeline<I, O> {
+    pub fn of<P: CascadeOf<I, O> + Length>() -> Self {
+        Self { cascade: P::cascade, len: P::len() }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn cascade(&self, input: I) -> O {
+        (self.cascade)(input)
+    }
+}
+
+/// A pipeline whose number of links depends on what category its input falls in. Each category
+/// gets its own fixed-length pipeline, and all of them take and return the same types, so
+/// `ByCategory<Self>` can stand in for any of them as a single `Cascade`.
+pub trait Categorized {
+    type In;
+    type Out;
+
+    /// Picks the sub-pipeline for `input`, usually by matching on its category.
+    fn select(input: &Self::In) -> SubPipeline<Self::In, Self::Out>;
+}
+
+/// One-link `Cascade` running each input through whichever sub-pipeline `T` selects for it.
+pub struct ByCategory<T>(PhantomData<T>);
+
+impl<T: Categorized> ByCategory<T> {
+    /// How many links `input` would go through.
+    pub fn stages(input: &T::In) -> usize {
+        T::select(input).len()
+    }
+}
+
+impl<T> Length for ByCategory<T> {
+    type Len = L<1>;
+}
+
+impl<T: Categorized> Chain<0> for ByCategory<T> {
+    type In<'a> = T::In;
+    type Out<'a> = T::Out;
+
+    fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+        T::select(&input).cascade(input)
+    }
+}
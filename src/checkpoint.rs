@@ -0,0 +1,62 @@
+use std::collections::BTreeSet;
+use std::marker::PhantomData;
+use std::time::Instant;
+
+use crate::{Named, Observer, StageInfo};
+
+/// Observer that dumps only the links of `T` whose `Named` name has been enabled, so specific
+/// stages can be instrumented from config without recompiling. Like every observer it sees which
+/// link ran and for how long, not the values passing through.
+pub struct Checkpoints<T> {
+    enabled: BTreeSet<String>,
+    started: Option<Instant>,
+    pub dumps: Vec<String>,
+    pipeline: PhantomData<fn() -> T>,
+}
+
+impl<T: Named> Checkpoints<T> {
+    pub fn new<S: Into<String>>(enabled: impl IntoIterator<Item = S>) -> Self {
+        Self {
+            enabled: enabled.into_iter().map(Into::into).collect(),
+            started: None,
+            dumps: Vec::new(),
+            pipeline: PhantomData,
+        }
+    }
+
+    /// Enables a comma separated list of names, e.g. from an environment variable. Blank
+    /// entries are ignored.
+    pub fn from_config(config: &str) -> Self {
+        Self::new(config.split(',').map(str::trim).filter(|name| !name.is_empty()))
+    }
+
+    pub fn enable(&mut self, name: impl Into<String>) {
+        self.enabled.insert(name.into());
+    }
+
+    pub fn disable(&mut self, name: &str) {
+        self.enabled.remove(name);
+    }
+
+    fn checkpoint(&self, stage: &StageInfo) -> Option<&'static str> {
+        T::stage_name(stage.index).filter(|name| self.enabled.contains(*name))
+    }
+}
+
+impl<T: Named> Observer for Checkpoints<T> {
+    fn before(&mut self, stage: &StageInfo) {
+        if self.checkpoint(stage).is_some() {
+            self.started = Some(Instant::now());
+        }
+    }
+
+    fn after(&mut self, stage: &StageInfo) {
+        if let Some(name) = self.checkpoint(stage) {
+            let elapsed = self.started.take().map(|started| started.elapsed()).unwrap_or_default();
+            self.dumps.push(format!(
+                "checkpoint {name}: stage {} {} -> {} in {elapsed:?}",
+                stage.index, stage.input, stage.output,
+            ));
+        }
+    }
+}
@@ -0,0 +1,220 @@
+use std::collections::VecDeque;
+use std::error::Error;
+use std::fmt;
+use std::future::Future;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use crate::{AsyncChain, Clock, InRange, Length, No, Select, Yes};
+
+/// Tracks the outcomes of the last `window` calls to a stage, and opens once `failures` of them
+/// have failed. While open every call fails fast until `cooldown` passes, after which a single
+/// call is let through as a trial while the rest keep failing fast: if it succeeds the breaker
+/// closes, otherwise it opens again.
+pub struct Breaker<C> {
+    failures: usize,
+    window: usize,
+    cooldown: Duration,
+    state: Mutex<BreakerState>,
+    clock: C,
+}
+
+struct BreakerState {
+    outcomes: VecDeque<bool>,
+    phase: Phase,
+}
+
+enum Phase {
+    Closed,
+    Open { since: Duration },
+    HalfOpen { trial_in_flight: bool },
+}
+
+impl<C: Clock> Breaker<C> {
+    /// Panics unless `0 < failures <= window`, since the breaker would otherwise open on the first
+    /// call or never.
+    pub const fn new(failures: usize, window: usize, cooldown: Duration, clock: C) -> Self {
+        assert!(0 < failures && failures <= window, "a breaker has to open after 1 to `window` failures");
+        Self {
+            failures,
+            window,
+            cooldown,
+            state: Mutex::new(BreakerState { outcomes: VecDeque::new(), phase: Phase::Closed }),
+            clock,
+        }
+    }
+
+    /// Whether calls are currently being failed fast.
+    pub fn is_open(&self) -> bool {
+        match self.state.lock().unwrap().phase {
+            Phase::Closed => false,
+            Phase::Open { since } => self.clock.now() < since + self.cooldown,
+            Phase::HalfOpen { trial_in_flight } => trial_in_flight,
+        }
+    }
+
+    /// Lets a call through, unless it should fail fast. Once the cooldown has passed, only the
+    /// first call is let through, as the trial.
+    pub fn admit(&self) -> Option<Admitted<'_, C>> {
+        let mut state = self.state.lock().unwrap();
+        let trial = match state.phase {
+            Phase::Closed => false,
+            Phase::Open { since } if self.clock.now() >= since + self.cooldown => true,
+            Phase::HalfOpen { trial_in_flight: false } => true,
+            Phase::Open { .. } | Phase::HalfOpen { trial_in_flight: true } => return None,
+        };
+        if trial {
+            state.phase = Phase::HalfOpen { trial_in_flight: true };
+        }
+        Some(Admitted { breaker: self, trial, recorded: false })
+    }
+
+    fn record(&self, trial: bool, failed: bool) {
+        let mut state = self.state.lock().unwrap();
+        if trial {
+            state.phase = match failed {
+                true => Phase::Open { since: self.clock.now() },
+                false => Phase::Closed,
+            };
+            return;
+        }
+        if !matches!(state.phase, Phase::Closed) {
+            // let through before the breaker opened, so it says nothing about the trial
+            return;
+        }
+        state.outcomes.push_back(failed);
+        if state.outcomes.len() > self.window {
+            state.outcomes.pop_front();
+        }
+        if state.outcomes.iter().filter(|failed| **failed).count() >= self.failures {
+            state.outcomes.clear();
+            state.phase = Phase::Open { since: self.clock.now() };
+        }
+    }
+}
+
+/// A call let through by a `Breaker`, which reports how it went with `record`. A trial dropped
+/// without recording, e.g. because the call was cancelled, lets the next call be the trial.
+pub struct Admitted<'b, C: Clock> {
+    breaker: &'b Breaker<C>,
+    trial: bool,
+    recorded: bool,
+}
+
+impl<C: Clock> Admitted<'_, C> {
+    /// Whether this is the trial call after the cooldown.
+    pub fn is_trial(&self) -> bool {
+        self.trial
+    }
+
+    pub fn record(mut self, failed: bool) {
+        self.recorded = true;
+        self.breaker.record(self.trial, failed);
+    }
+}
+
+impl<C: Clock> Drop for Admitted<'_, C> {
+    fn drop(&mut self) {
+        if self.trial && !self.recorded {
+            let mut state = self.breaker.state.lock().unwrap();
+            if let Phase::HalfOpen { trial_in_flight } = &mut state.phase {
+                *trial_in_flight = false;
+            }
+        }
+    }
+}
+
+/// Provides the breaker shared by every run of a `CircuitBreaker` pipeline.
+pub trait CircuitBreak {
+    type Clock: Clock + Sync + 'static;
+
+    fn breaker() -> &'static Breaker<Self::Clock>;
+}
+
+/// Returned instead of calling the stage while its breaker is open.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BreakerOpen;
+
+impl fmt::Display for BreakerOpen {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("circuit breaker is open")
+    }
+}
+
+impl Error for BreakerOpen {}
+
+/// Output of a stage guarded by a `CircuitBreaker`, which needs to tell failures apart and be
+/// able to stand in a failure of its own when the breaker is open.
+pub trait Outcome {
+    fn is_failure(&self) -> bool;
+
+    fn open() -> Self;
+}
+
+impl<V, E: From<BreakerOpen>> Outcome for Result<V, E> {
+    fn is_failure(&self) -> bool {
+        self.is_err()
+    }
+
+    fn open() -> Self {
+        Err(BreakerOpen.into())
+    }
+}
+
+/// Wraps an async chain so that link `N` goes through `T::breaker()`, which stops calling it for
+/// a while once it keeps failing. Every other link behaves as usual.
+pub struct CircuitBreaker<T, const N: usize>(T);
+
+impl<T: Length, const N: usize> Length for CircuitBreaker<T, N> {
+    type Len = T::Len;
+}
+
+impl<const M: usize, const N: usize, T> AsyncChain<M> for CircuitBreaker<T, N>
+where
+    (): Select<M, N>,
+    T: AsyncChain<M> + OrBreak<M, <() as Select<M, N>>::Is>,
+    Self: InRange<M, Self::Len>,
+{
+    type In<'a> = <T as AsyncChain<M>>::In<'a>;
+    type Out<'a> = <T as AsyncChain<M>>::Out<'a>;
+
+    fn chain(input: Self::In<'_>) -> impl Future<Output = Self::Out<'_>> + Send {
+        <T as OrBreak<M, <() as Select<M, N>>::Is>>::or_break(input)
+    }
+}
+
+/// Implementation detail of `CircuitBreaker`, going through the breaker only for the selected link.
+pub trait OrBreak<const M: usize, Is>: AsyncChain<M>
+where
+    Self: InRange<M, <Self as Length>::Len>,
+{
+    fn or_break(input: Self::In<'_>) -> impl Future<Output = Self::Out<'_>> + Send;
+}
+
+impl<const M: usize, T: AsyncChain<M>> OrBreak<M, No> for T
+where
+    T: InRange<M, <T as Length>::Len>,
+{
+    fn or_break(input: Self::In<'_>) -> impl Future<Output = Self::Out<'_>> + Send {
+        <T as AsyncChain<M>>::chain(input)
+    }
+}
+
+impl<const M: usize, T: AsyncChain<M> + CircuitBreak> OrBreak<M, Yes> for T
+where
+    T: InRange<M, <T as Length>::Len>,
+    for<'a> T::Out<'a>: Outcome,
+{
+    fn or_break(input: Self::In<'_>) -> impl Future<Output = Self::Out<'_>> + Send {
+        let admitted = T::breaker().admit();
+        let stage = admitted.is_some().then(|| <T as AsyncChain<M>>::chain(input));
+        async move {
+            let (Some(admitted), Some(stage)) = (admitted, stage) else {
+                return Outcome::open();
+            };
+            let out = stage.await;
+            admitted.record(out.is_failure());
+            out
+        }
+    }
+}
@@ -0,0 +1,126 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Condvar, Mutex, OnceLock};
+use std::task::{Context, Poll, Waker};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Source of time for anything that needs to wait or measure, so tests can swap in a
+/// `ManualClock` instead of actually sleeping.
+pub trait Clock {
+    /// Time elapsed since some fixed point, which only needs to be consistent per clock.
+    fn now(&self) -> Duration;
+
+    fn sleep(&self, duration: Duration) -> impl Future<Output = ()> + Send;
+}
+
+impl<C: Clock> Clock for &C {
+    fn now(&self) -> Duration {
+        (**self).now()
+    }
+
+    fn sleep(&self, duration: Duration) -> impl Future<Output = ()> + Send {
+        (**self).sleep(duration)
+    }
+}
+
+/// Wall clock time. Sleeping doesn't rely on any particular runtime: every sleep is woken by a
+/// single timer thread shared across the process, started by the first sleep.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Duration {
+        static START: OnceLock<Instant> = OnceLock::new();
+        START.get_or_init(Instant::now).elapsed()
+    }
+
+    fn sleep(&self, duration: Duration) -> impl Future<Output = ()> + Send {
+        Sleep { until: Instant::now() + duration }
+    }
+}
+
+struct Sleep {
+    until: Instant,
+}
+
+impl Future for Sleep {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        if Instant::now() >= self.until {
+            return Poll::Ready(());
+        }
+        Timer::get().wake_at(self.until, cx.waker().clone());
+        Poll::Pending
+    }
+}
+
+/// Wakes sleeping futures once their deadline passes.
+struct Timer {
+    sleepers: Mutex<Vec<(Instant, Waker)>>,
+    changed: Condvar,
+}
+
+impl Timer {
+    fn get() -> &'static Timer {
+        static TIMER: OnceLock<Timer> = OnceLock::new();
+        TIMER.get_or_init(|| {
+            thread::spawn(|| Timer::get().run());
+            Timer { sleepers: Mutex::new(Vec::new()), changed: Condvar::new() }
+        })
+    }
+
+    fn wake_at(&self, until: Instant, waker: Waker) {
+        self.sleepers.lock().unwrap().push((until, waker));
+        self.changed.notify_one();
+    }
+
+    fn run(&self) {
+        let mut sleepers = self.sleepers.lock().unwrap();
+        loop {
+            let now = Instant::now();
+            let (due, waiting) = sleepers.drain(..).partition::<Vec<_>, _>(|(until, _)| *until <= now);
+            *sleepers = waiting;
+            if !due.is_empty() {
+                // woken without the lock held, in case waking polls the future right away
+                drop(sleepers);
+                due.into_iter().for_each(|(_, waker)| waker.wake());
+                sleepers = self.sleepers.lock().unwrap();
+                continue;
+            }
+            sleepers = match sleepers.iter().map(|(until, _)| *until).min() {
+                Some(next) => self.changed.wait_timeout(sleepers, next - now).unwrap().0,
+                None => self.changed.wait(sleepers).unwrap(),
+            };
+        }
+    }
+}
+
+/// Clock that only moves when told to. Sleeping advances it immediately, so anything waiting on
+/// it finishes instantly while still observing the time it would have taken.
+#[derive(Debug, Default)]
+pub struct ManualClock {
+    nanos: AtomicU64,
+}
+
+impl ManualClock {
+    pub const fn new() -> Self {
+        Self { nanos: AtomicU64::new(0) }
+    }
+
+    pub fn advance(&self, duration: Duration) {
+        self.nanos.fetch_add(duration.as_nanos() as u64, Ordering::SeqCst);
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> Duration {
+        Duration::from_nanos(self.nanos.load(Ordering::SeqCst))
+    }
+
+    async fn sleep(&self, duration: Duration) {
+        self.advance(duration);
+    }
+}
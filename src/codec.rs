@@ -0,0 +1,171 @@
+use std::error::Error;
+use std::fmt;
+
+/// Minimal binary encoding for anything that needs to leave the process, like snapshots of
+/// pipeline state. Values are written back to back with no framing or versioning, so decoding
+/// has to happen in the same order as encoding.
+///
+/// Values of types that aren't zero-sized have to take up at least one byte, which is what lets
+/// collections reject lengths the remaining bytes couldn't possibly hold.
+pub trait Codec: Sized {
+    fn encode(&self, out: &mut Vec<u8>);
+
+    /// Decodes a value from the front of `bytes`, advancing it past what was read.
+    fn decode(bytes: &mut &[u8]) -> Result<Self, DecodeError>;
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        self.encode(&mut out);
+        out
+    }
+
+    /// Decodes a value that has to take up all of `bytes`.
+    fn from_bytes(mut bytes: &[u8]) -> Result<Self, DecodeError> {
+        let value = Self::decode(&mut bytes)?;
+        match bytes.is_empty() {
+            true => Ok(value),
+            false => Err(DecodeError),
+        }
+    }
+}
+
+/// The bytes ran out or didn't make sense for the type being decoded.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DecodeError;
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("invalid or truncated encoding")
+    }
+}
+
+impl Error for DecodeError {}
+
+fn take<'a>(bytes: &mut &'a [u8], len: usize) -> Result<&'a [u8], DecodeError> {
+    if bytes.len() < len {
+        return Err(DecodeError);
+    }
+    let (head, tail) = bytes.split_at(len);
+    *bytes = tail;
+    Ok(head)
+}
+
+macro_rules! impl_codec_num {
+    ($($num:ty),*) => {$(
+        impl Codec for $num {
+            fn encode(&self, out: &mut Vec<u8>) {
+                out.extend_from_slice(&self.to_le_bytes());
+            }
+
+            fn decode(bytes: &mut &[u8]) -> Result<Self, DecodeError> {
+                let bytes = take(bytes, size_of::<$num>())?;
+                Ok(<$num>::from_le_bytes(bytes.try_into().unwrap()))
+            }
+        }
+    )*};
+}
+
+impl_codec_num!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128, f32, f64);
+
+// always 8 bytes so the encoding doesn't depend on the platform
+impl Codec for usize {
+    fn encode(&self, out: &mut Vec<u8>) {
+        (*self as u64).encode(out);
+    }
+
+    fn decode(bytes: &mut &[u8]) -> Result<Self, DecodeError> {
+        usize::try_from(u64::decode(bytes)?).map_err(|_| DecodeError)
+    }
+}
+
+impl Codec for bool {
+    fn encode(&self, out: &mut Vec<u8>) {
+        out.push(*self as u8);
+    }
+
+    fn decode(bytes: &mut &[u8]) -> Result<Self, DecodeError> {
+        match u8::decode(bytes)? {
+            0 => Ok(false),
+            1 => Ok(true),
+            _ => Err(DecodeError),
+        }
+    }
+}
+
+impl Codec for String {
+    fn encode(&self, out: &mut Vec<u8>) {
+        self.len().encode(out);
+        out.extend_from_slice(self.as_bytes());
+    }
+
+    fn decode(bytes: &mut &[u8]) -> Result<Self, DecodeError> {
+        let len = usize::decode(bytes)?;
+        let utf8 = take(bytes, len)?;
+        String::from_utf8(utf8.to_vec()).map_err(|_| DecodeError)
+    }
+}
+
+/// Most zero-sized items a `Vec` decodes, so a made up length can't keep decoding forever.
+pub const MAX_ZERO_SIZED_ITEMS: usize = 1 << 16;
+
+impl<T: Codec> Codec for Vec<T> {
+    fn encode(&self, out: &mut Vec<u8>) {
+        self.len().encode(out);
+        for item in self {
+            item.encode(out);
+        }
+    }
+
+    fn decode(bytes: &mut &[u8]) -> Result<Self, DecodeError> {
+        let len = usize::decode(bytes)?;
+        // zero-sized items take up no bytes, so there's nothing to check their count against
+        let limit = match size_of::<T>() {
+            0 => MAX_ZERO_SIZED_ITEMS,
+            _ => bytes.len(),
+        };
+        if len > limit {
+            return Err(DecodeError);
+        }
+        // don't trust the length enough to preallocate with it
+        let mut items = Vec::new();
+        for _ in 0..len {
+            items.push(T::decode(bytes)?);
+        }
+        Ok(items)
+    }
+}
+
+impl<T: Codec> Codec for Option<T> {
+    fn encode(&self, out: &mut Vec<u8>) {
+        self.is_some().encode(out);
+        if let Some(value) = self {
+            value.encode(out);
+        }
+    }
+
+    fn decode(bytes: &mut &[u8]) -> Result<Self, DecodeError> {
+        match bool::decode(bytes)? {
+            true => Ok(Some(T::decode(bytes)?)),
+            false => Ok(None),
+        }
+    }
+}
+
+impl Codec for () {
+    fn encode(&self, _: &mut Vec<u8>) {}
+
+    fn decode(_: &mut &[u8]) -> Result<Self, DecodeError> {
+        Ok(())
+    }
+}
+
+impl<A: Codec, B: Codec> Codec for (A, B) {
+    fn encode(&self, out: &mut Vec<u8>) {
+        self.0.encode(out);
+        self.1.encode(out);
+    }
+
+    fn decode(bytes: &mut &[u8]) -> Result<Self, DecodeError> {
+        Ok((A::decode(bytes)?, B::decode(bytes)?))
+    }
+}
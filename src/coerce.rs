@@ -0,0 +1,91 @@
+use std::error::Error;
+use std::fmt;
+
+use crate::Cascade;
+
+/// Returned when an input can't be made into the first link's input type at all.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CoerceError {
+    pub input: String,
+    pub target: &'static str,
+}
+
+impl fmt::Display for CoerceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "can't coerce `{}` into {}", self.input, self.target)
+    }
+}
+
+impl Error for CoerceError {}
+
+/// A value made from a loosely typed source, along with a warning if it doesn't hold exactly
+/// what the source did.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Coerced<T> {
+    pub value: T,
+    pub warning: Option<String>,
+}
+
+/// Best effort conversion from a loosely typed `S`, like the text of a number.
+pub trait CoerceFrom<S>: Sized {
+    fn coerce_from(source: S) -> Result<Coerced<Self>, CoerceError>;
+}
+
+impl<T> CoerceFrom<T> for T {
+    fn coerce_from(source: T) -> Result<Coerced<T>, CoerceError> {
+        Ok(Coerced { value: source, warning: None })
+    }
+}
+
+macro_rules! impl_coerce_number {
+    ($($t:ty)*) => {$(
+        /// Parses the trimmed text as a number and converts it, saturating if it's out of range.
+        /// The conversion is lossy if printing the result doesn't give back the same number.
+        impl CoerceFrom<&str> for $t {
+            fn coerce_from(source: &str) -> Result<Coerced<Self>, CoerceError> {
+                let error = || CoerceError { input: source.to_owned(), target: stringify!($t) };
+                let text = source.trim();
+                let parsed: f64 = text.parse().map_err(|_| error())?;
+                let value = text.parse::<$t>().unwrap_or(parsed as $t);
+                let exact = value.to_string().parse::<f64>() == Ok(parsed);
+                let warning = (!exact).then(|| {
+                    format!("coerced `{source}` into {} {value}, losing precision", stringify!($t))
+                });
+                Ok(Coerced { value, warning })
+            }
+        }
+
+        impl CoerceFrom<String> for $t {
+            fn coerce_from(source: String) -> Result<Coerced<Self>, CoerceError> {
+                <$t>::coerce_from(source.as_str())
+            }
+        }
+    )*};
+}
+
+impl_coerce_number!(f32 f64 i8 i16 i32 i64 i128 isize u8 u16 u32 u64 u128 usize);
+
+/// Opts a cascade in to `cascade_lenient`.
+pub trait Lenient {
+    /// Called with the warning whenever an input was coerced lossily, e.g. to log it. Does
+    /// nothing by default.
+    fn coerced(_: &str) {}
+}
+
+pub trait LenientCascade: Cascade + Lenient {
+    /// Coerces a loosely typed `input` into the first link's input type and cascades it, passing
+    /// a warning to `Lenient::coerced` if the coercion was lossy instead of giving up. Only input
+    /// that can't be coerced at all is an error.
+    fn cascade_lenient<'a, S>(input: S) -> Result<Self::Out<'a>, CoerceError>
+    where
+        Self::In<'a>: CoerceFrom<S>,
+    {
+        let coerced = Self::In::<'a>::coerce_from(input)?;
+        if let Some(warning) = &coerced.warning {
+            Self::coerced(warning);
+        }
+        Ok(Self::cascade(coerced.value))
+    }
+}
+
+impl<T: Cascade + Lenient> LenientCascade for T {}
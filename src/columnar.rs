@@ -0,0 +1,80 @@
+use std::sync::Arc;
+
+use arrow::array::{Array, ArrayRef, BooleanArray};
+use arrow::compute::filter_record_batch;
+use arrow::datatypes::{Field, Schema};
+use arrow::error::ArrowError;
+use arrow::record_batch::RecordBatch;
+
+/// Column-at-a-time helpers for stages working on Arrow record batches, so that a pipeline over
+/// `RecordBatch` can transform whole columns with Arrow's vectorized kernels instead of going row
+/// by row. Every helper returns a new batch, leaving the original one as it was.
+pub trait Columnar: Sized {
+    /// The column called `name`, downcast to its concrete array type.
+    fn column_as<A: Array + 'static>(&self, name: &str) -> Result<&A, ArrowError>;
+
+    /// Replaces column `name` with what `transform` makes of it, which may change its type.
+    fn map_column(
+        &self,
+        name: &str,
+        transform: impl FnOnce(&ArrayRef) -> Result<ArrayRef, ArrowError>,
+    ) -> Result<Self, ArrowError>;
+
+    /// Adds `array` as column `name` after the existing ones, or replaces the column if there's
+    /// already one called that.
+    fn with_column(&self, name: &str, array: ArrayRef) -> Result<Self, ArrowError>;
+
+    /// Keeps only the rows `predicate` marks as true. Rows it leaves null are dropped too.
+    fn filter_rows(
+        &self,
+        predicate: impl FnOnce(&Self) -> Result<BooleanArray, ArrowError>,
+    ) -> Result<Self, ArrowError>;
+}
+
+impl Columnar for RecordBatch {
+    fn column_as<A: Array + 'static>(&self, name: &str) -> Result<&A, ArrowError> {
+        let column = self
+            .column_by_name(name)
+            .ok_or_else(|| ArrowError::SchemaError(format!("no column named `{name}`")))?;
+        column.as_any().downcast_ref().ok_or_else(|| {
+            ArrowError::CastError(format!("column `{name}` is {}, not the requested array type", column.data_type()))
+        })
+    }
+
+    fn map_column(
+        &self,
+        name: &str,
+        transform: impl FnOnce(&ArrayRef) -> Result<ArrayRef, ArrowError>,
+    ) -> Result<Self, ArrowError> {
+        let index = self.schema().index_of(name)?;
+        let array = transform(self.column(index))?;
+        self.with_column(name, array)
+    }
+
+    fn with_column(&self, name: &str, array: ArrayRef) -> Result<Self, ArrowError> {
+        let schema = self.schema();
+        let field = Arc::new(Field::new(name, array.data_type().clone(), array.null_count() > 0));
+        let mut fields: Vec<_> = schema.fields().iter().cloned().collect();
+        let mut columns = self.columns().to_vec();
+        match schema.index_of(name) {
+            Ok(index) => {
+                let nullable = field.is_nullable() || fields[index].is_nullable();
+                fields[index] = Arc::new(field.as_ref().clone().with_nullable(nullable));
+                columns[index] = array;
+            }
+            Err(_) => {
+                fields.push(field);
+                columns.push(array);
+            }
+        }
+        let schema = Schema::new_with_metadata(fields, schema.metadata().clone());
+        RecordBatch::try_new(Arc::new(schema), columns)
+    }
+
+    fn filter_rows(
+        &self,
+        predicate: impl FnOnce(&Self) -> Result<BooleanArray, ArrowError>,
+    ) -> Result<Self, ArrowError> {
+        filter_record_batch(self, &predicate(self)?)
+    }
+}
@@ -0,0 +1,80 @@
+use std::marker::PhantomData;
+
+use crate::{Cascade, Chain, Length, L};
+
+/// Evidence that an `A` can be used where a `B` is expected, supplied by hand where the compiler
+/// can't see it on its own, like when `A` is the output of one generic pipeline and `B` the input
+/// of another. `Refl` proves any type is itself, and newtypes can prove they're
+/// interchangeable with what they wrap by implementing this.
+pub trait Witness<A, B> {
+    fn coerce(a: A) -> B;
+}
+
+/// The witness that every type is itself, which type checks as soon as both sides are known to
+/// be the same type.
+pub struct Refl;
+
+impl<T> Witness<T, T> for Refl {
+    fn coerce(a: T) -> T {
+        a
+    }
+}
+
+/// `A` followed by `B` as a single one-link `Cascade`, with the handoff in between going through
+/// the witness `W`. Useful in generic code, where `A::Out` and `B::In` are only associated types,
+/// so nothing says they line up until the caller picks the pipelines and their witness:
+///
+/// ```
+/// use chain_link::*;
+///
+/// fn both<A, B, W>(input: A::In<'_>) -> B::Out<'_>
+/// where
+///     A: Cascade,
+///     B: Cascade,
+///     W: for<'a> Witness<A::Out<'a>, B::In<'a>>,
+/// {
+///     Composed::<A, B, W>::cascade(input)
+/// }
+/// ```
+///
+/// Pipelines that don't line up don't have a `Refl` witness:
+///
+/// ```compile_fail
+/// use chain_link::*;
+///
+/// struct Len;
+///
+/// impl Chain<0> for Len {
+///     type In<'a> = &'a str;
+///     type Out<'a> = usize;
+///
+///     fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+///         input.len()
+///     }
+/// }
+///
+/// impl Length for Len {
+///     type Len = L<1>;
+/// }
+///
+/// Composed::<Len, Len, Refl>::cascade("abc");
+/// ```
+pub struct Composed<A, B, W = Refl>(PhantomData<(A, B, W)>);
+
+impl<A, B, W> Length for Composed<A, B, W> {
+    type Len = L<1>;
+}
+
+impl<A, B, W> Chain<0> for Composed<A, B, W>
+where
+    A: Cascade,
+    B: Cascade,
+    W: for<'a> Witness<A::Out<'a>, B::In<'a>>,
+{
+    type In<'a> = A::In<'a>;
+    type Out<'a> = B::Out<'a>;
+
+    fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+        B::cascade(W::coerce(A::cascade(input)))
+    }
+}
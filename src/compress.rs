@@ -0,0 +1,41 @@
+use std::io::{Read, Write};
+
+use flate2::read::DeflateDecoder;
+use flate2::write::DeflateEncoder;
+use flate2::Compression;
+
+use crate::{DecodeError, SplitCascade};
+
+/// Most bytes `resume_from_pivot_compressed` inflates a pivot to, so a tiny payload can't
+/// decompress to gigabytes.
+pub const MAX_PIVOT_LEN: usize = 64 << 20;
+
+/// `SplitCascade` with the pivot deflated in between, for sending it over the network.
+pub trait CompressedSplitCascade<const P: usize>: SplitCascade<P> {
+    fn cascade_to_pivot_compressed(input: Self::In<'_>) -> Vec<u8> {
+        let pivot = Self::cascade_to_pivot(input);
+        let mut encoder = DeflateEncoder::new(Vec::new(), Compression::default());
+        // writing to a `Vec` can't fail
+        encoder.write_all(&pivot).unwrap();
+        encoder.finish().unwrap()
+    }
+
+    /// Fails if the pivot inflates to more than `MAX_PIVOT_LEN` bytes.
+    fn resume_from_pivot_compressed(pivot: &[u8]) -> Result<Self::Out<'static>, DecodeError> {
+        Self::resume_from_pivot_compressed_within(pivot, MAX_PIVOT_LEN)
+    }
+
+    /// Same as `resume_from_pivot_compressed`, but fails if the pivot inflates to more than
+    /// `limit` bytes instead.
+    fn resume_from_pivot_compressed_within(pivot: &[u8], limit: usize) -> Result<Self::Out<'static>, DecodeError> {
+        let mut decompressed = Vec::new();
+        let mut decoder = DeflateDecoder::new(pivot).take((limit as u64).saturating_add(1));
+        decoder.read_to_end(&mut decompressed).map_err(|_| DecodeError)?;
+        if decompressed.len() > limit {
+            return Err(DecodeError);
+        }
+        Self::resume_from_pivot(&decompressed)
+    }
+}
+
+impl<const P: usize, T: SplitCascade<P>> CompressedSplitCascade<P> for T {}
@@ -0,0 +1,72 @@
+use seq_macro::seq;
+
+use crate::{InRange, Length, L};
+
+/// A `Chain<N>` that also gets to read a `Context` shared by the whole cascade, for settings
+/// that aren't part of the data but that links still need, like a locale. Every link of a chain
+/// has to agree on the same `Context`.
+pub trait ContextChain<const N: usize>
+where
+    Self: InRange<N, <Self as Length>::Len>,
+{
+    type Context;
+    type In<'a>;
+    type Out<'a>;
+
+    fn chain<'a>(context: &Self::Context, input: Self::In<'a>) -> Self::Out<'a>;
+}
+
+pub trait ContextLink<const N: usize> {
+    type Context;
+    type In<'a>;
+    type Out<'a>;
+
+    fn context_link<'a>(context: &Self::Context, input: Self::In<'a>) -> Self::Out<'a>;
+}
+
+impl<T: ContextChain<0>> ContextLink<1> for T {
+    type Context = <T as ContextChain<0>>::Context;
+    type In<'a> = <T as ContextChain<0>>::In<'a>;
+    type Out<'a> = <T as ContextChain<0>>::Out<'a>;
+
+    fn context_link<'a>(context: &Self::Context, input: Self::In<'a>) -> Self::Out<'a> {
+        <T as ContextChain<0>>::chain(context, input)
+    }
+}
+
+seq!(N in 2..=32 {
+    impl<T> ContextLink<N> for T
+    where
+        T: ContextChain<0>,
+        for<'a> T: ContextLink<{N - 1}, Context = <T as ContextChain<0>>::Context, In<'a> = <T as ContextChain<0>>::In<'a>>,
+        for<'a> T: ContextChain<{N - 1}, Context = <T as ContextChain<0>>::Context, In<'a> = <T as ContextLink<{N - 1}>>::Out<'a>>,
+    {
+        type Context = <T as ContextChain<0>>::Context;
+        type In<'a> = <T as ContextChain<0>>::In<'a>;
+        type Out<'a> = <T as ContextChain<{N - 1}>>::Out<'a>;
+
+        fn context_link<'a>(context: &Self::Context, input: Self::In<'a>) -> Self::Out<'a> {
+            let out = <T as ContextLink<{N - 1}>>::context_link(context, input);
+            <T as ContextChain<{N - 1}>>::chain(context, out)
+        }
+    }
+});
+
+pub trait ContextCascade {
+    type Context;
+    type In<'a>;
+    type Out<'a>;
+
+    /// Cascades `input`, handing every link the same `context`.
+    fn cascade_with<'a>(context: &Self::Context, input: Self::In<'a>) -> Self::Out<'a>;
+}
+
+impl<const N: usize, T: ContextLink<N> + Length<Len = L<N>>> ContextCascade for T {
+    type Context = <T as ContextLink<N>>::Context;
+    type In<'a> = <T as ContextLink<N>>::In<'a>;
+    type Out<'a> = <T as ContextLink<N>>::Out<'a>;
+
+    fn cascade_with<'a>(context: &Self::Context, input: Self::In<'a>) -> Self::Out<'a> {
+        <T as ContextLink<N>>::context_link(context, input)
+    }
+}
@@ -0,0 +1,82 @@
+use seq_macro::seq;
+
+use crate::{Cascade, Chain, InRange, Length, Link, Named, L};
+
+/// Preconditions and postconditions of a `Chain<N>`, checked around the link by
+/// `cascade_checked` in debug builds. Each returns the name of the contract that doesn't hold, and
+/// both hold by default, so `impl Contract<N> for T {}` is enough for links without any.
+pub trait Contract<const N: usize>: Chain<N>
+where
+    Self: InRange<N, <Self as Length>::Len>,
+{
+    fn requires(_input: &Self::In<'_>) -> Result<(), &'static str> {
+        Ok(())
+    }
+
+    fn ensures(_output: &Self::Out<'_>) -> Result<(), &'static str> {
+        Ok(())
+    }
+}
+
+fn stage<const N: usize, T: Named>() -> String {
+    match T::stage_name(N) {
+        Some(name) => format!("stage {N} ({name})"),
+        None => format!("stage {N}"),
+    }
+}
+
+fn check<'a, const N: usize, T>(input: T::In<'a>) -> T::Out<'a>
+where
+    T: Contract<N> + Named + InRange<N, <T as Length>::Len>,
+{
+    if cfg!(debug_assertions) {
+        if let Err(contract) = T::requires(&input) {
+            panic!("{}: precondition `{contract}` failed", stage::<N, T>());
+        }
+    }
+    let out = T::chain(input);
+    if cfg!(debug_assertions) {
+        if let Err(contract) = T::ensures(&out) {
+            panic!("{}: postcondition `{contract}` failed", stage::<N, T>());
+        }
+    }
+    out
+}
+
+/// Same as `Link<N>`, but checks the contracts of every link.
+pub trait ContractLink<const N: usize>: Link<N> {
+    fn contract_link(input: Self::In<'_>) -> Self::Out<'_>;
+}
+
+impl<T: Contract<0> + Named> ContractLink<1> for T {
+    fn contract_link(input: Self::In<'_>) -> Self::Out<'_> {
+        check::<0, T>(input)
+    }
+}
+
+seq!(N in 2..=32 {
+    impl<T> ContractLink<N> for T
+    where
+        T: Chain<0> + Named,
+        for<'a> T: ContractLink<{N - 1}, In<'a> = <T as Chain<0>>::In<'a>>,
+        for<'a> T: Contract<{N - 1}, In<'a> = <T as Link<{N - 1}>>::Out<'a>>,
+    {
+        fn contract_link(input: Self::In<'_>) -> Self::Out<'_> {
+            let out = <T as ContractLink<{N - 1}>>::contract_link(input);
+            check::<{N - 1}, T>(out)
+        }
+    }
+});
+
+pub trait ContractedCascade: Cascade {
+    /// Cascades as usual, but in debug builds panics as soon as a link's precondition or
+    /// postcondition doesn't hold, naming the link and the contract. Release builds skip the
+    /// checks entirely.
+    fn cascade_checked(input: Self::In<'_>) -> Self::Out<'_>;
+}
+
+impl<const N: usize, T: ContractLink<N> + Length<Len = L<N>>> ContractedCascade for T {
+    fn cascade_checked(input: Self::In<'_>) -> Self::Out<'_> {
+        <T as ContractLink<N>>::contract_link(input)
+    }
+}
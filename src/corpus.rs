@@ -0,0 +1,101 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::{Cascade, Codec, DecodeError};
+
+/// One recorded run of a whole cascade: its encoded input and what it output.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CorpusEntry {
+    pub input: Vec<u8>,
+    pub output: Vec<u8>,
+}
+
+impl Codec for CorpusEntry {
+    fn encode(&self, out: &mut Vec<u8>) {
+        self.input.encode(out);
+        self.output.encode(out);
+    }
+
+    fn decode(bytes: &mut &[u8]) -> Result<Self, DecodeError> {
+        Ok(Self { input: Codec::decode(bytes)?, output: Codec::decode(bytes)? })
+    }
+}
+
+/// Real inputs captured from production along with what the cascade made of them, to be replayed
+/// in tests with `replay_corpus`. Anything sensitive should be scrubbed from an input before it's
+/// recorded, since the recorded output is of the scrubbed input.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Corpus {
+    pub entries: Vec<CorpusEntry>,
+}
+
+impl Corpus {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cascades `input` as usual, adding it and its output to the corpus.
+    pub fn record<'a, T>(&mut self, input: T::In<'a>) -> T::Out<'a>
+    where
+        T: Cascade,
+        T::In<'a>: Codec,
+        T::Out<'a>: Codec,
+    {
+        let encoded = input.to_bytes();
+        let out = T::cascade(input);
+        self.entries.push(CorpusEntry { input: encoded, output: out.to_bytes() });
+        out
+    }
+
+    /// Cascades every recorded input again, returning the indexes of the entries whose output
+    /// changed, or whose input no longer decodes.
+    pub fn replay<T>(&self) -> Vec<usize>
+    where
+        T: Cascade,
+        T::In<'static>: Codec,
+        T::Out<'static>: Codec,
+    {
+        let matches = |entry: &CorpusEntry| match T::In::from_bytes(&entry.input) {
+            Ok(input) => T::cascade(input).to_bytes() == entry.output,
+            Err(_) => false,
+        };
+        (0..self.entries.len()).filter(|&i| !matches(&self.entries[i])).collect()
+    }
+
+    /// Decodes every recorded input, e.g. to run them through another pipeline with
+    /// `diff_versions`.
+    pub fn inputs<A: Codec>(&self) -> Result<Vec<A>, DecodeError> {
+        self.entries.iter().map(|entry| A::from_bytes(&entry.input)).collect()
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        fs::write(path, self.to_bytes())
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        let bytes = fs::read(path)?;
+        Self::from_bytes(&bytes).map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))
+    }
+}
+
+impl Codec for Corpus {
+    fn encode(&self, out: &mut Vec<u8>) {
+        self.entries.encode(out);
+    }
+
+    fn decode(bytes: &mut &[u8]) -> Result<Self, DecodeError> {
+        Ok(Self { entries: Codec::decode(bytes)? })
+    }
+}
+
+/// Loads the corpus saved at `path` and replays it against `T`, returning the indexes of the
+/// entries that regressed. Meant to be called from a test with an `assert!(...is_empty())`.
+pub fn replay_corpus<T>(path: impl AsRef<Path>) -> io::Result<Vec<usize>>
+where
+    T: Cascade,
+    T::In<'static>: Codec,
+    T::Out<'static>: Codec,
+{
+    Ok(Corpus::load(path)?.replay::<T>())
+}
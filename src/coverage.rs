@@ -0,0 +1,77 @@
+use std::any::type_name;
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::hash::Hash;
+use std::marker::PhantomData;
+
+use crate::type_flow::strip_paths;
+use crate::{Observer, RouteOrDefault, StageInfo, TypeFlow};
+
+/// Counts how often every stage was exercised across any number of cascades, e.g. a whole test
+/// suite, to find the ones that never were. Stages are identified by name, `Pipeline[1]` for link
+/// 1 of `Pipeline` and the key for a route, and only stages registered with one of the `expect`
+/// methods can be reported as missed.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct StageCoverage {
+    hits: BTreeMap<String, usize>,
+}
+
+impl StageCoverage {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn expect(&mut self, stage: impl Into<String>) {
+        self.hits.entry(stage.into()).or_default();
+    }
+
+    /// Expects every link of `T`.
+    pub fn expect_pipeline<T: TypeFlow>(&mut self) {
+        let pipeline = strip_paths(type_name::<T>());
+        for stage in T::stage_infos() {
+            self.expect(format!("{pipeline}[{}]", stage.index));
+        }
+    }
+
+    /// Expects every route of `router`, and its default as `default`.
+    pub fn expect_routes<K, I, O, R>(&mut self, router: &RouteOrDefault<K, I, O, R>)
+    where
+        K: Eq + Hash + Display,
+        R: Fn(&I) -> Option<K>,
+    {
+        for key in router.keys() {
+            self.expect(key.to_string());
+        }
+        self.expect("default");
+    }
+
+    pub fn hit(&mut self, stage: impl Into<String>) {
+        *self.hits.entry(stage.into()).or_default() += 1;
+    }
+
+    pub fn hits(&self, stage: &str) -> usize {
+        self.hits.get(stage).copied().unwrap_or_default()
+    }
+
+    /// Every expected stage that was never hit, sorted by name.
+    pub fn unexercised(&self) -> Vec<&str> {
+        self.hits.iter().filter(|(_, &hits)| hits == 0).map(|(stage, _)| stage.as_str()).collect()
+    }
+
+    /// Observer recording a hit for every link of `T` that runs.
+    pub fn observer<T>(&mut self) -> CoverageObserver<'_, T> {
+        CoverageObserver { pipeline: strip_paths(type_name::<T>()), coverage: self, observed: PhantomData }
+    }
+}
+
+pub struct CoverageObserver<'a, T> {
+    pipeline: String,
+    coverage: &'a mut StageCoverage,
+    observed: PhantomData<fn() -> T>,
+}
+
+impl<T> Observer for CoverageObserver<'_, T> {
+    fn before(&mut self, stage: &StageInfo) {
+        self.coverage.hit(format!("{}[{}]", self.pipeline, stage.index));
+    }
+}
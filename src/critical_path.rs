@@ -0,0 +1,212 @@
+use std::cell::RefCell;
+use std::future::Future;
+use std::mem::take;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+
+use seq_macro::seq;
+
+use crate::{AsyncChain, AsyncLink, Length, L};
+
+/// How long one arm of a `fan_out` took, from the fan-out starting until that arm finished.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ArmTiming {
+    pub name: &'static str,
+    pub elapsed: Duration,
+}
+
+/// One link along a `CriticalPath`, with the slowest arm of every fan-out it joined on, in the
+/// order they finished.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CriticalStep {
+    pub stage: usize,
+    pub elapsed: Duration,
+    pub arms: Vec<ArmTiming>,
+}
+
+/// The chain of links and fan-out arms that the total latency of a run was waiting on. Links run
+/// one after the other so all of them are on it, but only the slowest arm of each fan-out is,
+/// since speeding up any other arm wouldn't have finished the run any sooner.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CriticalPath {
+    pub steps: Vec<CriticalStep>,
+}
+
+impl CriticalPath {
+    pub fn total(&self) -> Duration {
+        self.steps.iter().map(|step| step.elapsed).sum()
+    }
+
+    /// The link that took the longest.
+    pub fn dominant(&self) -> Option<&CriticalStep> {
+        self.steps.iter().max_by_key(|step| step.elapsed)
+    }
+}
+
+thread_local! {
+    /// Slowest arms of the fan-outs finished by the link being polled, if it's being timed.
+    static ARMS: RefCell<Option<Vec<ArmTiming>>> = const { RefCell::new(None) };
+}
+
+/// One named branch of a `fan_out`.
+pub struct Arm<F: Future> {
+    name: &'static str,
+    future: Option<Pin<Box<F>>>,
+    output: Option<F::Output>,
+    elapsed: Duration,
+}
+
+// the future is boxed, and nothing else is ever pinned
+impl<F: Future> Unpin for Arm<F> {}
+
+pub fn arm<F: Future>(name: &'static str, future: F) -> Arm<F> {
+    Arm { name, future: Some(Box::pin(future)), output: None, elapsed: Duration::ZERO }
+}
+
+impl<F: Future> Arm<F> {
+    /// Polls the arm if it's still running, returning whether it's done.
+    fn poll_arm(&mut self, cx: &mut Context<'_>, started: Instant) -> bool {
+        if let Some(future) = &mut self.future {
+            let Poll::Ready(output) = future.as_mut().poll(cx) else {
+                return false;
+            };
+            self.output = Some(output);
+            self.elapsed = started.elapsed();
+            self.future = None;
+        }
+        true
+    }
+
+    fn timing(&self) -> ArmTiming {
+        ArmTiming { name: self.name, elapsed: self.elapsed }
+    }
+}
+
+/// Tuples of `Arm`s that `fan_out` can run concurrently.
+pub trait Arms: Unpin {
+    type Output;
+
+    fn poll_arms(&mut self, cx: &mut Context<'_>, started: Instant) -> Poll<Self::Output>;
+}
+
+macro_rules! impl_arms {
+    ($($F:ident $i:tt),+) => {
+        impl<$($F: Future),+> Arms for ($(Arm<$F>,)+) {
+            type Output = ($($F::Output,)+);
+
+            fn poll_arms(&mut self, cx: &mut Context<'_>, started: Instant) -> Poll<Self::Output> {
+                let mut done = true;
+                $(done &= self.$i.poll_arm(cx, started);)+
+                if !done {
+                    return Poll::Pending;
+                }
+                let slowest = [$(self.$i.timing()),+].into_iter().max_by_key(|arm| arm.elapsed);
+                ARMS.with_borrow_mut(|arms| arms.as_mut().map(|arms| arms.extend(slowest)));
+                Poll::Ready(($(self.$i.output.take().expect("fan_out polled after completion"),)+))
+            }
+        }
+    };
+}
+
+impl_arms!(A 0, B 1);
+impl_arms!(A 0, B 1, C 2);
+impl_arms!(A 0, B 1, C 2, D 3);
+
+pub struct FanOut<A> {
+    arms: A,
+    started: Option<Instant>,
+}
+
+/// Runs every arm concurrently, resolving to all of their outputs once the last one is done.
+/// Inside a link cascaded with `cascade_with_critical_path`, the slowest arm is recorded as part
+/// of the critical path.
+pub fn fan_out<A: Arms>(arms: A) -> FanOut<A> {
+    FanOut { arms, started: None }
+}
+
+impl<A: Arms> Future for FanOut<A> {
+    type Output = A::Output;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<A::Output> {
+        let this = &mut *self;
+        let started = *this.started.get_or_insert_with(Instant::now);
+        this.arms.poll_arms(cx, started)
+    }
+}
+
+/// Times a link's future, collecting the fan-outs it finishes while it's being polled.
+struct Timed<F: Future> {
+    stage: usize,
+    future: Pin<Box<F>>,
+    started: Option<Instant>,
+    arms: Vec<ArmTiming>,
+}
+
+impl<F: Future> Future for Timed<F> {
+    type Output = (F::Output, CriticalStep);
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = &mut *self;
+        let started = *this.started.get_or_insert_with(Instant::now);
+        let outer = ARMS.replace(Some(take(&mut this.arms)));
+        let poll = this.future.as_mut().poll(cx);
+        this.arms = ARMS.replace(outer).unwrap_or_default();
+
+        poll.map(|output| {
+            let step = CriticalStep { stage: this.stage, elapsed: started.elapsed(), arms: take(&mut this.arms) };
+            (output, step)
+        })
+    }
+}
+
+fn timed<F: Future>(stage: usize, future: F) -> Timed<F> {
+    Timed { stage, future: Box::pin(future), started: None, arms: Vec::new() }
+}
+
+/// Same as `AsyncLink<N>`, but times every link along the way.
+pub trait CriticalPathLink<const N: usize>: AsyncLink<N> {
+    fn critical_path_link(input: Self::In<'_>) -> impl Future<Output = (Self::Out<'_>, Vec<CriticalStep>)>;
+}
+
+impl<T: AsyncChain<0>> CriticalPathLink<1> for T {
+    async fn critical_path_link(input: Self::In<'_>) -> (Self::Out<'_>, Vec<CriticalStep>) {
+        let (out, step) = timed(0, <T as AsyncChain<0>>::chain(input)).await;
+        (out, vec![step])
+    }
+}
+
+seq!(N in 2..=32 {
+    impl<T> CriticalPathLink<N> for T
+    where
+        T: AsyncChain<0>,
+        for<'a> T: CriticalPathLink<{N - 1}, In<'a> = <T as AsyncChain<0>>::In<'a>>,
+        for<'a> T: AsyncChain<{N - 1}, In<'a> = <T as AsyncLink<{N - 1}>>::Out<'a>>,
+    {
+        async fn critical_path_link(input: Self::In<'_>) -> (Self::Out<'_>, Vec<CriticalStep>) {
+            let (out, mut steps) = <T as CriticalPathLink<{N - 1}>>::critical_path_link(input).await;
+            let (out, step) = timed(N - 1, <T as AsyncChain<{N - 1}>>::chain(out)).await;
+            steps.push(step);
+            (out, steps)
+        }
+    }
+});
+
+pub trait CriticalPathCascade {
+    type In<'a>;
+    type Out<'a>;
+
+    /// Cascades as usual, also returning the critical path of the run: how long each link took,
+    /// and which arm of every `fan_out` it was left waiting on.
+    fn cascade_with_critical_path(input: Self::In<'_>) -> impl Future<Output = (Self::Out<'_>, CriticalPath)>;
+}
+
+impl<const N: usize, T: CriticalPathLink<N> + Length<Len = L<N>>> CriticalPathCascade for T {
+    type In<'a> = <T as AsyncLink<N>>::In<'a>;
+    type Out<'a> = <T as AsyncLink<N>>::Out<'a>;
+
+    async fn cascade_with_critical_path(input: Self::In<'_>) -> (Self::Out<'_>, CriticalPath) {
+        let (out, steps) = <T as CriticalPathLink<N>>::critical_path_link(input).await;
+        (out, CriticalPath { steps })
+    }
+}
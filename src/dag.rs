@@ -0,0 +1,175 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+
+struct Stage<T> {
+    name: String,
+    dependencies: Vec<String>,
+    run: Box<dyn Fn(Vec<T>) -> T>,
+}
+
+/// Why a `DagBuilder` couldn't build its graph.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DagError {
+    /// The stages depend on each other in a loop, given in order, starting and ending with the
+    /// same stage.
+    Cycle(Vec<String>),
+    UnknownDependency { stage: String, dependency: String },
+    DuplicateStage(String),
+    /// Every graph needs exactly one stage nothing depends on, whose output is the result.
+    Outputs(Vec<String>),
+}
+
+impl fmt::Display for DagError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DagError::Cycle(stages) => write!(f, "stages depend on each other in a cycle: {}", stages.join(" -> ")),
+            DagError::UnknownDependency { stage, dependency } => {
+                write!(f, "stage `{stage}` depends on `{dependency}`, which doesn't exist")
+            }
+            DagError::DuplicateStage(stage) => write!(f, "stage `{stage}` is defined more than once"),
+            DagError::Outputs(stages) if stages.is_empty() => write!(f, "graph has no output stage"),
+            DagError::Outputs(stages) => write!(f, "graph has more than one output stage: {}", stages.join(", ")),
+        }
+    }
+}
+
+impl Error for DagError {}
+
+/// Builds a pipeline whose stages form a graph rather than a line, where each stage gets the
+/// outputs of the stages it depends on, in the order they're listed, and stages without
+/// dependencies get the cascade's input. All of them work on the same type `T`.
+///
+/// The graph is checked when it's built, so a cycle is reported as an error naming the stages
+/// involved, instead of the cascade never finishing.
+pub struct DagBuilder<T> {
+    stages: Vec<Stage<T>>,
+}
+
+impl<T> DagBuilder<T> {
+    pub fn new() -> Self {
+        Self { stages: Vec::new() }
+    }
+
+    pub fn stage(mut self, name: &str, dependencies: &[&str], run: impl Fn(Vec<T>) -> T + 'static) -> Self {
+        self.stages.push(Stage {
+            name: name.to_owned(),
+            dependencies: dependencies.iter().map(|&dependency| dependency.to_owned()).collect(),
+            run: Box::new(run),
+        });
+        self
+    }
+
+    pub fn build(self) -> Result<Dag<T>, DagError> {
+        let mut indices = HashMap::new();
+        for (index, stage) in self.stages.iter().enumerate() {
+            if indices.insert(stage.name.as_str(), index).is_some() {
+                return Err(DagError::DuplicateStage(stage.name.clone()));
+            }
+        }
+
+        let mut edges = Vec::with_capacity(self.stages.len());
+        for stage in &self.stages {
+            let dependencies = stage.dependencies.iter().map(|dependency| match indices.get(dependency.as_str()) {
+                Some(&index) => Ok(index),
+                None => Err(DagError::UnknownDependency { stage: stage.name.clone(), dependency: dependency.clone() }),
+            });
+            edges.push(dependencies.collect::<Result<Vec<_>, _>>()?);
+        }
+
+        let order = Visit::sort(&edges).map_err(|cycle| {
+            DagError::Cycle(cycle.into_iter().map(|index| self.stages[index].name.clone()).collect())
+        })?;
+
+        let mut depended_on = vec![false; self.stages.len()];
+        edges.iter().flatten().for_each(|&index| depended_on[index] = true);
+        let outputs: Vec<_> = (0..self.stages.len()).filter(|&index| !depended_on[index]).collect();
+        let [output] = outputs[..] else {
+            return Err(DagError::Outputs(outputs.into_iter().map(|index| self.stages[index].name.clone()).collect()));
+        };
+
+        Ok(Dag { stages: self.stages, edges, order, output })
+    }
+}
+
+impl<T> Default for DagBuilder<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Mark {
+    Unvisited,
+    InProgress,
+    Done,
+}
+
+/// Depth first topological sort, which finds a cycle as soon as it runs into a stage it's still
+/// in the middle of visiting.
+struct Visit<'a> {
+    edges: &'a [Vec<usize>],
+    marks: Vec<Mark>,
+    path: Vec<usize>,
+    order: Vec<usize>,
+}
+
+impl<'a> Visit<'a> {
+    /// Every stage after its dependencies, or the stages of a cycle.
+    fn sort(edges: &'a [Vec<usize>]) -> Result<Vec<usize>, Vec<usize>> {
+        let mut visit = Visit { edges, marks: vec![Mark::Unvisited; edges.len()], path: Vec::new(), order: Vec::new() };
+        for index in 0..edges.len() {
+            visit.visit(index)?;
+        }
+        Ok(visit.order)
+    }
+
+    fn visit(&mut self, index: usize) -> Result<(), Vec<usize>> {
+        match self.marks[index] {
+            Mark::Done => return Ok(()),
+            Mark::InProgress => {
+                let start = self.path.iter().position(|&on_path| on_path == index).unwrap_or_default();
+                let mut cycle = self.path[start..].to_vec();
+                cycle.push(index);
+                return Err(cycle);
+            }
+            Mark::Unvisited => {}
+        }
+        self.marks[index] = Mark::InProgress;
+        self.path.push(index);
+        for &dependency in &self.edges[index] {
+            self.visit(dependency)?;
+        }
+        self.path.pop();
+        self.marks[index] = Mark::Done;
+        self.order.push(index);
+        Ok(())
+    }
+}
+
+/// A graph of stages checked by `DagBuilder`, run one stage at a time in dependency order.
+pub struct Dag<T> {
+    stages: Vec<Stage<T>>,
+    edges: Vec<Vec<usize>>,
+    order: Vec<usize>,
+    output: usize,
+}
+
+impl<T: Clone> Dag<T> {
+    /// The stage names in the order they run.
+    pub fn order(&self) -> Vec<&str> {
+        self.order.iter().map(|&index| self.stages[index].name.as_str()).collect()
+    }
+
+    pub fn cascade(&self, input: T) -> T {
+        let mut outputs: Vec<Option<T>> = (0..self.stages.len()).map(|_| None).collect();
+        for &index in &self.order {
+            let inputs = match self.edges[index].as_slice() {
+                [] => vec![input.clone()],
+                dependencies => dependencies.iter().filter_map(|&dependency| outputs[dependency].clone()).collect(),
+            };
+            outputs[index] = Some((self.stages[index].run)(inputs));
+        }
+        outputs[self.output].take().expect("the output stage runs last")
+    }
+}
@@ -0,0 +1,42 @@
+use std::time::Duration;
+
+use crate::{AsyncCascade, Clock};
+
+/// Coalesces bursts of inputs to an async cascade: every `push` replaces whatever was pending and
+/// restarts the wait, so the cascade only runs on the last input once `interval` has passed
+/// without a new one.
+pub struct Debounce<'a, T: AsyncCascade, C: Clock> {
+    clock: C,
+    interval: Duration,
+    pending: Option<(T::In<'a>, Duration)>,
+}
+
+impl<'a, T: AsyncCascade, C: Clock> Debounce<'a, T, C> {
+    pub fn new(interval: Duration, clock: C) -> Self {
+        Self { clock, interval, pending: None }
+    }
+
+    /// Replaces the pending input, dropping the previous one without running it.
+    pub fn push(&mut self, input: T::In<'a>) {
+        self.pending = Some((input, self.clock.now() + self.interval));
+    }
+
+    pub fn is_pending(&self) -> bool {
+        self.pending.is_some()
+    }
+
+    /// Whether the pending input has been left alone for long enough to run.
+    pub fn is_settled(&self) -> bool {
+        matches!(&self.pending, Some((_, deadline)) if self.clock.now() >= *deadline)
+    }
+
+    /// Waits for the pending input to settle, then cascades it. `None` if nothing was pending.
+    pub async fn settled(&mut self) -> Option<T::Out<'a>> {
+        let (input, deadline) = self.pending.take()?;
+        let now = self.clock.now();
+        if deadline > now {
+            self.clock.sleep(deadline - now).await;
+        }
+        Some(T::async_cascade(input).await)
+    }
+}
@@ -0,0 +1,48 @@
+use crate::TypeFlow;
+
+/// 64 bit FNV-1a, picked over `DefaultHasher` since its output is the same on every platform
+/// and every release.
+struct Fnv1a(u64);
+
+impl Fnv1a {
+    fn new() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 ^= byte as u64;
+            self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+        }
+        // keeps ("ab", "c") and ("a", "bc") apart
+        self.0 ^= 0xff;
+        self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+    }
+}
+
+pub trait DefinitionHash: TypeFlow {
+    /// Hash of the input and output types of every link, for telling when a pipeline's
+    /// definition has changed, e.g. to throw away anything cached from the old one. It only sees
+    /// types, so changing what a link does without changing its types keeps the same hash, see
+    /// `definition_hash_with` for that.
+    ///
+    /// Type names come from `type_name`, which isn't guaranteed to be the same across compiler
+    /// versions, so hashes are only comparable between builds using the same compiler.
+    fn definition_hash() -> u64 {
+        Self::definition_hash_with("")
+    }
+
+    /// Same as `definition_hash`, also mixing in `metadata` like a version number that gets
+    /// bumped whenever the behavior of a link changes.
+    fn definition_hash_with(metadata: &str) -> u64 {
+        let mut hasher = Fnv1a::new();
+        hasher.write(metadata.as_bytes());
+        for stage in Self::stage_infos() {
+            hasher.write(stage.input.as_bytes());
+            hasher.write(stage.output.as_bytes());
+        }
+        hasher.0
+    }
+}
+
+impl<T: TypeFlow> DefinitionHash for T {}
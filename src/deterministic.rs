@@ -0,0 +1,55 @@
+use std::error::Error;
+use std::fmt;
+
+use crate::Cascade;
+
+/// Two runs of the same input disagreed.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Nondeterministic<T> {
+    pub first: T,
+    pub second: T,
+}
+
+impl<T: fmt::Debug> fmt::Display for Nondeterministic<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "cascade is nondeterministic: {:?} != {:?}", self.first, self.second)
+    }
+}
+
+impl<T: fmt::Debug> Error for Nondeterministic<T> {}
+
+/// Catches accidental nondeterminism (hash map iteration order, globals, clocks...) in cascades
+/// that are supposed to be pure, by running the same input through twice and comparing.
+pub trait CascadeDeterministic: Cascade {
+    /// Always runs twice, returning both outputs if they differ.
+    fn try_cascade_deterministic<'a>(
+        input: Self::In<'a>,
+    ) -> Result<Self::Out<'a>, Nondeterministic<Self::Out<'a>>>
+    where
+        Self::In<'a>: Clone,
+        Self::Out<'a>: PartialEq,
+    {
+        let first = Self::cascade(input.clone());
+        let second = Self::cascade(input);
+        match first == second {
+            true => Ok(first),
+            false => Err(Nondeterministic { first, second }),
+        }
+    }
+
+    /// Like `debug_assert!`, this only runs twice in debug builds, where it panics if the outputs
+    /// differ. Release builds just cascade once.
+    fn cascade_verify_deterministic<'a>(input: Self::In<'a>) -> Self::Out<'a>
+    where
+        Self::In<'a>: Clone,
+        Self::Out<'a>: PartialEq + fmt::Debug,
+    {
+        if cfg!(debug_assertions) {
+            Self::try_cascade_deterministic(input).unwrap_or_else(|e| panic!("{e}"))
+        } else {
+            Self::cascade(input)
+        }
+    }
+}
+
+impl<T: Cascade> CascadeDeterministic for T {}
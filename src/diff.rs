@@ -0,0 +1,98 @@
+use std::collections::BTreeMap;
+
+use crate::Cascade;
+
+/// Something that can describe how it changed relative to a previous version of itself, and
+/// replay that change onto the previous version.
+pub trait Diffable {
+    type Diff;
+
+    fn diff(&self, prev: &Self) -> Self::Diff;
+
+    fn apply(&mut self, diff: Self::Diff);
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Edit<T> {
+    Set(usize, T),
+    Push(T),
+    Truncate(usize),
+}
+
+impl<T: PartialEq + Clone> Diffable for Vec<T> {
+    type Diff = Vec<Edit<T>>;
+
+    fn diff(&self, prev: &Self) -> Self::Diff {
+        let mut edits: Vec<_> = self
+            .iter()
+            .zip(prev)
+            .enumerate()
+            .filter(|(_, (new, old))| new != old)
+            .map(|(i, (new, _))| Edit::Set(i, new.clone()))
+            .collect();
+        if self.len() < prev.len() {
+            edits.push(Edit::Truncate(self.len()));
+        }
+        edits.extend(self.iter().skip(prev.len()).cloned().map(Edit::Push));
+        edits
+    }
+
+    fn apply(&mut self, diff: Self::Diff) {
+        for edit in diff {
+            match edit {
+                Edit::Set(i, value) => self[i] = value,
+                Edit::Push(value) => self.push(value),
+                Edit::Truncate(len) => self.truncate(len),
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum MapEdit<K, V> {
+    Insert(K, V),
+    Remove(K),
+}
+
+impl<K: Ord + Clone, V: PartialEq + Clone> Diffable for BTreeMap<K, V> {
+    type Diff = Vec<MapEdit<K, V>>;
+
+    fn diff(&self, prev: &Self) -> Self::Diff {
+        let removed = prev
+            .keys()
+            .filter(|key| !self.contains_key(key))
+            .map(|key| MapEdit::Remove(key.clone()));
+        let inserted = self
+            .iter()
+            .filter(|(key, value)| prev.get(key) != Some(value))
+            .map(|(key, value)| MapEdit::Insert(key.clone(), value.clone()));
+        removed.chain(inserted).collect()
+    }
+
+    fn apply(&mut self, diff: Self::Diff) {
+        for edit in diff {
+            match edit {
+                MapEdit::Insert(key, value) => self.insert(key, value),
+                MapEdit::Remove(key) => self.remove(&key),
+            };
+        }
+    }
+}
+
+/// Cascades as usual, but also diffs the output against the previous run's output, so consumers
+/// only need to apply what changed.
+pub trait CascadeDiff: Cascade {
+    fn cascade_diff<'a>(
+        prev: &Self::Out<'a>,
+        input: Self::In<'a>,
+    ) -> (Self::Out<'a>, <Self::Out<'a> as Diffable>::Diff)
+    where
+        Self::Out<'a>: Diffable,
+    {
+        let out = Self::cascade(input);
+        let diff = out.diff(prev);
+        (out, diff)
+    }
+}
+
+impl<T: Cascade> CascadeDiff for T {}
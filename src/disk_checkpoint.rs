@@ -0,0 +1,119 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use seq_macro::seq;
+
+use crate::{Cascade, Chain, Codec, InRange, Length, Link, L};
+
+fn checkpoint_path(dir: &Path, stage: usize) -> PathBuf {
+    dir.join(format!("stage-{stage}.bin"))
+}
+
+fn invalid(error: impl Into<Box<dyn std::error::Error + Send + Sync>>) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, error)
+}
+
+/// Written to a temporary file first and renamed into place, so a crash mid-write never leaves
+/// a truncated checkpoint behind.
+fn write_checkpoint<T: Codec>(dir: &Path, stage: usize, value: &T) -> io::Result<()> {
+    let path = checkpoint_path(dir, stage);
+    let partial = path.with_extension("partial");
+    fs::write(&partial, value.to_bytes())?;
+    fs::rename(partial, path)
+}
+
+fn read_checkpoint<T: Codec>(dir: &Path, stage: usize) -> io::Result<Option<T>> {
+    match fs::read(checkpoint_path(dir, stage)) {
+        Ok(bytes) => T::from_bytes(&bytes).map(Some).map_err(invalid),
+        Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(error) => Err(error),
+    }
+}
+
+fn run<'a, const N: usize, T>(input: T::In<'a>, dir: &Path) -> io::Result<T::Out<'a>>
+where
+    T: Chain<N> + InRange<N, <T as Length>::Len>,
+    T::Out<'a>: Codec,
+{
+    let out = T::chain(input);
+    write_checkpoint(dir, N, &out)?;
+    Ok(out)
+}
+
+/// Same as `Link<N>`, but writes the output of every link to `dir` as it goes, and can pick up
+/// again from whatever was written.
+pub trait CheckpointLink<const N: usize>: Link<N> {
+    fn checkpointed_link<'a>(input: Self::In<'a>, dir: &Path) -> io::Result<Self::Out<'a>>;
+
+    /// Output of link `N - 1`, read from its checkpoint if it has one, otherwise computed from
+    /// the latest earlier checkpoint. `None` if there are no checkpoints at all.
+    fn resume_link(dir: &Path) -> io::Result<Option<Self::Out<'static>>>;
+}
+
+impl<T: Chain<0>> CheckpointLink<1> for T
+where
+    for<'a> <T as Chain<0>>::Out<'a>: Codec,
+{
+    fn checkpointed_link<'a>(input: Self::In<'a>, dir: &Path) -> io::Result<Self::Out<'a>> {
+        run::<0, T>(input, dir)
+    }
+
+    fn resume_link(dir: &Path) -> io::Result<Option<Self::Out<'static>>> {
+        read_checkpoint(dir, 0)
+    }
+}
+
+seq!(N in 2..=32 {
+    impl<T> CheckpointLink<N> for T
+    where
+        T: Chain<0>,
+        for<'a> T: CheckpointLink<{N - 1}, In<'a> = <T as Chain<0>>::In<'a>>,
+        for<'a> T: Chain<{N - 1}, In<'a> = <T as Link<{N - 1}>>::Out<'a>>,
+        for<'a> <T as Chain<{N - 1}>>::Out<'a>: Codec,
+    {
+        fn checkpointed_link<'a>(input: Self::In<'a>, dir: &Path) -> io::Result<Self::Out<'a>> {
+            let out = <T as CheckpointLink<{N - 1}>>::checkpointed_link(input, dir)?;
+            run::<{N - 1}, T>(out, dir)
+        }
+
+        fn resume_link(dir: &Path) -> io::Result<Option<Self::Out<'static>>> {
+            if let Some(out) = read_checkpoint(dir, N - 1)? {
+                return Ok(Some(out));
+            }
+            match <T as CheckpointLink<{N - 1}>>::resume_link(dir)? {
+                Some(previous) => run::<{N - 1}, T>(previous, dir).map(Some),
+                None => Ok(None),
+            }
+        }
+    }
+});
+
+pub trait CheckpointedCascade: Cascade {
+    /// Cascades as usual, writing the output of every link to `dir` so a crashed run can be
+    /// finished with `resume_from_checkpoint`. Checkpoints from a previous run are overwritten.
+    fn cascade_checkpointed<'a>(input: Self::In<'a>, dir: impl AsRef<Path>) -> io::Result<Self::Out<'a>>;
+
+    /// Finishes a cascade from the last link that completed, without rerunning anything before
+    /// it. `None` if `dir` has no checkpoints, in which case the cascade has to start over.
+    fn resume_from_checkpoint(dir: impl AsRef<Path>) -> io::Result<Option<Self::Out<'static>>>;
+}
+
+impl<const N: usize, T: CheckpointLink<N> + Length<Len = L<N>>> CheckpointedCascade for T {
+    fn cascade_checkpointed<'a>(input: Self::In<'a>, dir: impl AsRef<Path>) -> io::Result<Self::Out<'a>> {
+        let dir = dir.as_ref();
+        fs::create_dir_all(dir)?;
+        // a stale checkpoint from a later link would otherwise win on resume
+        for stage in 0..N {
+            match fs::remove_file(checkpoint_path(dir, stage)) {
+                Err(error) if error.kind() != io::ErrorKind::NotFound => return Err(error),
+                _ => {}
+            }
+        }
+        <T as CheckpointLink<N>>::checkpointed_link(input, dir)
+    }
+
+    fn resume_from_checkpoint(dir: impl AsRef<Path>) -> io::Result<Option<Self::Out<'static>>> {
+        <T as CheckpointLink<N>>::resume_link(dir.as_ref())
+    }
+}
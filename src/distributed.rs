@@ -0,0 +1,83 @@
+use std::error::Error;
+use std::fmt;
+use std::marker::PhantomData;
+
+use crate::{Codec, DecodeError, SplitCascade};
+
+/// Carries an encoded request to the worker running the second half of a `DistributedCascade`
+/// and brings back its encoded response, over whatever connects them: a socket, a queue, or a
+/// function call in tests.
+pub trait Transport {
+    type Error;
+
+    fn send(&mut self, request: Vec<u8>) -> Result<Vec<u8>, Self::Error>;
+}
+
+/// Why a `DistributedCascade` didn't produce an output.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DistributedError<E> {
+    /// The transport couldn't deliver the request or the response.
+    Transport(E),
+    /// The worker couldn't make sense of the request.
+    Rejected,
+    /// The response couldn't be decoded.
+    Decode(DecodeError),
+}
+
+impl<E: fmt::Display> fmt::Display for DistributedError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Transport(error) => write!(f, "transport failed: {error}"),
+            Self::Rejected => f.write_str("worker rejected the request"),
+            Self::Decode(error) => write!(f, "bad response: {error}"),
+        }
+    }
+}
+
+impl<E: fmt::Debug + fmt::Display> Error for DistributedError<E> {}
+
+/// Worker side of a `DistributedCascade` split at `P`: resumes the cascade from an encoded
+/// request and encodes the output as the response. A request that doesn't decode gets a
+/// response the coordinator reports as `DistributedError::Rejected`.
+pub fn serve<T, const P: usize>(request: &[u8]) -> Vec<u8>
+where
+    T: SplitCascade<P>,
+    T::Out<'static>: Codec,
+{
+    T::resume_from_pivot(request).ok().to_bytes()
+}
+
+/// Coordinator side of a cascade split at `P` across two nodes. Links `0..P` run here, their
+/// output is sent through the transport to a worker calling `serve`, which runs `P..N` and sends
+/// back the output.
+pub struct DistributedCascade<T, const P: usize, X> {
+    transport: X,
+    _pipeline: PhantomData<T>,
+}
+
+impl<T, const P: usize, X> DistributedCascade<T, P, X>
+where
+    T: SplitCascade<P>,
+    T::Out<'static>: Codec,
+    X: Transport,
+{
+    pub fn new(transport: X) -> Self {
+        Self { transport, _pipeline: PhantomData }
+    }
+
+    pub fn cascade(&mut self, input: T::In<'_>) -> Result<T::Out<'static>, DistributedError<X::Error>> {
+        let request = T::cascade_to_pivot(input);
+        let response = self.transport.send(request).map_err(DistributedError::Transport)?;
+        Option::<T::Out<'static>>::from_bytes(&response)
+            .map_err(DistributedError::Decode)?
+            .ok_or(DistributedError::Rejected)
+    }
+
+    pub fn transport(&self) -> &X {
+        &self.transport
+    }
+
+    pub fn into_transport(self) -> X {
+        self.transport
+    }
+}
@@ -0,0 +1,138 @@
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+use crate::Cascade;
+
+/// Dual number `re + eps * ε` where `ε² = 0`, used for forward-mode autodiff. Any stage that only
+/// does arithmetic on a `Dual` carries the derivative along with the value for free.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Dual {
+    pub re: f64,
+    pub eps: f64,
+}
+
+impl Dual {
+    /// A value whose derivative is 0, for constants in the computation.
+    pub fn constant(re: f64) -> Self {
+        Self { re, eps: 0.0 }
+    }
+
+    /// The value being differentiated with respect to.
+    pub fn variable(re: f64) -> Self {
+        Self { re, eps: 1.0 }
+    }
+
+    pub fn powi(self, n: i32) -> Self {
+        Self {
+            re: self.re.powi(n),
+            eps: n as f64 * self.re.powi(n - 1) * self.eps,
+        }
+    }
+
+    pub fn exp(self) -> Self {
+        let exp = self.re.exp();
+        Self { re: exp, eps: exp * self.eps }
+    }
+
+    pub fn ln(self) -> Self {
+        Self { re: self.re.ln(), eps: self.eps / self.re }
+    }
+
+    pub fn sin(self) -> Self {
+        Self { re: self.re.sin(), eps: self.re.cos() * self.eps }
+    }
+
+    pub fn cos(self) -> Self {
+        Self { re: self.re.cos(), eps: -self.re.sin() * self.eps }
+    }
+}
+
+impl From<f64> for Dual {
+    fn from(re: f64) -> Self {
+        Self::constant(re)
+    }
+}
+
+impl Neg for Dual {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self { re: -self.re, eps: -self.eps }
+    }
+}
+
+impl Add for Dual {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self { re: self.re + rhs.re, eps: self.eps + rhs.eps }
+    }
+}
+
+impl Sub for Dual {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Self { re: self.re - rhs.re, eps: self.eps - rhs.eps }
+    }
+}
+
+impl Mul for Dual {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        Self {
+            re: self.re * rhs.re,
+            eps: self.re * rhs.eps + self.eps * rhs.re,
+        }
+    }
+}
+
+impl Div for Dual {
+    type Output = Self;
+
+    fn div(self, rhs: Self) -> Self {
+        Self {
+            re: self.re / rhs.re,
+            eps: (self.eps * rhs.re - self.re * rhs.eps) / (rhs.re * rhs.re),
+        }
+    }
+}
+
+// mixed arithmetic with plain floats, so stages don't need `Dual::constant` everywhere
+macro_rules! impl_scalar_ops {
+    ($($op:ident $fn:ident),*) => {$(
+        impl $op<f64> for Dual {
+            type Output = Self;
+
+            fn $fn(self, rhs: f64) -> Self {
+                self.$fn(Dual::constant(rhs))
+            }
+        }
+
+        impl $op<Dual> for f64 {
+            type Output = Dual;
+
+            fn $fn(self, rhs: Dual) -> Dual {
+                Dual::constant(self).$fn(rhs)
+            }
+        }
+    )*};
+}
+
+impl_scalar_ops!(Add add, Sub sub, Mul mul, Div div);
+
+/// Implemented for any cascade over `Dual`, returning the output along with its derivative with
+/// respect to the input.
+pub trait CascadeGrad {
+    fn cascade_grad(input: f64) -> (f64, f64);
+}
+
+impl<T> CascadeGrad for T
+where
+    for<'a> T: Cascade<In<'a> = Dual, Out<'a> = Dual>,
+{
+    fn cascade_grad(input: f64) -> (f64, f64) {
+        let out = T::cascade(Dual::variable(input));
+        (out.re, out.eps)
+    }
+}
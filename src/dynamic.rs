@@ -0,0 +1,178 @@
+use std::future::Future;
+use std::pin::Pin;
+
+use crate::Sequential;
+
+/// Runtime counterpart to `Cascade` for when the stages of a pipeline aren't known until runtime.
+///
+/// Since the stages are boxed closures there's no type gymnastics here, which means every stage
+/// has to take and return the same type `T`.
+pub struct DynCascade<T> {
+    stages: Vec<Box<dyn Fn(T) -> T>>,
+}
+
+impl<T> DynCascade<T> {
+    pub fn new() -> Self {
+        Self { stages: Vec::new() }
+    }
+
+    /// Appends a stage to the end of the pipeline.
+    pub fn then(mut self, stage: impl Fn(T) -> T + 'static) -> Self {
+        self.push(stage);
+        self
+    }
+
+    pub fn push(&mut self, stage: impl Fn(T) -> T + 'static) {
+        self.stages.push(Box::new(stage));
+    }
+
+    pub fn len(&self) -> usize {
+        self.stages.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.stages.is_empty()
+    }
+
+    pub fn cascade(&self, input: T) -> T {
+        self.stages.iter().fold(input, |acc, stage| stage(acc))
+    }
+}
+
+impl<T> Default for DynCascade<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Boxed future returned by the stages of a `DynAsyncCascade`.
+pub type BoxFuture<T> = Pin<Box<dyn Future<Output = T> + Send>>;
+
+/// Async counterpart to `DynCascade`, for hosts that assemble async stages at runtime, e.g. from
+/// plugins. Each stage is an async closure whose future gets boxed, and all of them take and
+/// return the same type `T`.
+pub struct DynAsyncCascade<T> {
+    stages: Vec<Box<dyn Fn(T) -> BoxFuture<T>>>,
+}
+
+impl<T> DynAsyncCascade<T> {
+    pub fn new() -> Self {
+        Self { stages: Vec::new() }
+    }
+
+    /// Appends a stage to the end of the pipeline.
+    pub fn then<F>(mut self, stage: impl Fn(T) -> F + 'static) -> Self
+    where
+        F: Future<Output = T> + Send + 'static,
+    {
+        self.push(stage);
+        self
+    }
+
+    pub fn push<F>(&mut self, stage: impl Fn(T) -> F + 'static)
+    where
+        F: Future<Output = T> + Send + 'static,
+    {
+        self.stages.push(Box::new(move |input| Box::pin(stage(input))));
+    }
+
+    pub fn len(&self) -> usize {
+        self.stages.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.stages.is_empty()
+    }
+
+    pub async fn cascade(&self, input: T) -> T {
+        let mut value = input;
+        for stage in &self.stages {
+            value = stage(value).await;
+        }
+        value
+    }
+}
+
+impl<T> Default for DynAsyncCascade<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Flattened form of a `DynCascade` whose stages are plain `fn` pointers, stored contiguously in
+/// one allocation instead of each stage being boxed on its own, so running it doesn't chase a
+/// pointer and a vtable per stage. Closures that capture state can't be flattened, but
+/// non-capturing ones coerce to `fn` pointers just fine.
+pub struct FlatCascade<T> {
+    stages: Box<[Step<T>]>,
+}
+
+type Step<T> = fn(T) -> T;
+
+impl<T> FlatCascade<T> {
+    /// Flattens the runtime steps of a `Sequential` type.
+    pub fn of<S: Sequential<Item = T>>() -> Self {
+        S::steps().into_iter().collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.stages.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.stages.is_empty()
+    }
+
+    pub fn cascade(&self, input: T) -> T {
+        self.stages.iter().fold(input, |acc, stage| stage(acc))
+    }
+}
+
+impl<T> Clone for FlatCascade<T> {
+    fn clone(&self) -> Self {
+        Self { stages: self.stages.clone() }
+    }
+}
+
+impl<T> FromIterator<Step<T>> for FlatCascade<T> {
+    fn from_iter<I: IntoIterator<Item = Step<T>>>(iter: I) -> Self {
+        Self { stages: iter.into_iter().collect() }
+    }
+}
+
+impl<T: 'static> From<FlatCascade<T>> for DynCascade<T> {
+    fn from(flat: FlatCascade<T>) -> Self {
+        let mut cascade = DynCascade::new();
+        for stage in flat.stages {
+            cascade.push(stage);
+        }
+        cascade
+    }
+}
+
+/// Data-driven cascade where the stages aren't known up front at all. After each stage the
+/// closure inspects the current value and returns the next stage to run, or `None` to stop.
+///
+/// Since the closure decides when to terminate, it's up to the user to make sure it eventually
+/// returns `None`, otherwise the cascade will run forever.
+pub struct Unfold<F> {
+    next: F,
+}
+
+impl<F> Unfold<F> {
+    pub fn new(next: F) -> Self {
+        Self { next }
+    }
+
+    pub fn cascade<T, S>(&mut self, input: T) -> T
+    where
+        F: FnMut(&T) -> Option<S>,
+        S: FnOnce(T) -> T,
+    {
+        let mut value = input;
+        while let Some(stage) = (self.next)(&value) {
+            value = stage(value);
+        }
+        value
+    }
+}
@@ -0,0 +1,79 @@
+use std::collections::HashSet;
+use std::convert::Infallible;
+use std::hash::Hash;
+
+use crate::Cascade;
+
+/// Where `cascade_exactly_once` keeps the keys of inputs it already processed. Backing it with
+/// something durable, like a database table, is what lets the dedup survive restarts.
+pub trait IdempotencyStore<K> {
+    type Error;
+
+    fn has_seen(&self, key: &K) -> Result<bool, Self::Error>;
+
+    fn mark_seen(&mut self, key: K) -> Result<(), Self::Error>;
+}
+
+/// `IdempotencyStore` that only lasts as long as the process, for tests and for pipelines that
+/// just need dedup within a run. Unlike `SeenKeys`, it never forgets a key.
+#[derive(Clone, Debug)]
+pub struct MemoryStore<K> {
+    seen: HashSet<K>,
+}
+
+impl<K> MemoryStore<K> {
+    pub fn new() -> Self {
+        Self { seen: HashSet::new() }
+    }
+
+    pub fn len(&self) -> usize {
+        self.seen.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.seen.is_empty()
+    }
+}
+
+impl<K> Default for MemoryStore<K> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Hash + Eq> IdempotencyStore<K> for MemoryStore<K> {
+    type Error = Infallible;
+
+    fn has_seen(&self, key: &K) -> Result<bool, Infallible> {
+        Ok(self.seen.contains(key))
+    }
+
+    fn mark_seen(&mut self, key: K) -> Result<(), Infallible> {
+        self.seen.insert(key);
+        Ok(())
+    }
+}
+
+/// A pipeline fed by at-least-once delivery, where every input carries a key identifying it,
+/// like a message id, so a redelivered input can be recognized and skipped.
+pub trait ExactlyOnce: Cascade {
+    type Key;
+
+    fn key(input: &Self::In<'_>) -> Self::Key;
+
+    /// Cascades `input` unless `store` has already seen its key, in which case it's skipped and
+    /// `None` is returned. The key is only marked once the cascade finished, so an input that was
+    /// interrupted halfway is processed again rather than lost.
+    fn cascade_exactly_once<'a, S>(store: &mut S, input: Self::In<'a>) -> Result<Option<Self::Out<'a>>, S::Error>
+    where
+        S: IdempotencyStore<Self::Key>,
+    {
+        let key = Self::key(&input);
+        if store.has_seen(&key)? {
+            return Ok(None);
+        }
+        let out = Self::cascade(input);
+        store.mark_seen(key)?;
+        Ok(Some(out))
+    }
+}
@@ -0,0 +1,105 @@
+use std::sync::atomic::{AtomicU8, Ordering};
+
+use crate::{Chain, InRange, Length, No, Select, Yes};
+
+/// A faster implementation of link `N` that only works on some CPUs, e.g. one using SIMD
+/// instructions. The `Chain<N>` impl is the portable fallback, and has to give the same results.
+pub trait Accelerated<const N: usize>: Chain<N>
+where
+    Self: InRange<N, <Self as Length>::Len>,
+{
+    /// Whether `accelerated` can run here. It's asked on every run of the link, so it should only
+    /// actually detect once, e.g. with a `Detection` static around `is_x86_feature_detected!`.
+    fn detected() -> bool;
+
+    fn accelerated(input: Self::In<'_>) -> Self::Out<'_>;
+}
+
+/// Remembers whether a CPU feature is there after detecting it the first time, so that checking
+/// on every run costs a single atomic load:
+/// ```
+/// # use chain_link::Detection;
+/// # #[cfg(target_arch = "x86_64")]
+/// fn detected() -> bool {
+///     static DETECTION: Detection = Detection::new();
+///     DETECTION.get(|| is_x86_feature_detected!("avx2"))
+/// }
+/// ```
+/// A static in a generic impl is shared by all of its instantiations, so those that can detect
+/// differently need one each.
+#[derive(Debug, Default)]
+pub struct Detection(AtomicU8);
+
+const UNKNOWN: u8 = 0;
+const MISSING: u8 = 1;
+const PRESENT: u8 = 2;
+
+impl Detection {
+    pub const fn new() -> Self {
+        Self(AtomicU8::new(UNKNOWN))
+    }
+
+    /// The remembered result, or that of `detect` if there isn't one yet. Threads racing on the
+    /// first call may each detect, which is harmless since they all get the same answer.
+    pub fn get(&self, detect: impl FnOnce() -> bool) -> bool {
+        match self.0.load(Ordering::Relaxed) {
+            UNKNOWN => {
+                let present = detect();
+                self.0.store(if present { PRESENT } else { MISSING }, Ordering::Relaxed);
+                present
+            }
+            known => known == PRESENT,
+        }
+    }
+}
+
+/// Wraps a chain so that link `N` runs `Accelerated::accelerated` when the CPU supports it, and
+/// the regular `Chain<N>` otherwise. Every other link behaves as usual.
+pub struct FeatureSelected<T, const N: usize>(T);
+
+impl<T: Length, const N: usize> Length for FeatureSelected<T, N> {
+    type Len = T::Len;
+}
+
+impl<const M: usize, const N: usize, T> Chain<M> for FeatureSelected<T, N>
+where
+    (): Select<M, N>,
+    T: Chain<M> + OrAccelerated<M, <() as Select<M, N>>::Is>,
+    Self: InRange<M, Self::Len>,
+{
+    type In<'a> = <T as Chain<M>>::In<'a>;
+    type Out<'a> = <T as Chain<M>>::Out<'a>;
+
+    fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+        <T as OrAccelerated<M, <() as Select<M, N>>::Is>>::or_accelerated(input)
+    }
+}
+
+/// Implementation detail of `FeatureSelected`, picking an implementation only for the selected link.
+pub trait OrAccelerated<const M: usize, Is>: Chain<M>
+where
+    Self: InRange<M, <Self as Length>::Len>,
+{
+    fn or_accelerated(input: Self::In<'_>) -> Self::Out<'_>;
+}
+
+impl<const M: usize, T: Chain<M>> OrAccelerated<M, No> for T
+where
+    T: InRange<M, <T as Length>::Len>,
+{
+    fn or_accelerated(input: Self::In<'_>) -> Self::Out<'_> {
+        <T as Chain<M>>::chain(input)
+    }
+}
+
+impl<const M: usize, T: Accelerated<M>> OrAccelerated<M, Yes> for T
+where
+    T: InRange<M, <T as Length>::Len>,
+{
+    fn or_accelerated(input: Self::In<'_>) -> Self::Out<'_> {
+        match T::detected() {
+            true => T::accelerated(input),
+            false => <T as Chain<M>>::chain(input),
+        }
+    }
+}
@@ -0,0 +1,112 @@
+use seq_macro::seq;
+
+use crate::{InRange, Length, No, Select, Yes, L};
+
+/// A `Chain<N>` that can drop its input by returning `None`, which stops the whole cascade.
+pub trait FilterChain<const N: usize>
+where
+    Self: InRange<N, <Self as Length>::Len>,
+{
+    type In<'a>;
+    type Out<'a>;
+
+    fn filter(input: Self::In<'_>) -> Option<Self::Out<'_>>;
+}
+
+pub trait FilterLink<const N: usize> {
+    type In<'a>;
+    type Out<'a>;
+
+    fn filter_link(input: Self::In<'_>) -> Option<Self::Out<'_>>;
+}
+
+impl<T: FilterChain<0>> FilterLink<1> for T {
+    type In<'a> = <T as FilterChain<0>>::In<'a>;
+    type Out<'a> = <T as FilterChain<0>>::Out<'a>;
+
+    fn filter_link(input: Self::In<'_>) -> Option<Self::Out<'_>> {
+        <T as FilterChain<0>>::filter(input)
+    }
+}
+
+seq!(N in 2..=32 {
+    impl<T> FilterLink<N> for T
+    where
+        T: FilterChain<0>,
+        for<'a> T: FilterLink<{N - 1}, In<'a> = <T as FilterChain<0>>::In<'a>>,
+        for<'a> T: FilterChain<{N - 1}, In<'a> = <T as FilterLink<{N - 1}>>::Out<'a>>,
+    {
+        type In<'a> = <T as FilterChain<0>>::In<'a>;
+        type Out<'a> = <T as FilterChain<{N - 1}>>::Out<'a>;
+
+        fn filter_link(input: Self::In<'_>) -> Option<Self::Out<'_>> {
+            let out = <T as FilterLink<{N - 1}>>::filter_link(input)?;
+            <T as FilterChain<{N - 1}>>::filter(out)
+        }
+    }
+});
+
+pub trait FilterCascade {
+    type In<'a>;
+    type Out<'a>;
+
+    fn filter_cascade(input: Self::In<'_>) -> Option<Self::Out<'_>>;
+}
+
+impl<const N: usize, T: FilterLink<N> + Length<Len = L<N>>> FilterCascade for T {
+    type In<'a> = T::In<'a>;
+    type Out<'a> = T::Out<'a>;
+
+    fn filter_cascade(input: Self::In<'_>) -> Option<Self::Out<'_>> {
+        <T as FilterLink<N>>::filter_link(input)
+    }
+}
+
+/// Wraps a filter chain so that when link `N` filters out its input, the cascade continues with
+/// the default value of that link's output instead of stopping. Every other link behaves as usual.
+pub struct DefaultIfNone<T, const N: usize>(T);
+
+impl<T: Length, const N: usize> Length for DefaultIfNone<T, N> {
+    type Len = T::Len;
+}
+
+impl<const M: usize, const N: usize, T> FilterChain<M> for DefaultIfNone<T, N>
+where
+    (): Select<M, N>,
+    T: FilterChain<M> + OrDefault<M, <() as Select<M, N>>::Is>,
+    Self: InRange<M, Self::Len>,
+{
+    type In<'a> = <T as FilterChain<M>>::In<'a>;
+    type Out<'a> = <T as FilterChain<M>>::Out<'a>;
+
+    fn filter(input: Self::In<'_>) -> Option<Self::Out<'_>> {
+        <T as OrDefault<M, <() as Select<M, N>>::Is>>::or_default(input)
+    }
+}
+
+/// Implementation detail of `DefaultIfNone`, falling back to the default only for the selected link.
+pub trait OrDefault<const M: usize, Is>: FilterChain<M>
+where
+    Self: InRange<M, <Self as Length>::Len>,
+{
+    fn or_default(input: Self::In<'_>) -> Option<Self::Out<'_>>;
+}
+
+impl<const M: usize, T: FilterChain<M>> OrDefault<M, No> for T
+where
+    T: InRange<M, <T as Length>::Len>,
+{
+    fn or_default(input: Self::In<'_>) -> Option<Self::Out<'_>> {
+        <T as FilterChain<M>>::filter(input)
+    }
+}
+
+impl<const M: usize, T: FilterChain<M>> OrDefault<M, Yes> for T
+where
+    T: InRange<M, <T as Length>::Len>,
+    for<'a> T::Out<'a>: Default,
+{
+    fn or_default(input: Self::In<'_>) -> Option<Self::Out<'_>> {
+        Some(<T as FilterChain<M>>::filter(input).unwrap_or_default())
+    }
+}
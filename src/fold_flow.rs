@@ -0,0 +1,92 @@
+use std::ops::ControlFlow;
+
+use seq_macro::seq;
+
+use crate::{InRange, Length, L};
+
+/// A `Chain<N>` that also sees an accumulator shared by every link, and can stop the cascade
+/// by returning `Break` once the accumulated state says there's no point going on, e.g. once
+/// enough has been collected. Every link of a chain has to agree on the same `Acc` and `Break`.
+pub trait FoldFlowChain<const N: usize>
+where
+    Self: InRange<N, <Self as Length>::Len>,
+{
+    type Acc;
+    type Break;
+    type In<'a>;
+    type Out<'a>;
+
+    fn fold_flow<'a>(acc: &mut Self::Acc, input: Self::In<'a>) -> ControlFlow<Self::Break, Self::Out<'a>>;
+}
+
+pub trait FoldFlowLink<const N: usize> {
+    type Acc;
+    type Break;
+    type In<'a>;
+    type Out<'a>;
+
+    fn fold_flow_link<'a>(acc: &mut Self::Acc, input: Self::In<'a>) -> ControlFlow<Self::Break, Self::Out<'a>>;
+}
+
+impl<T: FoldFlowChain<0>> FoldFlowLink<1> for T {
+    type Acc = <T as FoldFlowChain<0>>::Acc;
+    type Break = <T as FoldFlowChain<0>>::Break;
+    type In<'a> = <T as FoldFlowChain<0>>::In<'a>;
+    type Out<'a> = <T as FoldFlowChain<0>>::Out<'a>;
+
+    fn fold_flow_link<'a>(acc: &mut Self::Acc, input: Self::In<'a>) -> ControlFlow<Self::Break, Self::Out<'a>> {
+        <T as FoldFlowChain<0>>::fold_flow(acc, input)
+    }
+}
+
+seq!(N in 2..=32 {
+    impl<T> FoldFlowLink<N> for T
+    where
+        T: FoldFlowChain<0>,
+        for<'a> T: FoldFlowLink<
+            {N - 1},
+            Acc = <T as FoldFlowChain<0>>::Acc,
+            Break = <T as FoldFlowChain<0>>::Break,
+            In<'a> = <T as FoldFlowChain<0>>::In<'a>,
+        >,
+        for<'a> T: FoldFlowChain<
+            {N - 1},
+            Acc = <T as FoldFlowChain<0>>::Acc,
+            Break = <T as FoldFlowChain<0>>::Break,
+            In<'a> = <T as FoldFlowLink<{N - 1}>>::Out<'a>,
+        >,
+    {
+        type Acc = <T as FoldFlowChain<0>>::Acc;
+        type Break = <T as FoldFlowChain<0>>::Break;
+        type In<'a> = <T as FoldFlowChain<0>>::In<'a>;
+        type Out<'a> = <T as FoldFlowChain<{N - 1}>>::Out<'a>;
+
+        fn fold_flow_link<'a>(acc: &mut Self::Acc, input: Self::In<'a>) -> ControlFlow<Self::Break, Self::Out<'a>> {
+            let out = <T as FoldFlowLink<{N - 1}>>::fold_flow_link(acc, input)?;
+            <T as FoldFlowChain<{N - 1}>>::fold_flow(acc, out)
+        }
+    }
+});
+
+pub trait FoldFlowCascade {
+    type Acc;
+    type Break;
+    type In<'a>;
+    type Out<'a>;
+
+    /// Cascades `input` with every link folding into `acc`, stopping at the first link that
+    /// breaks. `acc` keeps whatever was accumulated either way, so it can be carried across
+    /// inputs until a link decides the whole run is done.
+    fn fold_flow_cascade<'a>(acc: &mut Self::Acc, input: Self::In<'a>) -> ControlFlow<Self::Break, Self::Out<'a>>;
+}
+
+impl<const N: usize, T: FoldFlowLink<N> + Length<Len = L<N>>> FoldFlowCascade for T {
+    type Acc = <T as FoldFlowLink<N>>::Acc;
+    type Break = <T as FoldFlowLink<N>>::Break;
+    type In<'a> = <T as FoldFlowLink<N>>::In<'a>;
+    type Out<'a> = <T as FoldFlowLink<N>>::Out<'a>;
+
+    fn fold_flow_cascade<'a>(acc: &mut Self::Acc, input: Self::In<'a>) -> ControlFlow<Self::Break, Self::Out<'a>> {
+        <T as FoldFlowLink<N>>::fold_flow_link(acc, input)
+    }
+}
@@ -0,0 +1,107 @@
+use std::any::type_name;
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::time::{Duration, Instant};
+
+use crate::type_flow::strip_paths;
+use crate::{Cascade, ObservedCascade, Observer, StageInfo};
+
+struct Frame {
+    started: Instant,
+    children: Duration,
+}
+
+/// The profile being collected on this thread, if any. Kept thread local rather than threaded
+/// through the links so that a sub-pipeline cascaded from inside a link can find it.
+#[derive(Default)]
+struct Folded {
+    stack: Vec<String>,
+    frames: Vec<Frame>,
+    samples: BTreeMap<String, u128>,
+}
+
+thread_local! {
+    static PROFILE: RefCell<Option<Folded>> = const { RefCell::new(None) };
+}
+
+/// Pushes a frame named after the pipeline and link index onto the current profile, and on the
+/// way out charges the link its own time, minus whatever its nested sub-pipelines took.
+struct FoldedObserver {
+    pipeline: String,
+}
+
+impl Observer for FoldedObserver {
+    fn before(&mut self, stage: &StageInfo) {
+        PROFILE.with_borrow_mut(|profile| {
+            if let Some(profile) = profile {
+                profile.stack.push(format!("{}[{}]", self.pipeline, stage.index));
+                profile.frames.push(Frame { started: Instant::now(), children: Duration::ZERO });
+            }
+        });
+    }
+
+    fn after(&mut self, _: &StageInfo) {
+        PROFILE.with_borrow_mut(|profile| {
+            let Some(profile) = profile else {
+                return;
+            };
+            let Some(frame) = profile.frames.pop() else {
+                return;
+            };
+            let elapsed = frame.started.elapsed();
+            let own = elapsed.saturating_sub(frame.children);
+            *profile.samples.entry(profile.stack.join(";")).or_default() += own.as_nanos();
+            profile.stack.pop();
+            if let Some(parent) = profile.frames.last_mut() {
+                parent.children += elapsed;
+            }
+        });
+    }
+}
+
+/// Puts back whatever profile was active before, even if the cascade panics.
+struct Restore(Option<Folded>);
+
+impl Drop for Restore {
+    fn drop(&mut self) {
+        PROFILE.set(self.0.take());
+    }
+}
+
+pub trait FoldedProfileCascade: ObservedCascade {
+    /// Cascades as usual, also returning a folded stack profile, the format read by `inferno` and
+    /// `flamegraph.pl`: one `frame;frame;... nanoseconds` line per distinct stack. Links that
+    /// cascade a sub-pipeline with `cascade_nested` get its links as child frames, and only their
+    /// own time is counted against them.
+    fn cascade_folded_profile(input: Self::In<'_>) -> (Self::Out<'_>, String) {
+        let restore = Restore(PROFILE.replace(Some(Folded::default())));
+        let pipeline = strip_paths(type_name::<Self>());
+        let out = Self::observed_cascade(input, &mut FoldedObserver { pipeline });
+        let samples = PROFILE.take().map(|profile| profile.samples).unwrap_or_default();
+        drop(restore);
+
+        let mut folded = String::new();
+        for (stack, nanos) in samples {
+            let _ = writeln!(folded, "{stack} {nanos}");
+        }
+        (out, folded)
+    }
+}
+
+impl<T: ObservedCascade> FoldedProfileCascade for T {}
+
+pub trait NestedCascade: ObservedCascade {
+    /// For cascading a sub-pipeline from inside a link. Under `cascade_folded_profile` its links
+    /// show up nested under the calling link, otherwise it's the same as `cascade`.
+    fn cascade_nested(input: Self::In<'_>) -> Self::Out<'_> {
+        if PROFILE.with_borrow(Option::is_some) {
+            let pipeline = strip_paths(type_name::<Self>());
+            Self::observed_cascade(input, &mut FoldedObserver { pipeline })
+        } else {
+            <Self as Cascade>::cascade(input)
+        }
+    }
+}
+
+impl<T: ObservedCascade> NestedCascade for T {}
@@ -0,0 +1,47 @@
+use seq_macro::seq;
+
+use crate::{Cascade, Chain, InRange, Length, L};
+
+/// Marker for a `Chain<N>` that has no side effects, so it's safe to merge with its neighbors
+/// without anybody being able to tell the difference.
+pub trait Pure<const N: usize>: Chain<N>
+where
+    Self: InRange<N, <Self as Length>::Len>,
+{
+}
+
+/// Implemented when every link in 0..N is `Pure`.
+pub trait PureLink<const N: usize> {}
+
+impl<T: Pure<0>> PureLink<1> for T {}
+
+seq!(N in 2..=32 {
+    impl<T: PureLink<{N - 1}> + Pure<{N - 1}>> PureLink<N> for T {}
+});
+
+/// Collapses an entire pure chain into a single link, so a segment of trivial stages can be
+/// pulled out into its own type and then used as one stage of a bigger chain. The recursion only
+/// ever sees `Fuse<T>` as a chain of length 1, regardless of how many stages `T` has.
+pub struct Fuse<T>(T);
+
+impl<T> Length for Fuse<T> {
+    type Len = L<1>;
+}
+
+impl<const N: usize, T> Chain<0> for Fuse<T>
+where
+    T: Cascade + PureLink<N> + Length<Len = L<N>>,
+{
+    type In<'a> = <T as Cascade>::In<'a>;
+    type Out<'a> = <T as Cascade>::Out<'a>;
+
+    fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+        <T as Cascade>::cascade(input)
+    }
+}
+
+impl<const N: usize, T> Pure<0> for Fuse<T>
+where
+    T: Cascade + PureLink<N> + Length<Len = L<N>>,
+{
+}
@@ -0,0 +1,120 @@
+use std::any::Any;
+use std::marker::PhantomData;
+
+use seq_macro::seq;
+
+use crate::{Cascade, Chain, InRange, Length, Link, L};
+
+type ErasedStep = fn(Box<dyn Any>) -> Box<dyn Any>;
+
+fn erased<const N: usize, T>(value: Box<dyn Any>) -> Box<dyn Any>
+where
+    T: Chain<N> + InRange<N, <T as Length>::Len>,
+    T::In<'static>: 'static,
+    T::Out<'static>: 'static,
+{
+    // every step is only ever handed the output of the one before it
+    let input = *value.downcast::<T::In<'static>>().unwrap_or_else(|_| unreachable!());
+    Box::new(T::chain(input))
+}
+
+/// Collects every link from 0..N as a step over type erased values, so a cascade can be driven
+/// one link at a time. Needs every link's input and output to be `'static`.
+pub trait ErasedLink<const N: usize>: Link<N> {
+    fn erased_link() -> Vec<ErasedStep>;
+}
+
+impl<T: Chain<0>> ErasedLink<1> for T
+where
+    <T as Chain<0>>::In<'static>: 'static,
+    <T as Chain<0>>::Out<'static>: 'static,
+{
+    fn erased_link() -> Vec<ErasedStep> {
+        vec![erased::<0, T>]
+    }
+}
+
+seq!(N in 2..=32 {
+    impl<T> ErasedLink<N> for T
+    where
+        T: Chain<0>,
+        for<'a> T: ErasedLink<{N - 1}, In<'a> = <T as Chain<0>>::In<'a>>,
+        for<'a> T: Chain<{N - 1}, In<'a> = <T as Link<{N - 1}>>::Out<'a>>,
+        <T as Link<{N - 1}>>::Out<'static>: 'static,
+        <T as Chain<{N - 1}>>::Out<'static>: 'static,
+    {
+        fn erased_link() -> Vec<ErasedStep> {
+            let mut steps = <T as ErasedLink<{N - 1}>>::erased_link();
+            steps.push(erased::<{N - 1}, T>);
+            steps
+        }
+    }
+});
+
+/// A cascade paused at a link boundary, driven forward one link at a time by `resume`. In
+/// between, the value handed from one link to the next can be looked at with `intermediate`.
+pub struct StageGenerator<T> {
+    steps: Vec<ErasedStep>,
+    next: usize,
+    value: Box<dyn Any>,
+    _pipeline: PhantomData<T>,
+}
+
+impl<T: Cascade> StageGenerator<T> {
+    /// Runs the next link, returning its index, or `None` if every link has already run.
+    pub fn resume(&mut self) -> Option<usize> {
+        let step = self.steps.get(self.next)?;
+        let value = std::mem::replace(&mut self.value, Box::new(()));
+        self.value = step(value);
+        self.next += 1;
+        Some(self.next - 1)
+    }
+
+    /// How many links have run so far.
+    pub fn completed(&self) -> usize {
+        self.next
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.next == self.steps.len()
+    }
+
+    /// The current value, if it's a `V`: the input before anything has run, then the output of
+    /// the last link that ran.
+    pub fn intermediate<V: 'static>(&self) -> Option<&V> {
+        self.value.downcast_ref()
+    }
+
+    /// The cascade's output once every link has run, otherwise the generator is handed back.
+    pub fn into_output(self) -> Result<T::Out<'static>, Self>
+    where
+        T::Out<'static>: 'static,
+    {
+        if !self.is_complete() {
+            return Err(self);
+        }
+        Ok(*self.value.downcast().unwrap_or_else(|_| unreachable!()))
+    }
+}
+
+pub trait GeneratorCascade: Cascade {
+    /// Sets up a cascade of `input` without running any of it, for the caller to step through.
+    fn into_generator(input: Self::In<'static>) -> StageGenerator<Self>
+    where
+        Self: Sized;
+}
+
+impl<const N: usize, T> GeneratorCascade for T
+where
+    T: ErasedLink<N> + Length<Len = L<N>>,
+    <T as Cascade>::In<'static>: 'static,
+{
+    fn into_generator(input: Self::In<'static>) -> StageGenerator<Self> {
+        StageGenerator {
+            steps: <T as ErasedLink<N>>::erased_link(),
+            next: 0,
+            value: Box::new(input),
+            _pipeline: PhantomData,
+        }
+    }
+}
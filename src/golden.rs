@@ -0,0 +1,151 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use seq_macro::seq;
+
+use crate::{Cascade, Chain, Codec, DecodeError, InRange, Length, Link, L};
+
+/// The encoded input and output of one link, as recorded by `record_golden`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GoldenVector {
+    pub input: Vec<u8>,
+    pub output: Vec<u8>,
+}
+
+impl Codec for GoldenVector {
+    fn encode(&self, out: &mut Vec<u8>) {
+        self.input.encode(out);
+        self.output.encode(out);
+    }
+
+    fn decode(bytes: &mut &[u8]) -> Result<Self, DecodeError> {
+        Ok(Self { input: Codec::decode(bytes)?, output: Codec::decode(bytes)? })
+    }
+}
+
+/// One `GoldenVector` per link, in order, to be checked into the repo next to the pipeline.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct GoldenVectors {
+    pub stages: Vec<GoldenVector>,
+}
+
+impl GoldenVectors {
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        fs::write(path, self.to_bytes())
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        let bytes = fs::read(path)?;
+        Self::from_bytes(&bytes).map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))
+    }
+}
+
+impl Codec for GoldenVectors {
+    fn encode(&self, out: &mut Vec<u8>) {
+        self.stages.encode(out);
+    }
+
+    fn decode(bytes: &mut &[u8]) -> Result<Self, DecodeError> {
+        Ok(Self { stages: Codec::decode(bytes)? })
+    }
+}
+
+fn record<'a, const N: usize, T>(input: T::In<'a>, vectors: &mut GoldenVectors) -> T::Out<'a>
+where
+    T: Chain<N> + InRange<N, <T as Length>::Len>,
+    T::In<'a>: Codec,
+    T::Out<'a>: Codec,
+{
+    let encoded = input.to_bytes();
+    let out = T::chain(input);
+    vectors.stages.push(GoldenVector { input: encoded, output: out.to_bytes() });
+    out
+}
+
+/// Whether link `N` still turns its golden input into its golden output.
+fn verify<const N: usize, T>(vectors: &GoldenVectors) -> bool
+where
+    T: Chain<N> + InRange<N, <T as Length>::Len>,
+    T::In<'static>: Codec,
+    T::Out<'static>: Codec,
+{
+    let Some(vector) = vectors.stages.get(N) else {
+        return false;
+    };
+    match <T::In<'static> as Codec>::from_bytes(&vector.input) {
+        Ok(input) => T::chain(input).to_bytes() == vector.output,
+        Err(_) => false,
+    }
+}
+
+/// Same as `Link<N>`, but records every link's input and output as it goes, and can replay them
+/// one link at a time.
+pub trait GoldenLink<const N: usize>: Link<N> {
+    fn golden_link<'a>(input: Self::In<'a>, vectors: &mut GoldenVectors) -> Self::Out<'a>;
+
+    /// Adds the index of every link in 0..N that no longer matches `vectors` to `changed`.
+    fn verify_link(vectors: &GoldenVectors, changed: &mut Vec<usize>);
+}
+
+impl<T: Chain<0>> GoldenLink<1> for T
+where
+    for<'a> <T as Chain<0>>::In<'a>: Codec,
+    for<'a> <T as Chain<0>>::Out<'a>: Codec,
+{
+    fn golden_link<'a>(input: Self::In<'a>, vectors: &mut GoldenVectors) -> Self::Out<'a> {
+        record::<0, T>(input, vectors)
+    }
+
+    fn verify_link(vectors: &GoldenVectors, changed: &mut Vec<usize>) {
+        if !verify::<0, T>(vectors) {
+            changed.push(0);
+        }
+    }
+}
+
+seq!(N in 2..=32 {
+    impl<T> GoldenLink<N> for T
+    where
+        T: Chain<0>,
+        for<'a> T: GoldenLink<{N - 1}, In<'a> = <T as Chain<0>>::In<'a>>,
+        for<'a> T: Chain<{N - 1}, In<'a> = <T as Link<{N - 1}>>::Out<'a>>,
+        for<'a> <T as Chain<{N - 1}>>::In<'a>: Codec,
+        for<'a> <T as Chain<{N - 1}>>::Out<'a>: Codec,
+    {
+        fn golden_link<'a>(input: Self::In<'a>, vectors: &mut GoldenVectors) -> Self::Out<'a> {
+            let out = <T as GoldenLink<{N - 1}>>::golden_link(input, vectors);
+            record::<{N - 1}, T>(out, vectors)
+        }
+
+        fn verify_link(vectors: &GoldenVectors, changed: &mut Vec<usize>) {
+            <T as GoldenLink<{N - 1}>>::verify_link(vectors, changed);
+            if !verify::<{N - 1}, T>(vectors) {
+                changed.push(N - 1);
+            }
+        }
+    }
+});
+
+pub trait GoldenCascade: Cascade {
+    /// Cascades as usual, also recording every link's input and output as golden vectors.
+    fn record_golden(input: Self::In<'_>) -> (Self::Out<'_>, GoldenVectors);
+
+    /// Replays each link on its own golden input, returning the indexes of the links whose
+    /// output no longer matches, or that have no usable golden vector.
+    fn verify_golden(vectors: &GoldenVectors) -> Vec<usize>;
+}
+
+impl<const N: usize, T: GoldenLink<N> + Length<Len = L<N>>> GoldenCascade for T {
+    fn record_golden(input: Self::In<'_>) -> (Self::Out<'_>, GoldenVectors) {
+        let mut vectors = GoldenVectors::default();
+        let out = <T as GoldenLink<N>>::golden_link(input, &mut vectors);
+        (out, vectors)
+    }
+
+    fn verify_golden(vectors: &GoldenVectors) -> Vec<usize> {
+        let mut changed = Vec::new();
+        <T as GoldenLink<N>>::verify_link(vectors, &mut changed);
+        changed
+    }
+}
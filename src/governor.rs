@@ -0,0 +1,270 @@
+use std::cell::Cell;
+use std::error::Error;
+use std::fmt;
+use std::time::Duration;
+
+use seq_macro::seq;
+
+use crate::{BudgetExceeded, Cascade, Chain, Clock, InRange, Length, Link, L, MemSize, MemoryBudget};
+use crate::{No, Select, SystemClock, Yes};
+
+/// A cheaper way of doing link `N`, e.g. sampling instead of processing everything, for
+/// `Degraded` to switch to when a governed cascade is running low on memory or time. Unlike
+/// `Accelerated` it doesn't have to give the same results as the `Chain<N>` impl.
+pub trait Degradable<const N: usize>: Chain<N>
+where
+    Self: InRange<N, <Self as Length>::Len>,
+{
+    fn degraded(input: Self::In<'_>) -> Self::Out<'_>;
+}
+
+/// Wraps a chain so that link `N` runs `Degradable::degraded` when it's reached under pressure in
+/// `cascade_governed`, and the regular `Chain<N>` otherwise. Every other link behaves as usual,
+/// like with `FeatureSelected`.
+pub struct Degraded<T, const N: usize>(T);
+
+impl<T: Length, const N: usize> Length for Degraded<T, N> {
+    type Len = T::Len;
+}
+
+impl<const M: usize, const N: usize, T> Chain<M> for Degraded<T, N>
+where
+    (): Select<M, N>,
+    T: Chain<M> + OrDegraded<M, <() as Select<M, N>>::Is>,
+    Self: InRange<M, Self::Len>,
+{
+    type In<'a> = <T as Chain<M>>::In<'a>;
+    type Out<'a> = <T as Chain<M>>::Out<'a>;
+
+    fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+        <T as OrDegraded<M, <() as Select<M, N>>::Is>>::or_degraded(input)
+    }
+}
+
+/// Implementation detail of `Degraded`, picking an implementation only for the selected link.
+pub trait OrDegraded<const M: usize, Is>: Chain<M>
+where
+    Self: InRange<M, <Self as Length>::Len>,
+{
+    fn or_degraded(input: Self::In<'_>) -> Self::Out<'_>;
+}
+
+impl<const M: usize, T: Chain<M>> OrDegraded<M, No> for T
+where
+    T: InRange<M, <T as Length>::Len>,
+{
+    fn or_degraded(input: Self::In<'_>) -> Self::Out<'_> {
+        <T as Chain<M>>::chain(input)
+    }
+}
+
+impl<const M: usize, T: Degradable<M>> OrDegraded<M, Yes> for T
+where
+    T: InRange<M, <T as Length>::Len>,
+{
+    fn or_degraded(input: Self::In<'_>) -> Self::Out<'_> {
+        match PRESSURE.replace(Pressure::Relaxed) {
+            Pressure::Degrade => {
+                let out = T::degraded(input);
+                PRESSURE.set(Pressure::Degraded);
+                out
+            }
+            _ => <T as Chain<M>>::chain(input),
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Pressure {
+    Relaxed,
+    /// A `Degraded` link should degrade.
+    Degrade,
+    /// A `Degraded` link did degrade.
+    Degraded,
+}
+
+thread_local! {
+    /// Whether the link a governor is running should degrade, and then whether it did.
+    static PRESSURE: Cell<Pressure> = const { Cell::new(Pressure::Relaxed) };
+}
+
+/// Puts back the pressure from before a governed link, even if the link panics.
+struct Restore(Pressure);
+
+impl Drop for Restore {
+    fn drop(&mut self) {
+        PRESSURE.set(self.0);
+    }
+}
+
+/// The memory and time a governed cascade may use, and how much of either it can use up before
+/// a `Degraded` link switches to its degraded version.
+#[derive(Clone, Copy, Debug)]
+pub struct Budgets<C = SystemClock> {
+    memory: usize,
+    time: Duration,
+    degrade_at: f64,
+    clock: C,
+}
+
+impl Budgets {
+    /// Degrades once 80% of either budget is used, timed on the system clock. `memory` counts
+    /// the bytes of every link's output, like `MemoryBudget`.
+    pub fn new(memory: usize, time: Duration) -> Self {
+        Self { memory, time, degrade_at: 0.8, clock: SystemClock }
+    }
+}
+
+impl<C: Clock> Budgets<C> {
+    /// Degrades once `fraction` of either budget is used, where 0 always degrades and 1 only
+    /// stops the cascade when a budget actually runs out.
+    ///
+    /// Panics if `fraction` isn't between 0 and 1.
+    pub fn degrade_at(self, fraction: f64) -> Self {
+        assert!((0.0..=1.0).contains(&fraction), "budgets degrade at a fraction between 0 and 1, not {fraction}");
+        Self { degrade_at: fraction, ..self }
+    }
+
+    pub fn with_clock<D: Clock>(self, clock: D) -> Budgets<D> {
+        Budgets { memory: self.memory, time: self.time, degrade_at: self.degrade_at, clock }
+    }
+}
+
+/// What ran out despite degrading.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OverBudget {
+    Memory(BudgetExceeded),
+    Time { stage: usize, elapsed: Duration, limit: Duration },
+}
+
+impl fmt::Display for OverBudget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OverBudget::Memory(exceeded) => exceeded.fmt(f),
+            OverBudget::Time { stage, elapsed, limit } => {
+                write!(f, "stage {stage} finished after {elapsed:?}, past the {limit:?} time budget")
+            }
+        }
+    }
+}
+
+impl Error for OverBudget {}
+
+/// Tracks one governed cascade against its `Budgets`.
+pub struct Governor<'b, C> {
+    budgets: &'b Budgets<C>,
+    memory: MemoryBudget,
+    started: Duration,
+    degraded: Vec<usize>,
+}
+
+impl<'b, C: Clock> Governor<'b, C> {
+    pub fn new(budgets: &'b Budgets<C>) -> Self {
+        Self { budgets, memory: MemoryBudget::new(budgets.memory), started: budgets.clock.now(), degraded: Vec::new() }
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        self.budgets.clock.now().saturating_sub(self.started)
+    }
+
+    pub fn memory_used(&self) -> usize {
+        self.memory.used()
+    }
+
+    /// The links that ran degraded so far.
+    pub fn degraded(&self) -> &[usize] {
+        &self.degraded
+    }
+
+    /// Whether either budget is used up to the point where links should degrade.
+    pub fn under_pressure(&self) -> bool {
+        let fraction = self.budgets.degrade_at;
+        self.memory.used() as f64 >= self.budgets.memory as f64 * fraction
+            || self.elapsed() >= self.budgets.time.mul_f64(fraction)
+    }
+}
+
+fn govern<'a, const N: usize, T, C: Clock>(
+    input: T::In<'a>,
+    governor: &mut Governor<'_, C>,
+) -> Result<T::Out<'a>, OverBudget>
+where
+    T: Chain<N> + InRange<N, <T as Length>::Len>,
+    T::Out<'a>: MemSize,
+{
+    let pressure = match governor.under_pressure() {
+        true => Pressure::Degrade,
+        false => Pressure::Relaxed,
+    };
+    let restore = Restore(PRESSURE.replace(pressure));
+    let out = T::chain(input);
+    if PRESSURE.get() == Pressure::Degraded {
+        governor.degraded.push(N);
+    }
+    drop(restore);
+    let elapsed = governor.elapsed();
+    if elapsed > governor.budgets.time {
+        return Err(OverBudget::Time { stage: N, elapsed, limit: governor.budgets.time });
+    }
+    governor.memory.charge(N, out.mem_size()).map_err(OverBudget::Memory)?;
+    Ok(out)
+}
+
+/// Same as `Link<N>`, but runs every link under a `Governor`.
+pub trait GovernedLink<const N: usize>: Link<N> {
+    fn governed_link<'a, C: Clock>(
+        input: Self::In<'a>,
+        governor: &mut Governor<'_, C>,
+    ) -> Result<Self::Out<'a>, OverBudget>;
+}
+
+impl<T: Chain<0>> GovernedLink<1> for T
+where
+    for<'a> <T as Chain<0>>::Out<'a>: MemSize,
+{
+    fn governed_link<'a, C: Clock>(
+        input: Self::In<'a>,
+        governor: &mut Governor<'_, C>,
+    ) -> Result<Self::Out<'a>, OverBudget> {
+        govern::<0, T, C>(input, governor)
+    }
+}
+
+seq!(N in 2..=32 {
+    impl<T> GovernedLink<N> for T
+    where
+        T: Chain<0>,
+        for<'a> T: GovernedLink<{N - 1}, In<'a> = <T as Chain<0>>::In<'a>>,
+        for<'a> T: Chain<{N - 1}, In<'a> = <T as Link<{N - 1}>>::Out<'a>>,
+        for<'a> <T as Chain<{N - 1}>>::Out<'a>: MemSize,
+    {
+        fn governed_link<'a, C: Clock>(
+            input: Self::In<'a>,
+            governor: &mut Governor<'_, C>,
+        ) -> Result<Self::Out<'a>, OverBudget> {
+            let out = <T as GovernedLink<{N - 1}>>::governed_link(input, governor)?;
+            govern::<{N - 1}, T, C>(out, governor)
+        }
+    }
+});
+
+pub trait GovernedCascade: Cascade {
+    /// Cascades `input` within `budgets`, switching a `Degraded` link to its degraded version if
+    /// either budget is nearly used up by the time it's reached, and also returning which links
+    /// ran degraded. Fails after the link that runs out of either budget anyway.
+    fn cascade_governed<'a, C: Clock>(
+        budgets: &Budgets<C>,
+        input: Self::In<'a>,
+    ) -> Result<(Self::Out<'a>, Vec<usize>), OverBudget>;
+}
+
+impl<const N: usize, T: GovernedLink<N> + Length<Len = L<N>>> GovernedCascade for T {
+    fn cascade_governed<'a, C: Clock>(
+        budgets: &Budgets<C>,
+        input: Self::In<'a>,
+    ) -> Result<(Self::Out<'a>, Vec<usize>), OverBudget> {
+        let mut governor = Governor::new(budgets);
+        let out = <T as GovernedLink<N>>::governed_link(input, &mut governor)?;
+        Ok((out, governor.degraded))
+    }
+}
@@ -0,0 +1,63 @@
+use crate::{Chain, InRange, Length, No, Select, Yes};
+
+/// A cheap check in front of an expensive link `N`, saying whether the link has anything to do
+/// for a given input. Only for links whose output is the same type as their input, since a
+/// skipped link passes its input along unchanged.
+pub trait GuardedStage<const N: usize>: Chain<N>
+where
+    Self: InRange<N, <Self as Length>::Len>,
+{
+    fn needed(input: &Self::In<'_>) -> bool;
+}
+
+/// Wraps a chain so that link `N` only runs when `GuardedStage::needed` says so, and is skipped
+/// otherwise. Every other link behaves as usual.
+pub struct Guarded<T, const N: usize>(T);
+
+impl<T: Length, const N: usize> Length for Guarded<T, N> {
+    type Len = T::Len;
+}
+
+impl<const M: usize, const N: usize, T> Chain<M> for Guarded<T, N>
+where
+    (): Select<M, N>,
+    T: Chain<M> + OrGuard<M, <() as Select<M, N>>::Is>,
+    Self: InRange<M, Self::Len>,
+{
+    type In<'a> = <T as Chain<M>>::In<'a>;
+    type Out<'a> = <T as Chain<M>>::Out<'a>;
+
+    fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+        <T as OrGuard<M, <() as Select<M, N>>::Is>>::or_guard(input)
+    }
+}
+
+/// Implementation detail of `Guarded`, checking the guard only for the selected link.
+pub trait OrGuard<const M: usize, Is>: Chain<M>
+where
+    Self: InRange<M, <Self as Length>::Len>,
+{
+    fn or_guard(input: Self::In<'_>) -> Self::Out<'_>;
+}
+
+impl<const M: usize, T: Chain<M>> OrGuard<M, No> for T
+where
+    T: InRange<M, <T as Length>::Len>,
+{
+    fn or_guard(input: Self::In<'_>) -> Self::Out<'_> {
+        <T as Chain<M>>::chain(input)
+    }
+}
+
+impl<const M: usize, T: GuardedStage<M>> OrGuard<M, Yes> for T
+where
+    T: InRange<M, <T as Length>::Len>,
+    for<'a> T: Chain<M, Out<'a> = <T as Chain<M>>::In<'a>>,
+{
+    fn or_guard(input: Self::In<'_>) -> Self::Out<'_> {
+        match T::needed(&input) {
+            true => <T as Chain<M>>::chain(input),
+            false => input,
+        }
+    }
+}
@@ -0,0 +1,22 @@
+use std::future::Future;
+
+use axum::Json;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::AsyncCascade;
+
+/// Axum handler running an `AsyncCascade` on the JSON request body, responding with its output
+/// as JSON. Mount it with e.g. `post(cascade_handler::<Pipeline>)`. Bodies that don't
+/// deserialize into `In` get rejected by axum before the cascade runs.
+pub fn cascade_handler<T>(Json(input): Json<T::In<'static>>) -> impl Future<Output = Json<T::Out<'static>>> + Send
+where
+    T: AsyncCascade + 'static,
+    T::In<'static>: DeserializeOwned + Send,
+    T::Out<'static>: Serialize + Send,
+{
+    // started outside the async block, since the compiler can't yet prove an `async fn` holding
+    // the cascade's future is `Send` (rust-lang/rust#100013)
+    let cascade = T::async_cascade(input);
+    async move { Json(cascade.await) }
+}
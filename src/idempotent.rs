@@ -0,0 +1,154 @@
+use std::collections::{HashSet, VecDeque};
+use std::hash::{BuildHasherDefault, DefaultHasher, Hash};
+use std::sync::Mutex;
+
+use crate::{FilterChain, InRange, Length, No, Select, Yes};
+
+/// Remembers the last `capacity` keys it was shown, forgetting the oldest first.
+pub struct SeenKeys<K> {
+    capacity: usize,
+    state: Mutex<SeenState<K>>,
+}
+
+struct SeenState<K> {
+    order: VecDeque<K>,
+    keys: HashSet<K, BuildHasherDefault<DefaultHasher>>,
+}
+
+impl<K: Hash + Eq + Clone> SeenKeys<K> {
+    /// Panics if `capacity` is zero, since nothing would ever be remembered.
+    pub const fn new(capacity: usize) -> Self {
+        assert!(capacity > 0, "seen keys have to remember at least one key");
+        Self {
+            capacity,
+            state: Mutex::new(SeenState {
+                order: VecDeque::new(),
+                keys: HashSet::with_hasher(BuildHasherDefault::new()),
+            }),
+        }
+    }
+
+    pub fn contains(&self, key: &K) -> bool {
+        self.state.lock().unwrap().keys.contains(key)
+    }
+
+    /// Records `key`, returning whether it's the first time it was seen (or the first time since
+    /// it was forgotten).
+    pub fn insert(&self, key: K) -> bool {
+        let mut state = self.state.lock().unwrap();
+        if !state.keys.insert(key.clone()) {
+            return false;
+        }
+        state.order.push_back(key);
+        if state.order.len() > self.capacity {
+            if let Some(oldest) = state.order.pop_front() {
+                state.keys.remove(&oldest);
+            }
+        }
+        true
+    }
+
+    /// Forgets `key`, returning whether it was remembered.
+    pub fn remove(&self, key: &K) -> bool {
+        let mut state = self.state.lock().unwrap();
+        if !state.keys.remove(key) {
+            return false;
+        }
+        state.order.retain(|seen| seen != key);
+        true
+    }
+
+    pub fn len(&self) -> usize {
+        self.state.lock().unwrap().order.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Says which inputs of link `N` count as the same for an `Idempotent` pipeline, and provides the
+/// keys seen so far, shared by every run.
+pub trait IdempotencyKey<const N: usize>: FilterChain<N>
+where
+    Self: InRange<N, <Self as Length>::Len>,
+{
+    type Key: Hash + Eq + Clone + 'static;
+
+    fn key(input: &Self::In<'_>) -> Self::Key;
+
+    fn seen() -> &'static SeenKeys<Self::Key>;
+}
+
+/// Wraps a filter chain so that link `N` drops any input whose key it has already handled, for
+/// pipelines fed by at-least-once delivery where a redelivered input mustn't repeat its side
+/// effects. Unlike caching, nothing is returned for a duplicate, it just isn't processed again.
+/// A key is claimed before the link runs, so a redelivery racing the first attempt is dropped too,
+/// but it's released again if the link panics, so an attempt that crashed partway gets processed
+/// again when it's redelivered. Every other link behaves as usual.
+pub struct Idempotent<T, const N: usize>(T);
+
+impl<T: Length, const N: usize> Length for Idempotent<T, N> {
+    type Len = T::Len;
+}
+
+impl<const M: usize, const N: usize, T> FilterChain<M> for Idempotent<T, N>
+where
+    (): Select<M, N>,
+    T: FilterChain<M> + OrDuplicate<M, <() as Select<M, N>>::Is>,
+    Self: InRange<M, Self::Len>,
+{
+    type In<'a> = <T as FilterChain<M>>::In<'a>;
+    type Out<'a> = <T as FilterChain<M>>::Out<'a>;
+
+    fn filter(input: Self::In<'_>) -> Option<Self::Out<'_>> {
+        <T as OrDuplicate<M, <() as Select<M, N>>::Is>>::or_duplicate(input)
+    }
+}
+
+/// Implementation detail of `Idempotent`, checking the key only for the selected link.
+pub trait OrDuplicate<const M: usize, Is>: FilterChain<M>
+where
+    Self: InRange<M, <Self as Length>::Len>,
+{
+    fn or_duplicate(input: Self::In<'_>) -> Option<Self::Out<'_>>;
+}
+
+impl<const M: usize, T: FilterChain<M>> OrDuplicate<M, No> for T
+where
+    T: InRange<M, <T as Length>::Len>,
+{
+    fn or_duplicate(input: Self::In<'_>) -> Option<Self::Out<'_>> {
+        <T as FilterChain<M>>::filter(input)
+    }
+}
+
+impl<const M: usize, T: IdempotencyKey<M>> OrDuplicate<M, Yes> for T
+where
+    T: InRange<M, <T as Length>::Len>,
+{
+    fn or_duplicate(input: Self::In<'_>) -> Option<Self::Out<'_>> {
+        let key = T::key(&input);
+        if !T::seen().insert(key.clone()) {
+            return None;
+        }
+        let mut claim = Claim { seen: T::seen(), key: Some(key) };
+        let out = <T as FilterChain<M>>::filter(input);
+        claim.key = None;
+        out
+    }
+}
+
+/// Releases a claimed key unless the link it was claimed for finished.
+struct Claim<K: Hash + Eq + Clone + 'static> {
+    seen: &'static SeenKeys<K>,
+    key: Option<K>,
+}
+
+impl<K: Hash + Eq + Clone + 'static> Drop for Claim<K> {
+    fn drop(&mut self) {
+        if let Some(key) = &self.key {
+            self.seen.remove(key);
+        }
+    }
+}
@@ -0,0 +1,34 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use crate::Cascade;
+
+pub trait IterCascade: Cascade {
+    /// Lazily cascades every input as the returned iterator is advanced.
+    fn cascade_iter<'a>(
+        inputs: impl IntoIterator<Item = Self::In<'a>>,
+    ) -> impl Iterator<Item = Self::Out<'a>> {
+        inputs.into_iter().map(Self::cascade)
+    }
+
+    /// Same as `cascade_iter`, but remembers the output for every distinct input, so inputs that
+    /// repeat in the stream reuse it instead of running the cascade again. The cache lives as long
+    /// as the iterator and grows with the number of distinct inputs.
+    fn cascade_iter_cached<'a>(
+        inputs: impl IntoIterator<Item = Self::In<'a>>,
+    ) -> impl Iterator<Item = Self::Out<'a>>
+    where
+        Self::In<'a>: Hash + Eq + Clone,
+        Self::Out<'a>: Clone,
+    {
+        let mut cache = HashMap::new();
+        inputs.into_iter().map(move |input: Self::In<'a>| {
+            cache
+                .entry(input.clone())
+                .or_insert_with(|| Self::cascade(input))
+                .clone()
+        })
+    }
+}
+
+impl<T: Cascade> IterCascade for T {}
@@ -0,0 +1,105 @@
+use std::future::Future;
+use std::time::Duration;
+
+use seq_macro::seq;
+
+use crate::sampled::splitmix64;
+use crate::{AsyncChain, AsyncLink, Clock, Length, SystemClock, L};
+
+/// Pseudo-random delays to insert before every link of an async pipeline, to shake out bugs that
+/// only show up under unlucky timing. The delays only depend on the seed, so a failing schedule
+/// can be replayed by running with the same seed again.
+#[derive(Clone, Copy, Debug)]
+pub struct Jitter<C = SystemClock> {
+    seed: u64,
+    max: Duration,
+    clock: C,
+}
+
+impl Jitter {
+    /// Delays of up to 10ms, slept on the system clock.
+    pub fn new(seed: u64) -> Self {
+        Self { seed, max: Duration::from_millis(10), clock: SystemClock }
+    }
+}
+
+impl<C: Clock> Jitter<C> {
+    pub fn with_max(self, max: Duration) -> Self {
+        Self { max, ..self }
+    }
+
+    pub fn with_clock<D: Clock>(self, clock: D) -> Jitter<D> {
+        Jitter { seed: self.seed, max: self.max, clock }
+    }
+
+    /// The delay before link `stage`, anywhere from zero up to and including the max.
+    pub fn delay(&self, stage: usize) -> Duration {
+        // skip straight to the `stage`th number of the sequence
+        let mut state = self.seed.wrapping_add((stage as u64).wrapping_mul(0x9e37_79b9_7f4a_7c15));
+        let nanos = self.max.as_nanos().min(u64::MAX as u128 - 1) as u64;
+        Duration::from_nanos(splitmix64(&mut state) % (nanos + 1))
+    }
+
+    /// The delays before each of the first `stages` links.
+    pub fn delays(&self, stages: usize) -> Vec<Duration> {
+        (0..stages).map(|stage| self.delay(stage)).collect()
+    }
+
+    async fn before(&self, stage: usize) {
+        let delay = self.delay(stage);
+        if !delay.is_zero() {
+            self.clock.sleep(delay).await;
+        }
+    }
+}
+
+/// Same as `AsyncLink<N>`, but sleeps for the jitter of each link before running it.
+pub trait JitterLink<const N: usize>: AsyncLink<N> {
+    fn jitter_link<'a, C: Clock>(jitter: &Jitter<C>, input: Self::In<'a>) -> impl Future<Output = Self::Out<'a>>;
+}
+
+impl<T: AsyncChain<0>> JitterLink<1> for T {
+    async fn jitter_link<'a, C: Clock>(jitter: &Jitter<C>, input: Self::In<'a>) -> Self::Out<'a> {
+        jitter.before(0).await;
+        <T as AsyncChain<0>>::chain(input).await
+    }
+}
+
+seq!(N in 2..=32 {
+    impl<T> JitterLink<N> for T
+    where
+        T: AsyncChain<0>,
+        for<'a> T: JitterLink<{N - 1}, In<'a> = <T as AsyncChain<0>>::In<'a>>,
+        for<'a> T: AsyncChain<{N - 1}, In<'a> = <T as AsyncLink<{N - 1}>>::Out<'a>>,
+    {
+        async fn jitter_link<'a, C: Clock>(jitter: &Jitter<C>, input: Self::In<'a>) -> Self::Out<'a> {
+            let out = <T as JitterLink<{N - 1}>>::jitter_link(jitter, input).await;
+            jitter.before(N - 1).await;
+            <T as AsyncChain<{N - 1}>>::chain(out).await
+        }
+    }
+});
+
+pub trait JitteredCascade {
+    type In<'a>;
+    type Out<'a>;
+
+    /// Cascades with up to 10ms of jitter before every link, seeded by `seed`.
+    fn async_cascade_jittered(seed: u64, input: Self::In<'_>) -> impl Future<Output = Self::Out<'_>>;
+
+    /// Cascades with the delays of `jitter` before every link.
+    fn async_cascade_with_jitter<'a, C: Clock>(jitter: &Jitter<C>, input: Self::In<'a>) -> impl Future<Output = Self::Out<'a>>;
+}
+
+impl<const N: usize, T: JitterLink<N> + Length<Len = L<N>>> JitteredCascade for T {
+    type In<'a> = <T as AsyncLink<N>>::In<'a>;
+    type Out<'a> = <T as AsyncLink<N>>::Out<'a>;
+
+    async fn async_cascade_jittered(seed: u64, input: Self::In<'_>) -> Self::Out<'_> {
+        <T as JitterLink<N>>::jitter_link(&Jitter::new(seed), input).await
+    }
+
+    fn async_cascade_with_jitter<'a, C: Clock>(jitter: &Jitter<C>, input: Self::In<'a>) -> impl Future<Output = Self::Out<'a>> {
+        <T as JitterLink<N>>::jitter_link(jitter, input)
+    }
+}
@@ -0,0 +1,72 @@
+use std::io::Write;
+use std::marker::PhantomData;
+use std::panic::{self, AssertUnwindSafe};
+use std::time::Instant;
+
+use serde_json::json;
+
+use crate::{Named, ObservedCascade, Observer, StageInfo};
+
+/// Observer that writes one self-contained JSON object per line for every link of `T`, giving
+/// its index, `Named` name, types, duration in nanoseconds and whether it finished, ready to be
+/// shipped to a log aggregator as is. Logging is best effort, write errors are ignored.
+pub struct JsonLogger<T, W> {
+    writer: W,
+    running: Option<(StageInfo, Instant)>,
+    pipeline: PhantomData<fn() -> T>,
+}
+
+impl<T: Named, W: Write> JsonLogger<T, W> {
+    pub fn new(writer: W) -> Self {
+        Self { writer, running: None, pipeline: PhantomData }
+    }
+
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+
+    fn log(&mut self, ok: bool) {
+        let Some((stage, started)) = self.running.take() else {
+            return;
+        };
+        let record = json!({
+            "stage": stage.index,
+            "name": T::stage_name(stage.index),
+            "input": stage.input,
+            "output": stage.output,
+            "duration_ns": started.elapsed().as_nanos() as u64,
+            "ok": ok,
+        });
+        let _ = writeln!(self.writer, "{record}");
+    }
+
+    /// Logs the link that was running as having failed, for when it panicked.
+    pub fn failed(&mut self) {
+        self.log(false);
+    }
+}
+
+impl<T: Named, W: Write> Observer for JsonLogger<T, W> {
+    fn before(&mut self, stage: &StageInfo) {
+        self.running = Some((*stage, Instant::now()));
+    }
+
+    fn after(&mut self, _: &StageInfo) {
+        self.log(true);
+    }
+}
+
+pub trait JsonLoggedCascade: ObservedCascade + Named + Sized {
+    /// Cascades as usual, writing a JSON log line to `writer` for every link. If a link panics
+    /// it's logged as failed before the panic carries on.
+    fn cascade_json_logged<W: Write>(input: Self::In<'_>, writer: W) -> Self::Out<'_> {
+        let mut logger = JsonLogger::<Self, W>::new(writer);
+        let out = panic::catch_unwind(AssertUnwindSafe(|| Self::observed_cascade(input, &mut logger)));
+        out.unwrap_or_else(|payload| {
+            logger.failed();
+            panic::resume_unwind(payload)
+        })
+    }
+}
+
+impl<T: ObservedCascade + Named> JsonLoggedCascade for T {}
@@ -0,0 +1,116 @@
+use std::future::{poll_fn, Future};
+use std::pin::pin;
+use std::sync::Mutex;
+use std::task::Poll;
+use std::time::Duration;
+
+use crate::{AsyncChain, Clock, InRange, Length, No, Select, Yes};
+
+/// How long a stage gets before `TimeoutWithLastGood` gives up on it, and the last value it
+/// produced in time to fall back on when it does.
+pub struct LastGoodCache<V, C> {
+    timeout: Duration,
+    last: Mutex<Option<V>>,
+    clock: C,
+}
+
+impl<V: Clone, C: Clock> LastGoodCache<V, C> {
+    pub const fn new(timeout: Duration, clock: C) -> Self {
+        Self { timeout, last: Mutex::new(None), clock }
+    }
+
+    pub fn last(&self) -> Option<V> {
+        self.last.lock().unwrap().clone()
+    }
+
+    fn store(&self, value: &V) {
+        *self.last.lock().unwrap() = Some(value.clone());
+    }
+
+    /// Waits on `stage` for up to the timeout, falling back on the last value it produced in time.
+    async fn race(&self, stage: impl Future<Output = V>) -> V {
+        let mut stage = pin!(stage);
+        let in_time = {
+            let mut timeout = pin!(self.clock.sleep(self.timeout));
+            poll_fn(|cx| match stage.as_mut().poll(cx) {
+                Poll::Ready(out) => Poll::Ready(Some(out)),
+                Poll::Pending => timeout.as_mut().poll(cx).map(|()| None),
+            })
+            .await
+        };
+        let out = match in_time {
+            Some(out) => out,
+            None => match self.last() {
+                Some(last) => return last,
+                None => stage.await,
+            },
+        };
+        self.store(&out);
+        out
+    }
+}
+
+/// Provides the cache shared by every run of a `TimeoutWithLastGood` pipeline. `Value` has to
+/// be the output of link `N`, which can't borrow from its input since it has to outlive the
+/// cascade that produced it.
+pub trait LastGood<const N: usize>: AsyncChain<N>
+where
+    Self: InRange<N, <Self as Length>::Len>,
+{
+    type Value: Clone + Send + 'static;
+    type Clock: Clock + Sync + 'static;
+
+    fn last_good() -> &'static LastGoodCache<Self::Value, Self::Clock>;
+}
+
+/// Wraps an async chain so that when link `N` takes longer than its cache's timeout, the cascade
+/// continues with the last output that link produced in time. Until there's been one, the link
+/// is waited on as usual. Every other link behaves as usual.
+pub struct TimeoutWithLastGood<T, const N: usize>(T);
+
+impl<T: Length, const N: usize> Length for TimeoutWithLastGood<T, N> {
+    type Len = T::Len;
+}
+
+impl<const M: usize, const N: usize, T> AsyncChain<M> for TimeoutWithLastGood<T, N>
+where
+    (): Select<M, N>,
+    T: AsyncChain<M> + OrLastGood<M, <() as Select<M, N>>::Is>,
+    Self: InRange<M, Self::Len>,
+{
+    type In<'a> = <T as AsyncChain<M>>::In<'a>;
+    type Out<'a> = <T as AsyncChain<M>>::Out<'a>;
+
+    fn chain(input: Self::In<'_>) -> impl Future<Output = Self::Out<'_>> + Send {
+        <T as OrLastGood<M, <() as Select<M, N>>::Is>>::or_last_good(input)
+    }
+}
+
+/// Implementation detail of `TimeoutWithLastGood`, applying the timeout only for the selected link.
+pub trait OrLastGood<const M: usize, Is>: AsyncChain<M>
+where
+    Self: InRange<M, <Self as Length>::Len>,
+{
+    fn or_last_good(input: Self::In<'_>) -> impl Future<Output = Self::Out<'_>> + Send;
+}
+
+impl<const M: usize, T: AsyncChain<M>> OrLastGood<M, No> for T
+where
+    T: InRange<M, <T as Length>::Len>,
+{
+    fn or_last_good(input: Self::In<'_>) -> impl Future<Output = Self::Out<'_>> + Send {
+        <T as AsyncChain<M>>::chain(input)
+    }
+}
+
+impl<const M: usize, T: LastGood<M>> OrLastGood<M, Yes> for T
+where
+    T: InRange<M, <T as Length>::Len>,
+    for<'a> T: AsyncChain<M, Out<'a> = <T as LastGood<M>>::Value>,
+{
+    fn or_last_good(input: Self::In<'_>) -> impl Future<Output = Self::Out<'_>> + Send {
+        let cache = T::last_good();
+        let stage = <T as AsyncChain<M>>::chain(input);
+        async move { cache.race(stage).await }
+    }
+}
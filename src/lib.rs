@@ -1,6 +1,237 @@
 use sealed::Len;
 use seq_macro::seq;
 
+mod ab;
+mod adaptive_batch;
+#[cfg(feature = "bumpalo")]
+mod allocators;
+mod async_chain;
+mod async_checkpoint;
+mod auto_convert;
+mod batch;
+#[cfg(feature = "tokio")]
+mod broadcast;
+#[cfg(feature = "futures")]
+mod buffered;
+mod builder;
+mod by_category;
+mod checkpoint;
+mod circuit_breaker;
+mod clock;
+mod codec;
+mod coerce;
+#[cfg(feature = "arrow")]
+mod columnar;
+mod compose;
+#[cfg(feature = "flate2")]
+mod compress;
+mod context;
+mod contract;
+mod corpus;
+mod coverage;
+mod critical_path;
+mod dag;
+mod debounce;
+mod definition_hash;
+mod deterministic;
+mod diff;
+#[cfg(feature = "disk_checkpoint")]
+mod disk_checkpoint;
+mod distributed;
+mod dual;
+mod dynamic;
+mod exactly_once;
+mod feature_selected;
+mod filter;
+mod fold_flow;
+mod folded;
+mod fuse;
+mod generator;
+mod golden;
+mod governor;
+mod guarded;
+#[cfg(feature = "axum")]
+mod http;
+mod idempotent;
+mod iter;
+mod jitter;
+#[cfg(feature = "json_log")]
+mod json_log;
+mod last_good;
+#[cfg(feature = "openlineage")]
+mod lineage;
+mod locale;
+mod map_each;
+mod maybe_async;
+mod memory;
+mod merge;
+mod middleware;
+mod mock;
+mod monitor;
+mod observe;
+mod optional;
+#[cfg(feature = "proptest")]
+mod oracle;
+#[cfg(feature = "opentelemetry")]
+mod otel;
+#[cfg(feature = "rayon")]
+mod parallelism;
+mod partial_eval;
+#[cfg(feature = "core_affinity")]
+mod pinned;
+mod pipeline_cache;
+mod profile;
+mod push;
+mod query;
+mod rate_limit;
+mod recorder;
+mod recovery;
+mod registry;
+mod repeat;
+mod report;
+mod route;
+mod sampled;
+mod schema;
+mod select;
+mod selectivity;
+mod sequence;
+#[cfg(feature = "tower")]
+mod service;
+mod shadow;
+mod shape;
+mod sla;
+#[cfg(feature = "insta")]
+mod snapshot;
+mod split;
+#[cfg(feature = "prometheus")]
+mod stage_metrics;
+mod stage_tests;
+mod stateful;
+mod thread_safe;
+mod transaction;
+mod ttl_memoized;
+mod type_flow;
+mod typed;
+mod uninit;
+mod validate;
+#[cfg(feature = "yaml")]
+mod workflow;
+pub use ab::*;
+pub use adaptive_batch::*;
+#[cfg(feature = "bumpalo")]
+pub use allocators::*;
+pub use async_chain::*;
+pub use async_checkpoint::*;
+pub use auto_convert::*;
+pub use batch::*;
+#[cfg(feature = "tokio")]
+pub use broadcast::*;
+#[cfg(feature = "futures")]
+pub use buffered::*;
+pub use builder::*;
+pub use by_category::*;
+pub use checkpoint::*;
+pub use circuit_breaker::*;
+pub use clock::*;
+pub use codec::*;
+pub use coerce::*;
+#[cfg(feature = "arrow")]
+pub use columnar::*;
+pub use compose::*;
+#[cfg(feature = "flate2")]
+pub use compress::*;
+pub use context::*;
+pub use contract::*;
+pub use corpus::*;
+pub use coverage::*;
+pub use critical_path::*;
+pub use dag::*;
+pub use debounce::*;
+pub use definition_hash::*;
+pub use deterministic::*;
+pub use diff::*;
+#[cfg(feature = "disk_checkpoint")]
+pub use disk_checkpoint::*;
+pub use distributed::*;
+pub use dual::*;
+pub use dynamic::*;
+pub use exactly_once::*;
+pub use feature_selected::*;
+pub use filter::*;
+pub use fold_flow::*;
+pub use folded::*;
+pub use fuse::*;
+pub use generator::*;
+pub use golden::*;
+pub use governor::*;
+pub use guarded::*;
+#[cfg(feature = "axum")]
+pub use http::*;
+pub use idempotent::*;
+pub use iter::*;
+pub use jitter::*;
+#[cfg(feature = "json_log")]
+pub use json_log::*;
+pub use last_good::*;
+#[cfg(feature = "openlineage")]
+pub use lineage::*;
+pub use locale::*;
+pub use map_each::*;
+pub use maybe_async::*;
+pub use memory::*;
+pub use merge::*;
+pub use middleware::*;
+pub use mock::*;
+pub use monitor::*;
+pub use observe::*;
+pub use optional::*;
+#[cfg(feature = "proptest")]
+pub use oracle::*;
+#[cfg(feature = "opentelemetry")]
+pub use otel::*;
+#[cfg(feature = "rayon")]
+pub use parallelism::*;
+pub use partial_eval::*;
+#[cfg(feature = "core_affinity")]
+pub use pinned::*;
+pub use pipeline_cache::*;
+pub use profile::*;
+pub use push::*;
+pub use query::*;
+pub use rate_limit::*;
+pub use recorder::*;
+pub use recovery::*;
+pub use registry::*;
+pub use repeat::*;
+pub use report::*;
+pub use route::*;
+pub use sampled::*;
+pub use schema::*;
+pub use select::*;
+pub use selectivity::*;
+pub use sequence::*;
+#[cfg(feature = "tower")]
+pub use service::*;
+pub use shadow::*;
+pub use shape::*;
+pub use sla::*;
+#[cfg(feature = "insta")]
+pub use snapshot::*;
+pub use split::*;
+#[cfg(feature = "prometheus")]
+pub use stage_metrics::*;
+pub use stage_tests::*;
+pub use stateful::*;
+pub use thread_safe::*;
+pub use transaction::*;
+pub use ttl_memoized::*;
+pub use type_flow::*;
+pub use typed::*;
+pub use uninit::*;
+pub use validate::*;
+#[cfg(feature = "yaml")]
+pub use workflow::*;
+
 /// WIP I'm stuck between requiring `Length` trait and eliminating it
 ///     If it's kept, it ensures the user cannot implement Chain past its length
 ///         Which is a good guardrail, since it ensures the user always knows the chain length
@@ -15,9 +246,9 @@ use seq_macro::seq;
 ///      this is desireable to reduce verbosity (which there is already too much of IMO)
 ///      this can be simplified by supporting sequences:
 /// 
-/// WIP often we don't want to be able to transform inputs -> outputs during the chain
-///     it would be helpful for the user to be able to implement a Sequence<N> wrapper
-///     this would wrap the Chain<N> implementation, forcing its `In` and `Out` to be the same value
+/// DONE often we don't want to be able to transform inputs -> outputs during the chain
+///     so users can now implement `Sequence<N>`, which wraps the `Chain<N>` implementation,
+///     forcing its `In` and `Out` to be the same `Homogeneous::Item`
 const _WIP: () = ();
 
 /// Require all Length::Len types to be `L<const N: usize>` so that the InRange traits can be
@@ -0,0 +1,58 @@
+use std::any::type_name;
+
+use serde_json::{json, Value};
+
+use crate::type_flow::strip_paths;
+use crate::{Named, TypeFlow};
+
+const PRODUCER: &str = "https://crates.io/crates/chain_link";
+const JOB_EVENT: &str = "https://openlineage.io/spec/2-0-2/OpenLineage.json#/$defs/JobEvent";
+const SCHEMA_FACET: &str = "https://openlineage.io/spec/facets/1-1-1/SchemaDatasetFacet.json#/$defs/SchemaDatasetFacet";
+
+fn dataset(namespace: &str, name: &str, type_name: &str) -> Value {
+    json!({
+        "namespace": namespace,
+        "name": name,
+        "facets": {
+            "schema": {
+                "_producer": PRODUCER,
+                "_schemaURL": SCHEMA_FACET,
+                "fields": [{ "name": "value", "type": strip_paths(type_name) }],
+            },
+        },
+    })
+}
+
+/// Describes a pipeline as static OpenLineage lineage, for data catalogs that track where data
+/// comes from. Every link becomes a job named after the pipeline and its `Named` name, or its
+/// index if it has none, reading the dataset the previous link wrote and writing its own
+/// `<job>.out` dataset. The pipeline's input is the `<pipeline>.in` dataset.
+pub trait OpenLineage: TypeFlow + Named {
+    /// One OpenLineage `JobEvent` per link, in order. `event_time` has to be an ISO 8601 date and
+    /// time, like `2024-01-01T00:00:00Z`.
+    fn openlineage_events(namespace: &str, event_time: &str) -> Vec<Value> {
+        let pipeline = strip_paths(type_name::<Self>());
+        let stages = Self::stage_infos();
+        let mut input = format!("{pipeline}.in");
+        let mut events = Vec::with_capacity(stages.len());
+        for stage in &stages {
+            let job = match Self::stage_name(stage.index) {
+                Some(name) => format!("{pipeline}.{name}"),
+                None => format!("{pipeline}.{}", stage.index),
+            };
+            let output = format!("{job}.out");
+            events.push(json!({
+                "eventTime": event_time,
+                "producer": PRODUCER,
+                "schemaURL": JOB_EVENT,
+                "job": { "namespace": namespace, "name": job },
+                "inputs": [dataset(namespace, &input, stage.input)],
+                "outputs": [dataset(namespace, &output, stage.output)],
+            }));
+            input = output;
+        }
+        events
+    }
+}
+
+impl<T: TypeFlow + Named> OpenLineage for T {}
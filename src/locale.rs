@@ -0,0 +1,51 @@
+use crate::ContextCascade;
+
+/// How numbers are written in a given locale.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Locale {
+    pub decimal_separator: char,
+    pub group_separator: char,
+}
+
+impl Locale {
+    pub const EN_US: Self = Self { decimal_separator: '.', group_separator: ',' };
+    pub const DE_DE: Self = Self { decimal_separator: ',', group_separator: '.' };
+    pub const FR_FR: Self = Self { decimal_separator: ',', group_separator: '\u{202f}' };
+
+    /// Writes `value` rounded to `decimals` places, with its integer part grouped in thousands.
+    pub fn format(&self, value: f64, decimals: usize) -> String {
+        let plain = format!("{:.*}", decimals, value.abs());
+        let (integer, fraction) = plain.split_once('.').unwrap_or((&plain, ""));
+
+        let mut formatted = String::new();
+        if value.is_sign_negative() && plain.bytes().any(|b| b.is_ascii_digit() && b != b'0') {
+            formatted.push('-');
+        }
+        for (i, digit) in integer.chars().enumerate() {
+            if i > 0 && (integer.len() - i).is_multiple_of(3) {
+                formatted.push(self.group_separator);
+            }
+            formatted.push(digit);
+        }
+        if !fraction.is_empty() {
+            formatted.push(self.decimal_separator);
+            formatted.push_str(fraction);
+        }
+        formatted
+    }
+}
+
+impl Default for Locale {
+    fn default() -> Self {
+        Self::EN_US
+    }
+}
+
+pub trait LocaleCascade: ContextCascade<Context = Locale> {
+    /// Cascades `input` with every link formatting for `locale`.
+    fn cascade_with_locale<'a>(locale: Locale, input: Self::In<'a>) -> Self::Out<'a> {
+        Self::cascade_with(&locale, input)
+    }
+}
+
+impl<T: ContextCascade<Context = Locale>> LocaleCascade for T {}
@@ -0,0 +1,30 @@
+use std::marker::PhantomData;
+
+use crate::{Homogeneous, InRange, Length, Sequence};
+
+/// A transform over a single type, with no `self` so it can be named as a type parameter.
+pub trait Transform<Item> {
+    fn apply(item: Item) -> Item;
+}
+
+/// Wraps a sequence so that `F` is applied to the output of every step, for cross-cutting
+/// concerns like normalizing after each stage.
+pub struct MapEach<T, F>(T, PhantomData<F>);
+
+impl<T: Homogeneous, F> Homogeneous for MapEach<T, F> {
+    type Item = T::Item;
+}
+
+impl<T: Length, F> Length for MapEach<T, F> {
+    type Len = T::Len;
+}
+
+impl<const N: usize, T: Sequence<N>, F: Transform<T::Item>> Sequence<N> for MapEach<T, F>
+where
+    T: InRange<N, <T as Length>::Len>,
+    Self: InRange<N, Self::Len>,
+{
+    fn step(item: Self::Item) -> Self::Item {
+        F::apply(T::step(item))
+    }
+}
@@ -0,0 +1,57 @@
+use seq_macro::seq;
+
+use crate::{AsyncChain, InRange, Length, L};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum StageKind {
+    /// Never actually awaits anything, so its future is ready on the first poll.
+    Sync,
+    Async,
+}
+
+/// An `AsyncChain<N>` that declares whether it really is async, for pipelines that mix cheap
+/// synchronous links in with async ones. A scheduler can use this to decide where each link
+/// should run, e.g. keeping sync links inline and sending async ones off to a runtime.
+pub trait MaybeAsyncChain<const N: usize>: AsyncChain<N>
+where
+    Self: InRange<N, <Self as Length>::Len>,
+{
+    const KIND: StageKind;
+}
+
+/// Writes the `StageKind` of every link in 0..N.
+pub trait KindLink<const N: usize> {
+    fn kind_link(kinds: &mut [StageKind]);
+}
+
+impl<T: MaybeAsyncChain<0>> KindLink<1> for T {
+    fn kind_link(kinds: &mut [StageKind]) {
+        kinds[0] = <T as MaybeAsyncChain<0>>::KIND;
+    }
+}
+
+seq!(N in 2..=32 {
+    impl<T: KindLink<{N - 1}> + MaybeAsyncChain<{N - 1}>> KindLink<N> for T {
+        fn kind_link(kinds: &mut [StageKind]) {
+            <T as KindLink<{N - 1}>>::kind_link(kinds);
+            kinds[N - 1] = <T as MaybeAsyncChain<{N - 1}>>::KIND;
+        }
+    }
+});
+
+pub trait StageKinds {
+    /// Always `[StageKind; N]` where N is the length of the chain.
+    type Kinds;
+
+    fn stage_kinds() -> Self::Kinds;
+}
+
+impl<const N: usize, T: KindLink<N> + Length<Len = L<N>>> StageKinds for T {
+    type Kinds = [StageKind; N];
+
+    fn stage_kinds() -> Self::Kinds {
+        let mut kinds = [StageKind::Sync; N];
+        <T as KindLink<N>>::kind_link(&mut kinds);
+        kinds
+    }
+}
@@ -0,0 +1,164 @@
+use std::error::Error;
+use std::fmt;
+use std::mem::size_of;
+
+use seq_macro::seq;
+
+use crate::{Cascade, Chain, InRange, Length, Link, L};
+
+/// Rough number of bytes a value holds on to, counting its heap allocations as well as itself.
+pub trait MemSize {
+    fn mem_size(&self) -> usize;
+}
+
+macro_rules! impl_mem_size_plain {
+    ($($ty:ty),*) => {$(
+        impl MemSize for $ty {
+            fn mem_size(&self) -> usize {
+                size_of::<Self>()
+            }
+        }
+    )*};
+}
+
+impl_mem_size_plain!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64, bool, char, ());
+
+impl MemSize for String {
+    fn mem_size(&self) -> usize {
+        size_of::<Self>() + self.capacity()
+    }
+}
+
+impl<T: MemSize> MemSize for Vec<T> {
+    fn mem_size(&self) -> usize {
+        let spare = (self.capacity() - self.len()) * size_of::<T>();
+        size_of::<Self>() + spare + self.iter().map(MemSize::mem_size).sum::<usize>()
+    }
+}
+
+impl<T: MemSize> MemSize for Box<T> {
+    fn mem_size(&self) -> usize {
+        size_of::<Self>() + (**self).mem_size()
+    }
+}
+
+impl<T: MemSize> MemSize for Option<T> {
+    fn mem_size(&self) -> usize {
+        match self {
+            // the inner value's own size already includes any niche the option lives in
+            Some(value) => size_of::<Self>() - size_of::<T>() + value.mem_size(),
+            None => size_of::<Self>(),
+        }
+    }
+}
+
+impl<A: MemSize, B: MemSize> MemSize for (A, B) {
+    fn mem_size(&self) -> usize {
+        size_of::<Self>() - size_of::<A>() - size_of::<B>() + self.0.mem_size() + self.1.mem_size()
+    }
+}
+
+/// Total number of bytes the intermediate values of a cascade may add up to.
+#[derive(Clone, Debug)]
+pub struct MemoryBudget {
+    limit: usize,
+    used: usize,
+}
+
+impl MemoryBudget {
+    pub fn new(limit: usize) -> Self {
+        Self { limit, used: 0 }
+    }
+
+    pub fn used(&self) -> usize {
+        self.used
+    }
+
+    pub fn remaining(&self) -> usize {
+        self.limit - self.used
+    }
+
+    /// Charges the output of link `stage`, failing without charging it if it doesn't fit.
+    pub fn charge(&mut self, stage: usize, bytes: usize) -> Result<(), BudgetExceeded> {
+        match self.used.checked_add(bytes).filter(|used| *used <= self.limit) {
+            Some(used) => {
+                self.used = used;
+                Ok(())
+            }
+            None => Err(BudgetExceeded { stage, used: self.used, requested: bytes, limit: self.limit }),
+        }
+    }
+}
+
+/// Link `stage` produced `requested` more bytes than the `limit - used` left in the budget.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BudgetExceeded {
+    pub stage: usize,
+    pub used: usize,
+    pub requested: usize,
+    pub limit: usize,
+}
+
+impl fmt::Display for BudgetExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "stage {} needed {} bytes with {} of {} already used",
+            self.stage, self.requested, self.used, self.limit,
+        )
+    }
+}
+
+impl Error for BudgetExceeded {}
+
+fn charge<'a, const N: usize, T>(input: T::In<'a>, budget: &mut MemoryBudget) -> Result<T::Out<'a>, BudgetExceeded>
+where
+    T: Chain<N> + InRange<N, <T as Length>::Len>,
+    T::Out<'a>: MemSize,
+{
+    let out = T::chain(input);
+    budget.charge(N, out.mem_size())?;
+    Ok(out)
+}
+
+/// Same as `Link<N>`, but charges every link's output to a `MemoryBudget`, stopping as soon as
+/// one doesn't fit.
+pub trait BudgetedLink<const N: usize>: Link<N> {
+    fn budgeted_link<'a>(input: Self::In<'a>, budget: &mut MemoryBudget) -> Result<Self::Out<'a>, BudgetExceeded>;
+}
+
+impl<T: Chain<0>> BudgetedLink<1> for T
+where
+    for<'a> <T as Chain<0>>::Out<'a>: MemSize,
+{
+    fn budgeted_link<'a>(input: Self::In<'a>, budget: &mut MemoryBudget) -> Result<Self::Out<'a>, BudgetExceeded> {
+        charge::<0, T>(input, budget)
+    }
+}
+
+seq!(N in 2..=32 {
+    impl<T> BudgetedLink<N> for T
+    where
+        T: Chain<0>,
+        for<'a> T: BudgetedLink<{N - 1}, In<'a> = <T as Chain<0>>::In<'a>>,
+        for<'a> T: Chain<{N - 1}, In<'a> = <T as Link<{N - 1}>>::Out<'a>>,
+        for<'a> <T as Chain<{N - 1}>>::Out<'a>: MemSize,
+    {
+        fn budgeted_link<'a>(input: Self::In<'a>, budget: &mut MemoryBudget) -> Result<Self::Out<'a>, BudgetExceeded> {
+            let out = <T as BudgetedLink<{N - 1}>>::budgeted_link(input, budget)?;
+            charge::<{N - 1}, T>(out, budget)
+        }
+    }
+});
+
+pub trait BudgetedCascade: Cascade {
+    /// Cascades as usual, unless the outputs of the links so far add up to more than `limit`
+    /// bytes, in which case the cascade is aborted after the link that went over.
+    fn cascade_budgeted(input: Self::In<'_>, limit: usize) -> Result<Self::Out<'_>, BudgetExceeded>;
+}
+
+impl<const N: usize, T: BudgetedLink<N> + Length<Len = L<N>>> BudgetedCascade for T {
+    fn cascade_budgeted(input: Self::In<'_>, limit: usize) -> Result<Self::Out<'_>, BudgetExceeded> {
+        <T as BudgetedLink<N>>::budgeted_link(input, &mut MemoryBudget::new(limit))
+    }
+}
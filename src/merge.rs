@@ -0,0 +1,60 @@
+use crate::Cascade;
+
+/// How `merge` picks which stream the next input comes from.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum MergeStrategy {
+    /// Takes turns, one input from each stream that has one.
+    #[default]
+    RoundRobin,
+    /// Always takes from the earliest stream that has an input, so later streams only get a turn
+    /// while every stream before them is empty.
+    Priority,
+}
+
+/// Fan-in of several streams of inputs into one, made by `merge`.
+pub struct Merge<S> {
+    streams: Vec<S>,
+    strategy: MergeStrategy,
+    turn: usize,
+}
+
+/// Merges `streams` into one according to `strategy`. Every stream is asked again on each step,
+/// so one that has nothing right now, like a channel's `try_iter`, isn't dropped, and the merge
+/// only ends once every stream comes up empty on the same step.
+pub fn merge<S: IntoIterator>(streams: impl IntoIterator<Item = S>, strategy: MergeStrategy) -> Merge<S::IntoIter> {
+    Merge { streams: streams.into_iter().map(IntoIterator::into_iter).collect(), strategy, turn: 0 }
+}
+
+impl<S: Iterator> Iterator for Merge<S> {
+    type Item = S::Item;
+
+    fn next(&mut self) -> Option<S::Item> {
+        let count = self.streams.len();
+        for offset in 0..count {
+            let index = match self.strategy {
+                MergeStrategy::RoundRobin => (self.turn + offset) % count,
+                MergeStrategy::Priority => offset,
+            };
+            if let Some(item) = self.streams[index].next() {
+                self.turn = (index + 1) % count;
+                return Some(item);
+            }
+        }
+        None
+    }
+}
+
+pub trait MergeCascade: Cascade {
+    /// Lazily cascades the inputs of every stream, merged into one by `strategy`.
+    fn cascade_merge<'a, S>(
+        streams: impl IntoIterator<Item = S>,
+        strategy: MergeStrategy,
+    ) -> impl Iterator<Item = Self::Out<'a>>
+    where
+        S: IntoIterator<Item = Self::In<'a>>,
+    {
+        merge(streams, strategy).map(Self::cascade)
+    }
+}
+
+impl<T: Cascade> MergeCascade for T {}
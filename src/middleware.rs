@@ -0,0 +1,37 @@
+use std::marker::PhantomData;
+
+use crate::{Cascade, Chain, Length, L};
+
+/// A cross-cutting concern applied around a whole cascade `P`, like logging, auth or caching.
+/// `wrap` gets the input and decides whether and how to hand it to `next`, which runs the rest of
+/// the stack and finally the pipeline itself. Middleware that doesn't care about the pipeline's
+/// types can be implemented for every `P: Cascade` at once, while middleware that does, like
+/// checking a request, is implemented only for pipelines of that shape.
+pub trait Middleware<P: Cascade> {
+    fn wrap<'a>(input: P::In<'a>, next: impl FnOnce(P::In<'a>) -> P::Out<'a>) -> P::Out<'a>;
+}
+
+/// Stacks two middlewares, with `A` on the outside: it sees the input first and the output last.
+/// Nest the tuples for deeper stacks.
+impl<P: Cascade, A: Middleware<P>, B: Middleware<P>> Middleware<P> for (A, B) {
+    fn wrap<'a>(input: P::In<'a>, next: impl FnOnce(P::In<'a>) -> P::Out<'a>) -> P::Out<'a> {
+        A::wrap(input, |input| B::wrap(input, next))
+    }
+}
+
+/// Pipeline `P` with middleware `M` around it, a single link cascade of the same shape as `P`.
+/// Being a cascade itself it can be wrapped again, or used anywhere `P` could be.
+pub struct Wrapped<P, M>(PhantomData<(P, M)>);
+
+impl<P, M> Length for Wrapped<P, M> {
+    type Len = L<1>;
+}
+
+impl<P: Cascade, M: Middleware<P>> Chain<0> for Wrapped<P, M> {
+    type In<'a> = P::In<'a>;
+    type Out<'a> = P::Out<'a>;
+
+    fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+        M::wrap(input, P::cascade)
+    }
+}
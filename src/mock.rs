@@ -0,0 +1,89 @@
+use std::any::{type_name, Any, TypeId};
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::marker::PhantomData;
+use std::rc::Rc;
+
+use crate::{Chain, Length, L};
+
+type Behavior<I, O> = Rc<dyn Fn(I) -> O>;
+
+thread_local! {
+    /// What each mock type is currently set up to do, on this thread only so that tests running in
+    /// parallel can't see each other's mocks.
+    static MOCKS: RefCell<HashMap<TypeId, Box<dyn Any>>> = RefCell::new(HashMap::new());
+}
+
+/// Stand-in `Cascade` from `I` to `O`, for testing code that's generic over a pipeline without
+/// running the real one. Since cascades are called through their type rather than a value, what
+/// the mock does is set up on the type itself with `returning` or `with`, until the returned
+/// guard is dropped. Give `Tag` a distinct type to have several mocks of the same shape at once.
+pub struct MockCascade<I, O, Tag = ()>(PhantomData<fn(I) -> (O, Tag)>);
+
+impl<I, O, Tag> Length for MockCascade<I, O, Tag> {
+    type Len = L<1>;
+}
+
+impl<I: 'static, O: 'static, Tag: 'static> MockCascade<I, O, Tag> {
+    /// Makes every cascade return a clone of `out`, whatever the input.
+    pub fn returning(out: O) -> MockGuard<Self>
+    where
+        O: Clone,
+    {
+        Self::with(move |_| out.clone())
+    }
+
+    /// Makes every cascade return whatever `behavior` maps the input to.
+    pub fn with(behavior: impl Fn(I) -> O + 'static) -> MockGuard<Self> {
+        let calls = Rc::new(Cell::new(0));
+        let counter = calls.clone();
+        let behavior: Behavior<I, O> = Rc::new(move |input| {
+            counter.set(counter.get() + 1);
+            behavior(input)
+        });
+        let previous = MOCKS.with_borrow_mut(|mocks| mocks.insert(TypeId::of::<Self>(), Box::new(behavior)));
+        MockGuard { previous, calls, mock: PhantomData }
+    }
+}
+
+impl<I: 'static, O: 'static, Tag: 'static> Chain<0> for MockCascade<I, O, Tag> {
+    type In<'a> = I;
+    type Out<'a> = O;
+
+    fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+        // cloned out so that the behavior can cascade other mocks
+        let behavior = MOCKS.with_borrow(|mocks| {
+            let behavior = mocks.get(&TypeId::of::<Self>())?.downcast_ref::<Behavior<I, O>>()?;
+            Some(behavior.clone())
+        });
+        match behavior {
+            Some(behavior) => behavior(input),
+            None => panic!("{} cascaded without being set up by `returning` or `with`", type_name::<Self>()),
+        }
+    }
+}
+
+/// Keeps a `MockCascade` set up until dropped, then puts back whatever it replaced.
+#[must_use = "the mock is only set up until the guard is dropped"]
+pub struct MockGuard<M: 'static> {
+    previous: Option<Box<dyn Any>>,
+    calls: Rc<Cell<usize>>,
+    mock: PhantomData<M>,
+}
+
+impl<M: 'static> MockGuard<M> {
+    /// How many times the mock was cascaded while set up by this guard.
+    pub fn calls(&self) -> usize {
+        self.calls.get()
+    }
+}
+
+impl<M: 'static> Drop for MockGuard<M> {
+    fn drop(&mut self) {
+        let previous = self.previous.take();
+        MOCKS.with_borrow_mut(|mocks| match previous {
+            Some(previous) => mocks.insert(TypeId::of::<M>(), previous),
+            None => mocks.remove(&TypeId::of::<M>()),
+        });
+    }
+}
@@ -0,0 +1,79 @@
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use crate::{Cascade, Clock};
+
+/// Levels of a sparkline bar, lowest first.
+const BARS: &[u8] = b"_.,:-=+*#@";
+
+struct Samples<C> {
+    clock: C,
+    started: Duration,
+    per_second: Vec<u64>,
+}
+
+/// Handle onto the throughput of a `cascade_iter_monitored` stream, counting outputs in one
+/// second buckets on its clock. Clones share the same counts, so one can be handed to another
+/// thread to watch the stream live.
+pub struct ThroughputMonitor<C> {
+    samples: Arc<Mutex<Samples<C>>>,
+}
+
+impl<C> Clone for ThroughputMonitor<C> {
+    fn clone(&self) -> Self {
+        Self { samples: self.samples.clone() }
+    }
+}
+
+impl<C: Clock> ThroughputMonitor<C> {
+    pub fn new(clock: C) -> Self {
+        let started = clock.now();
+        Self { samples: Arc::new(Mutex::new(Samples { clock, started, per_second: Vec::new() })) }
+    }
+
+    /// Counts one output in the current second.
+    pub fn record(&self) {
+        let mut samples = self.samples.lock().unwrap();
+        let second = (samples.clock.now() - samples.started).as_secs() as usize;
+        if samples.per_second.len() <= second {
+            samples.per_second.resize(second + 1, 0);
+        }
+        samples.per_second[second] += 1;
+    }
+
+    /// Outputs per second, from the first second up to the last one anything was recorded in.
+    pub fn samples(&self) -> Vec<u64> {
+        self.samples.lock().unwrap().per_second.clone()
+    }
+
+    /// One ASCII character per second, scaled so the busiest second is the tallest bar.
+    pub fn sparkline(&self) -> String {
+        let samples = self.samples();
+        let max = samples.iter().copied().max().unwrap_or(0).max(1);
+        let top = (BARS.len() - 1) as u64;
+        samples
+            .iter()
+            .map(|&count| BARS[(count * top).div_ceil(max) as usize] as char)
+            .collect()
+    }
+}
+
+pub trait MonitoredCascade: Cascade {
+    /// Lazily cascades every input like `cascade_iter`, recording each output on the returned
+    /// monitor as it's produced.
+    fn cascade_iter_monitored<'a, C: Clock>(
+        inputs: impl IntoIterator<Item = Self::In<'a>>,
+        clock: C,
+    ) -> (impl Iterator<Item = Self::Out<'a>>, ThroughputMonitor<C>) {
+        let monitor = ThroughputMonitor::new(clock);
+        let recorder = monitor.clone();
+        let outputs = inputs.into_iter().map(move |input| {
+            let out = Self::cascade(input);
+            recorder.record();
+            out
+        });
+        (outputs, monitor)
+    }
+}
+
+impl<T: Cascade> MonitoredCascade for T {}
@@ -0,0 +1,103 @@
+use std::any::type_name;
+
+use seq_macro::seq;
+
+use crate::{Cascade, Chain, InRange, Length, Link, L};
+
+/// Describes a single link in the chain to an `Observer`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct StageInfo {
+    pub index: usize,
+    pub input: &'static str,
+    pub output: &'static str,
+}
+
+impl StageInfo {
+    pub fn of<const N: usize, T>() -> Self
+    where
+        T: Chain<N> + InRange<N, <T as Length>::Len>,
+    {
+        Self {
+            index: N,
+            input: type_name::<T::In<'_>>(),
+            output: type_name::<T::Out<'_>>(),
+        }
+    }
+}
+
+/// Hooks that run around every link of an `ObservedCascade`. Observers only see which link is
+/// running, not the values flowing through it, since those are a different type at every link.
+pub trait Observer {
+    fn before(&mut self, _stage: &StageInfo) {}
+
+    fn after(&mut self, _stage: &StageInfo) {}
+}
+
+impl<O: Observer> Observer for &mut O {
+    fn before(&mut self, stage: &StageInfo) {
+        (**self).before(stage)
+    }
+
+    fn after(&mut self, stage: &StageInfo) {
+        (**self).after(stage)
+    }
+}
+
+/// Runs both observers, in order, around each link.
+impl<A: Observer, B: Observer> Observer for (A, B) {
+    fn before(&mut self, stage: &StageInfo) {
+        self.0.before(stage);
+        self.1.before(stage);
+    }
+
+    fn after(&mut self, stage: &StageInfo) {
+        self.1.after(stage);
+        self.0.after(stage);
+    }
+}
+
+fn observe<'a, const N: usize, T, O: Observer>(input: T::In<'a>, observer: &mut O) -> T::Out<'a>
+where
+    T: Chain<N> + InRange<N, <T as Length>::Len>,
+{
+    let stage = StageInfo::of::<N, T>();
+    observer.before(&stage);
+    let out = T::chain(input);
+    observer.after(&stage);
+    out
+}
+
+/// Same as `Link<N>`, but with an `Observer` wrapped around every link.
+pub trait ObservedLink<const N: usize>: Link<N> {
+    fn observed_link<'a, O: Observer>(input: Self::In<'a>, observer: &mut O) -> Self::Out<'a>;
+}
+
+impl<T: Chain<0>> ObservedLink<1> for T {
+    fn observed_link<'a, O: Observer>(input: Self::In<'a>, observer: &mut O) -> Self::Out<'a> {
+        observe::<0, T, O>(input, observer)
+    }
+}
+
+seq!(N in 2..=32 {
+    impl<T> ObservedLink<N> for T
+    where
+        T: Chain<0>,
+        for<'a> T: ObservedLink<{N - 1}, In<'a> = <T as Chain<0>>::In<'a>>,
+        for<'a> T: Chain<{N - 1}, In<'a> = <T as Link<{N - 1}>>::Out<'a>>,
+    {
+        fn observed_link<'a, O: Observer>(input: Self::In<'a>, observer: &mut O) -> Self::Out<'a> {
+            let out = <T as ObservedLink<{N - 1}>>::observed_link(input, observer);
+            observe::<{N - 1}, T, O>(out, observer)
+        }
+    }
+});
+
+pub trait ObservedCascade: Cascade {
+    fn observed_cascade<'a, O: Observer>(input: Self::In<'a>, observer: &mut O) -> Self::Out<'a>;
+}
+
+impl<const N: usize, T: ObservedLink<N> + Length<Len = L<N>>> ObservedCascade for T {
+    fn observed_cascade<'a, O: Observer>(input: Self::In<'a>, observer: &mut O) -> Self::Out<'a> {
+        <T as ObservedLink<N>>::observed_link(input, observer)
+    }
+}
@@ -0,0 +1,72 @@
+use std::fmt::Display;
+
+use crate::{Chain, Homogeneous, InRange, Length, No, Select, Yes};
+
+/// A stage that may fail to improve its `Item`, like an enrichment step that depends on an
+/// external lookup. Only meant to be used through `Optional`, which skips it on failure.
+pub trait Fallible<const N: usize>: Homogeneous {
+    type Error: Display;
+
+    fn try_step(item: &Self::Item) -> Result<Self::Item, Self::Error>;
+
+    /// Called with the error whenever the stage gets skipped, e.g. to log or count skips. Does
+    /// nothing by default.
+    fn skipped(_: Self::Error) {}
+}
+
+/// Wraps a chain so that link `N` is a `Fallible<N>` stage that, when it fails, passes its input
+/// along unchanged instead of aborting the cascade. Every other link is `T`'s own `Chain<M>`.
+pub struct Optional<T, const N: usize>(T);
+
+impl<T: Length, const N: usize> Length for Optional<T, N> {
+    type Len = T::Len;
+}
+
+impl<const M: usize, const N: usize, T> Chain<M> for Optional<T, N>
+where
+    (): Select<M, N>,
+    T: Length + OrSkip<M, <() as Select<M, N>>::Is>,
+    Self: InRange<M, Self::Len>,
+{
+    type In<'a> = <T as OrSkip<M, <() as Select<M, N>>::Is>>::In<'a>;
+    type Out<'a> = <T as OrSkip<M, <() as Select<M, N>>::Is>>::Out<'a>;
+
+    fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+        <T as OrSkip<M, <() as Select<M, N>>::Is>>::or_skip(input)
+    }
+}
+
+/// Implementation detail of `Optional`, running the fallible stage only for the selected link.
+pub trait OrSkip<const M: usize, Is> {
+    type In<'a>;
+    type Out<'a>;
+
+    fn or_skip(input: Self::In<'_>) -> Self::Out<'_>;
+}
+
+impl<const M: usize, T: Chain<M>> OrSkip<M, No> for T
+where
+    T: InRange<M, <T as Length>::Len>,
+{
+    type In<'a> = <T as Chain<M>>::In<'a>;
+    type Out<'a> = <T as Chain<M>>::Out<'a>;
+
+    fn or_skip(input: Self::In<'_>) -> Self::Out<'_> {
+        <T as Chain<M>>::chain(input)
+    }
+}
+
+impl<const M: usize, T: Fallible<M>> OrSkip<M, Yes> for T {
+    type In<'a> = T::Item;
+    type Out<'a> = T::Item;
+
+    fn or_skip(input: Self::In<'_>) -> Self::Out<'_> {
+        match T::try_step(&input) {
+            Ok(item) => item,
+            Err(error) => {
+                T::skipped(error);
+                input
+            }
+        }
+    }
+}
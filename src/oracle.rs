@@ -0,0 +1,29 @@
+use std::fmt::Debug;
+
+use proptest::strategy::Strategy;
+use proptest::test_runner::{TestCaseError, TestRunner};
+
+use crate::Cascade;
+
+/// Runs `T`'s cascade on random inputs from `inputs` and panics with the (shrunk) failing input
+/// if it ever disagrees with `oracle`, usually a slower but obviously correct implementation.
+pub fn assert_matches_oracle<T, S, F>(inputs: S, oracle: F)
+where
+    T: Cascade,
+    S: Strategy<Value = T::In<'static>>,
+    T::In<'static>: Clone + Debug,
+    T::Out<'static>: PartialEq + Debug,
+    F: Fn(T::In<'static>) -> T::Out<'static>,
+{
+    let result = TestRunner::default().run(&inputs, |input| {
+        let actual = T::cascade(input.clone());
+        let expected = oracle(input);
+        match actual == expected {
+            true => Ok(()),
+            false => Err(TestCaseError::fail(format!("cascade gave {actual:?}, oracle gave {expected:?}"))),
+        }
+    });
+    if let Err(error) = result {
+        panic!("cascade doesn't match oracle: {error}");
+    }
+}
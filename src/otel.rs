@@ -0,0 +1,46 @@
+use std::time::Instant;
+
+use opentelemetry::metrics::{Counter, Histogram, Meter};
+use opentelemetry::KeyValue;
+
+use crate::{Observer, StageInfo};
+
+/// Observer that reports every link to OpenTelemetry, as a `chain_link.stage.invocations`
+/// counter and a `chain_link.stage.duration` histogram in seconds. Both are tagged with the
+/// link's `stage` index, so each link gets its own series.
+pub struct OtelObserver {
+    invocations: Counter<u64>,
+    duration: Histogram<f64>,
+    started: Option<Instant>,
+}
+
+impl OtelObserver {
+    pub fn new(meter: &Meter) -> Self {
+        Self {
+            invocations: meter
+                .u64_counter("chain_link.stage.invocations")
+                .with_description("Number of times each link of a cascade ran")
+                .build(),
+            duration: meter
+                .f64_histogram("chain_link.stage.duration")
+                .with_description("Time spent in each link of a cascade")
+                .with_unit("s")
+                .build(),
+            started: None,
+        }
+    }
+}
+
+impl Observer for OtelObserver {
+    fn before(&mut self, _: &StageInfo) {
+        self.started = Some(Instant::now());
+    }
+
+    fn after(&mut self, stage: &StageInfo) {
+        let attributes = [KeyValue::new("stage", stage.index as i64)];
+        self.invocations.add(1, &attributes);
+        if let Some(started) = self.started.take() {
+            self.duration.record(started.elapsed().as_secs_f64(), &attributes);
+        }
+    }
+}
@@ -0,0 +1,43 @@
+use rayon::prelude::*;
+use rayon::{ThreadPool, ThreadPoolBuilder};
+
+use crate::ContextCascade;
+
+/// Context for links that do parallelizable work within a single input, like mapping over a
+/// large collection, capping how many threads they spread it over. The pool is built once up
+/// front and shared by every link.
+pub struct Parallelism {
+    pool: ThreadPool,
+}
+
+impl Parallelism {
+    /// Panics if the thread pool can't be built.
+    pub fn new(threads: usize) -> Self {
+        let pool = ThreadPoolBuilder::new().num_threads(threads.max(1)).build().unwrap();
+        Self { pool }
+    }
+
+    pub fn threads(&self) -> usize {
+        self.pool.current_num_threads()
+    }
+
+    /// Maps `f` over `items` on at most `threads()` threads, keeping their order.
+    pub fn map<T: Send, U: Send>(&self, items: Vec<T>, f: impl Fn(T) -> U + Sync + Send) -> Vec<U> {
+        self.pool.install(|| items.into_par_iter().map(f).collect())
+    }
+
+    /// Runs any other rayon work on this pool, so it's capped the same way.
+    pub fn install<R: Send>(&self, op: impl FnOnce() -> R + Send) -> R {
+        self.pool.install(op)
+    }
+}
+
+pub trait ParallelCascade: ContextCascade<Context = Parallelism> {
+    /// Cascades `input` with every link spreading its work over the threads of `parallelism`,
+    /// which can be built once and reused for every cascade.
+    fn cascade_with_parallelism<'a>(parallelism: &Parallelism, input: Self::In<'a>) -> Self::Out<'a> {
+        Self::cascade_with(parallelism, input)
+    }
+}
+
+impl<T: ContextCascade<Context = Parallelism>> ParallelCascade for T {}
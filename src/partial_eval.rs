@@ -0,0 +1,51 @@
+use std::sync::OnceLock;
+
+use crate::{Chain, Length, Link, ResumeLink, L};
+
+/// A chain whose input is known at compile time.
+pub trait ConstInput: Chain<0> {
+    const INPUT: <Self as Chain<0>>::In<'static>;
+}
+
+/// Provides where the output of links `0..K` on `INPUT` is kept for `PartialEval<K>`, shared by
+/// every run.
+pub trait FoldedPrefix<const K: usize>: ConstInput
+where
+    Self: Link<K>,
+{
+    fn folded() -> &'static OnceLock<<Self as Link<K>>::Out<'static>>;
+}
+
+/// Cascades a `ConstInput` chain with links `0..K` folded away, so only links `K..N` are left
+/// to run each time.
+///
+/// Calling trait methods in a const context isn't stable yet, so the prefix can't actually be
+/// evaluated by the compiler. Instead it runs the first time it's needed, and its output is kept
+/// in `FoldedPrefix::folded` for the rest of the process.
+pub trait PartialEval<const K: usize> {
+    type Out;
+
+    fn cascade_partial() -> Self::Out;
+
+    /// How many links still run on every `cascade_partial`.
+    fn runtime_links() -> usize;
+}
+
+impl<const K: usize, const N: usize, T> PartialEval<K> for T
+where
+    T: FoldedPrefix<K> + Length<Len = L<N>>,
+    for<'a> T: Link<K, In<'a> = <T as Chain<0>>::In<'a>>,
+    for<'a> T: ResumeLink<K, N, In<'a> = <T as Link<K>>::Out<'a>>,
+    <T as Link<K>>::Out<'static>: Clone + 'static,
+{
+    type Out = <T as ResumeLink<K, N>>::Out<'static>;
+
+    fn cascade_partial() -> Self::Out {
+        let folded = T::folded().get_or_init(|| <T as Link<K>>::link(T::INPUT));
+        <T as ResumeLink<K, N>>::resume_link(folded.clone())
+    }
+
+    fn runtime_links() -> usize {
+        N - K
+    }
+}
@@ -0,0 +1,45 @@
+use std::thread;
+
+pub use core_affinity::CoreId;
+
+use crate::{ObservedCascade, Observer, StageInfo};
+
+/// Observer that moves the current thread onto `cores[index % cores.len()]` before each link.
+/// Pinning is best effort, if the platform refuses the link just runs wherever it is.
+pub struct Pinner<'c> {
+    cores: &'c [CoreId],
+}
+
+impl<'c> Pinner<'c> {
+    /// Panics if `cores` is empty.
+    pub fn new(cores: &'c [CoreId]) -> Self {
+        assert!(!cores.is_empty(), "Pinner needs at least one core");
+        Self { cores }
+    }
+}
+
+impl Observer for Pinner<'_> {
+    fn before(&mut self, stage: &StageInfo) {
+        core_affinity::set_for_current(self.cores[stage.index % self.cores.len()]);
+    }
+}
+
+pub trait PinnedCascade: ObservedCascade {
+    /// Cascades on a fresh thread, pinning link `i` to `cores[i % cores.len()]`, so the caller's
+    /// own affinity is left alone. Panics if `cores` is empty.
+    fn cascade_pinned<'a>(cores: &[CoreId], input: Self::In<'a>) -> Self::Out<'a>
+    where
+        Self::In<'a>: Send,
+        Self::Out<'a>: Send,
+    {
+        let mut pinner = Pinner::new(cores);
+        thread::scope(|scope| {
+            scope
+                .spawn(move || Self::observed_cascade(input, &mut pinner))
+                .join()
+                .unwrap_or_else(|panic| std::panic::resume_unwind(panic))
+        })
+    }
+}
+
+impl<T: ObservedCascade> PinnedCascade for T {}
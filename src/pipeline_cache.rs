@@ -0,0 +1,36 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Reuses pipelines built from the same config instead of rebuilding them, for when building a
+/// pipeline (e.g. a `DynCascade` assembled from config) is expensive compared to running it.
+pub struct PipelineCache<C, P, F> {
+    build: F,
+    pipelines: HashMap<C, P>,
+}
+
+impl<C: Hash + Eq + Clone, P, F: FnMut(&C) -> P> PipelineCache<C, P, F> {
+    pub fn new(build: F) -> Self {
+        Self { build, pipelines: HashMap::new() }
+    }
+
+    pub fn get_or_build(&mut self, config: &C) -> &P {
+        if !self.pipelines.contains_key(config) {
+            let pipeline = (self.build)(config);
+            self.pipelines.insert(config.clone(), pipeline);
+        }
+        &self.pipelines[config]
+    }
+
+    pub fn len(&self) -> usize {
+        self.pipelines.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pipelines.is_empty()
+    }
+
+    /// Drops every cached pipeline, so the next request for any config rebuilds it.
+    pub fn clear(&mut self) {
+        self.pipelines.clear();
+    }
+}
@@ -0,0 +1,87 @@
+use std::collections::BTreeMap;
+use std::time::{Duration, Instant};
+
+use crate::{ObservedCascade, Observer, StageInfo};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct StageTiming {
+    pub stage: StageInfo,
+    pub elapsed: Duration,
+}
+
+/// Observer that times every link it sees.
+#[derive(Clone, Debug, Default)]
+pub struct Profiler {
+    started: Option<Instant>,
+    pub timings: Vec<StageTiming>,
+}
+
+impl Profiler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn total(&self) -> Duration {
+        self.timings.iter().map(|timing| timing.elapsed).sum()
+    }
+}
+
+impl Observer for Profiler {
+    fn before(&mut self, _: &StageInfo) {
+        self.started = Some(Instant::now());
+    }
+
+    fn after(&mut self, stage: &StageInfo) {
+        if let Some(started) = self.started.take() {
+            self.timings.push(StageTiming { stage: *stage, elapsed: started.elapsed() });
+        }
+    }
+}
+
+/// Latency distribution of one link over many cascades.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct StagePercentiles {
+    pub stage: StageInfo,
+    pub samples: usize,
+    pub p50: Duration,
+    pub p90: Duration,
+    pub p99: Duration,
+}
+
+/// Groups `timings` by link, in link order, using the nearest rank for each percentile so every
+/// reported value is one that was actually measured.
+pub fn stage_percentiles(timings: impl IntoIterator<Item = StageTiming>) -> Vec<StagePercentiles> {
+    let mut by_stage: BTreeMap<usize, (StageInfo, Vec<Duration>)> = BTreeMap::new();
+    for timing in timings {
+        by_stage.entry(timing.stage.index).or_insert_with(|| (timing.stage, Vec::new())).1.push(timing.elapsed);
+    }
+    by_stage
+        .into_values()
+        .map(|(stage, mut elapsed)| {
+            elapsed.sort_unstable();
+            let rank = |percent: usize| elapsed[(elapsed.len() * percent).div_ceil(100).max(1) - 1];
+            StagePercentiles { stage, samples: elapsed.len(), p50: rank(50), p90: rank(90), p99: rank(99) }
+        })
+        .collect()
+}
+
+pub trait ProfiledCascade: ObservedCascade {
+    /// Cascades as usual, also returning how long each link took.
+    fn cascade_profiled(input: Self::In<'_>) -> (Self::Out<'_>, Vec<StageTiming>) {
+        let mut profiler = Profiler::new();
+        let out = Self::observed_cascade(input, &mut profiler);
+        (out, profiler.timings)
+    }
+
+    /// Cascades every input, then reports the p50, p90 and p99 time of each link over all of
+    /// them, to find the links responsible for the slowest cascades rather than the average one.
+    fn cascade_iter_with_percentiles<'a>(
+        inputs: impl IntoIterator<Item = Self::In<'a>>,
+    ) -> (Vec<Self::Out<'a>>, Vec<StagePercentiles>) {
+        let mut profiler = Profiler::new();
+        let outs = inputs.into_iter().map(|input| Self::observed_cascade(input, &mut profiler)).collect();
+        (outs, stage_percentiles(profiler.timings))
+    }
+}
+
+impl<T: ObservedCascade> ProfiledCascade for T {}
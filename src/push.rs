@@ -0,0 +1,133 @@
+use std::error::Error;
+use std::fmt;
+use std::marker::PhantomData;
+
+use crate::Cascade;
+
+/// Splits complete units, like lines or records, off the front of a byte buffer, leaving any
+/// partial unit at the end for when more bytes arrive. Between calls bytes are only ever added to
+/// the end of the buffer, so a framer can remember how far it already looked instead of scanning
+/// the whole partial unit again every time.
+pub trait Unframe {
+    type Unit;
+
+    fn next_unit(&mut self, buffer: &mut Vec<u8>) -> Option<Self::Unit>;
+
+    /// Whatever's left once the input has ended, by default nothing since it's incomplete.
+    fn last_unit(&mut self, _buffer: &mut Vec<u8>) -> Option<Self::Unit> {
+        None
+    }
+}
+
+/// Newline terminated lines of UTF-8, with the `\n` or `\r\n` removed. Invalid UTF-8 is replaced
+/// rather than rejected. An unterminated last line still counts once the input ends.
+#[derive(Debug, Default)]
+pub struct Lines {
+    /// How much of the partial line is known not to have a `\n`.
+    scanned: usize,
+}
+
+impl Unframe for Lines {
+    type Unit = String;
+
+    fn next_unit(&mut self, buffer: &mut Vec<u8>) -> Option<String> {
+        let Some(found) = buffer[self.scanned..].iter().position(|&b| b == b'\n') else {
+            self.scanned = buffer.len();
+            return None;
+        };
+        let end = self.scanned + found;
+        self.scanned = 0;
+        let mut line: Vec<u8> = buffer.drain(..=end).collect();
+        line.pop();
+        if line.last() == Some(&b'\r') {
+            line.pop();
+        }
+        Some(String::from_utf8_lossy(&line).into_owned())
+    }
+
+    fn last_unit(&mut self, buffer: &mut Vec<u8>) -> Option<String> {
+        self.scanned = 0;
+        (!buffer.is_empty()).then(|| String::from_utf8_lossy(&std::mem::take(buffer)).into_owned())
+    }
+}
+
+/// Longest partial unit a `PushParser` buffers by default.
+pub const MAX_UNIT_LEN: usize = 1 << 20;
+
+/// Returned by `PushParser::feed` once a partial unit grows past the parser's max, e.g. because
+/// the peer never sends a terminator.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct UnitTooLong {
+    pub max: usize,
+}
+
+impl fmt::Display for UnitTooLong {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "unit is longer than the {} byte max", self.max)
+    }
+}
+
+impl Error for UnitTooLong {}
+
+/// Feeds a cascade from bytes as they arrive, e.g. off a socket. Bytes are buffered until `F`
+/// finds a complete unit in them, and only then is it cascaded.
+pub struct PushParser<T, F> {
+    buffer: Vec<u8>,
+    framer: F,
+    max_unit: usize,
+    _pipeline: PhantomData<T>,
+}
+
+impl<T, F> PushParser<T, F>
+where
+    F: Unframe + Default,
+    for<'a> T: Cascade<In<'a> = F::Unit>,
+{
+    /// Buffers partial units of up to `MAX_UNIT_LEN` bytes.
+    pub fn new() -> Self {
+        Self { buffer: Vec::new(), framer: F::default(), max_unit: MAX_UNIT_LEN, _pipeline: PhantomData }
+    }
+
+    pub fn with_max_unit(self, max_unit: usize) -> Self {
+        Self { max_unit, ..self }
+    }
+
+    /// Buffers `bytes`, cascading every unit they complete, in order.
+    ///
+    /// Fails if that leaves a partial unit longer than the max. The partial unit is dropped, along
+    /// with the outputs of this feed, since a peer that sends one usually can't be trusted with
+    /// anything else either.
+    pub fn feed(&mut self, bytes: &[u8]) -> Result<Vec<<T as Cascade>::Out<'static>>, UnitTooLong> {
+        self.buffer.extend_from_slice(bytes);
+        let mut outputs = Vec::new();
+        while let Some(unit) = self.framer.next_unit(&mut self.buffer) {
+            outputs.push(T::cascade(unit));
+        }
+        if self.buffer.len() > self.max_unit {
+            self.buffer = Vec::new();
+            self.framer = F::default();
+            return Err(UnitTooLong { max: self.max_unit });
+        }
+        Ok(outputs)
+    }
+
+    /// Bytes received that aren't part of a complete unit yet.
+    pub fn pending(&self) -> &[u8] {
+        &self.buffer
+    }
+
+    /// Ends the input, cascading the last unit if `F` accepts what's left as one.
+    pub fn finish(mut self) -> Option<<T as Cascade>::Out<'static>> {
+        self.framer.last_unit(&mut self.buffer).map(T::cascade)
+    }
+}
+
+impl<T, F> Default for PushParser<T, F>
+where
+    F: Unframe + Default,
+    for<'a> T: Cascade<In<'a> = F::Unit>,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,197 @@
+use std::error::Error;
+use std::fmt;
+
+use crate::{DynCascade, Param, StageError, StageRegistry};
+
+/// Why a query couldn't be turned into a pipeline.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum QueryError {
+    /// The query isn't in the supported subset of GraphQL.
+    Syntax(String),
+    Stage(StageError),
+}
+
+impl fmt::Display for QueryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            QueryError::Syntax(reason) => write!(f, "invalid query: {reason}"),
+            QueryError::Stage(error) => error.fmt(f),
+        }
+    }
+}
+
+impl Error for QueryError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            QueryError::Stage(error) => Some(error),
+            QueryError::Syntax(_) => None,
+        }
+    }
+}
+
+impl<T: 'static> StageRegistry<T> {
+    /// Compiles a query in a small subset of GraphQL into a pipeline of the selected stages, in
+    /// the order they're selected:
+    ///
+    /// ```graphql
+    /// query Shout { trim, upper, repeat(times: 2, separator: ", ") }
+    /// ```
+    ///
+    /// Arguments become the stage's params as a `Param::Map`, and stages without any get
+    /// `Param::Null`. Only a single flat selection is supported: no variables, fragments,
+    /// directives or nested selections.
+    pub fn query(&self, query: &str) -> Result<DynCascade<T>, QueryError> {
+        let selection = Parser { rest: query }.query()?;
+        let mut pipeline = DynCascade::new();
+        for (name, params) in selection {
+            pipeline.push(self.make(name, &params).map_err(QueryError::Stage)?);
+        }
+        Ok(pipeline)
+    }
+}
+
+fn invalid(reason: impl Into<String>) -> QueryError {
+    QueryError::Syntax(reason.into())
+}
+
+struct Parser<'a> {
+    rest: &'a str,
+}
+
+impl<'a> Parser<'a> {
+    fn query(mut self) -> Result<Vec<(&'a str, Param)>, QueryError> {
+        if self.peek() != Some('{') {
+            if self.name() != Some("query") {
+                return Err(invalid(format!("expected `query` or `{{` at `{}`", self.snippet())));
+            }
+            // the operation's name, if it has one
+            self.name();
+        }
+        self.expect('{')?;
+
+        let mut selection = Vec::new();
+        while !self.eat('}') {
+            let name = self.name().ok_or_else(|| match self.rest.is_empty() {
+                true => invalid("query ends before its closing `}`"),
+                false => invalid(format!("expected a stage name at `{}`", self.snippet())),
+            })?;
+            let params = match self.eat('(') {
+                true => self.arguments()?,
+                false => Param::Null,
+            };
+            if self.peek() == Some('{') {
+                return Err(invalid(format!("stage `{name}` can't have a selection of its own")));
+            }
+            selection.push((name, params));
+        }
+        match self.peek().is_none() {
+            true => Ok(selection),
+            false => Err(invalid(format!("unexpected `{}` after the query", self.snippet()))),
+        }
+    }
+
+    fn arguments(&mut self) -> Result<Param, QueryError> {
+        let mut arguments = Vec::new();
+        while !self.eat(')') {
+            let name = self.name().ok_or_else(|| invalid(format!("expected an argument at `{}`", self.snippet())))?;
+            self.expect(':')?;
+            let value = self.value()?;
+            arguments.push((name.to_owned(), value));
+        }
+        Ok(Param::Map(arguments))
+    }
+
+    fn value(&mut self) -> Result<Param, QueryError> {
+        self.skip_ignored();
+        if self.rest.starts_with('"') {
+            return self.string().map(Param::String);
+        }
+        if let Some(name) = self.name() {
+            return Ok(match name {
+                "true" => Param::Bool(true),
+                "false" => Param::Bool(false),
+                "null" => Param::Null,
+                // enum values, which stages get as strings
+                _ => Param::String(name.to_owned()),
+            });
+        }
+        let len = self
+            .rest
+            .find(|c: char| !(c.is_ascii_digit() || matches!(c, '-' | '+' | '.' | 'e' | 'E')))
+            .unwrap_or(self.rest.len());
+        let (number, rest) = self.rest.split_at(len);
+        let number = match (number.parse::<i64>(), number.parse::<f64>()) {
+            (Ok(integer), _) => Param::Int(integer),
+            (_, Ok(float)) if !number.is_empty() => Param::Float(float),
+            _ => return Err(invalid(format!("expected a value at `{}`", self.snippet()))),
+        };
+        self.rest = rest;
+        Ok(number)
+    }
+
+    fn string(&mut self) -> Result<String, QueryError> {
+        let mut chars = self.rest[1..].char_indices();
+        let mut string = String::new();
+        while let Some((index, c)) = chars.next() {
+            match c {
+                '"' => {
+                    self.rest = &self.rest[index + 2..];
+                    return Ok(string);
+                }
+                '\\' => match chars.next().map(|(_, escaped)| escaped) {
+                    Some('n') => string.push('\n'),
+                    Some('t') => string.push('\t'),
+                    Some(escaped @ ('"' | '\\' | '/')) => string.push(escaped),
+                    _ => return Err(invalid("unsupported escape in string")),
+                },
+                _ => string.push(c),
+            }
+        }
+        Err(invalid("unterminated string"))
+    }
+
+    fn name(&mut self) -> Option<&'a str> {
+        self.skip_ignored();
+        self.rest.chars().next().filter(|c| c.is_ascii_alphabetic() || *c == '_')?;
+        let len = self.rest.find(|c: char| !(c.is_ascii_alphanumeric() || c == '_')).unwrap_or(self.rest.len());
+        let (name, rest) = self.rest.split_at(len);
+        self.rest = rest;
+        Some(name)
+    }
+
+    fn peek(&mut self) -> Option<char> {
+        self.skip_ignored();
+        self.rest.chars().next()
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        let eaten = self.peek() == Some(c);
+        if eaten {
+            self.rest = &self.rest[c.len_utf8()..];
+        }
+        eaten
+    }
+
+    fn expect(&mut self, c: char) -> Result<(), QueryError> {
+        match self.eat(c) {
+            true => Ok(()),
+            false => Err(invalid(format!("expected `{c}` at `{}`", self.snippet()))),
+        }
+    }
+
+    /// Whitespace, commas and comments, which GraphQL all ignores.
+    fn skip_ignored(&mut self) {
+        loop {
+            self.rest = self.rest.trim_start_matches(|c: char| c.is_whitespace() || c == ',');
+            match self.rest.strip_prefix('#') {
+                Some(comment) => self.rest = comment.find('\n').map_or("", |end| &comment[end..]),
+                None => return,
+            }
+        }
+    }
+
+    fn snippet(&self) -> &str {
+        let end = self.rest.char_indices().nth(16).map_or(self.rest.len(), |(index, _)| index);
+        &self.rest[..end]
+    }
+}
@@ -0,0 +1,73 @@
+use std::future::Future;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use crate::{AsyncChain, Clock, InRange, Length};
+
+/// Spaces out acquisitions so they happen at most `per_second` times a second. Callers that
+/// arrive early wait for their slot on the limiter's clock.
+pub struct RateLimiter<C> {
+    interval: Duration,
+    next: Mutex<Duration>,
+    clock: C,
+}
+
+impl<C: Clock> RateLimiter<C> {
+    /// Panics if `per_second` is zero.
+    pub const fn new(per_second: u32, clock: C) -> Self {
+        assert!(per_second > 0, "a rate limiter has to allow at least one acquisition a second");
+        Self {
+            interval: Duration::from_nanos(1_000_000_000 / per_second as u64),
+            next: Mutex::new(Duration::ZERO),
+            clock,
+        }
+    }
+
+    pub async fn acquire(&self) {
+        let now = self.clock.now();
+        let slot = {
+            let mut next = self.next.lock().unwrap();
+            let slot = now.max(*next);
+            *next = slot + self.interval;
+            slot
+        };
+        if slot > now {
+            self.clock.sleep(slot - now).await;
+        }
+    }
+}
+
+/// Provides the limiter shared by every run of a `RateLimited` pipeline.
+pub trait RateLimit {
+    type Clock: Clock + Sync + 'static;
+
+    fn limiter() -> &'static RateLimiter<Self::Clock>;
+}
+
+/// Wraps an async chain so that link `N` waits on `T::limiter()` before running, throttling just
+/// that stage without touching the others.
+pub struct RateLimited<T, const N: usize>(T);
+
+impl<T: Length, const N: usize> Length for RateLimited<T, N> {
+    type Len = T::Len;
+}
+
+impl<const M: usize, const N: usize, T> AsyncChain<M> for RateLimited<T, N>
+where
+    T: AsyncChain<M> + RateLimit,
+    Self: InRange<M, Self::Len>,
+{
+    type In<'a> = <T as AsyncChain<M>>::In<'a>;
+    type Out<'a> = <T as AsyncChain<M>>::Out<'a>;
+
+    fn chain(input: Self::In<'_>) -> impl Future<Output = Self::Out<'_>> + Send {
+        let acquire = (M == N).then(|| T::limiter().acquire());
+        let stage = <T as AsyncChain<M>>::chain(input);
+        async move {
+            if let Some(acquire) = acquire {
+                acquire.await;
+            }
+            stage.await
+        }
+    }
+}
@@ -0,0 +1,124 @@
+use seq_macro::seq;
+
+use crate::{Cascade, Chain, Codec, DecodeError, InRange, Length, Link, L};
+
+/// The encoded input of every link a `RecordedCascade` started, in order. If a link panics its
+/// input is still in the log, it just never gets counted as `completed`, so a log taken from a
+/// crashed cascade points right at the link that crashed.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ExecutionLog {
+    pub inputs: Vec<Vec<u8>>,
+    pub completed: usize,
+}
+
+impl ExecutionLog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Index of the link that was running when the cascade stopped, if it didn't finish.
+    pub fn failed_stage(&self) -> Option<usize> {
+        (self.inputs.len() > self.completed).then_some(self.completed)
+    }
+}
+
+impl Codec for ExecutionLog {
+    fn encode(&self, out: &mut Vec<u8>) {
+        self.inputs.encode(out);
+        self.completed.encode(out);
+    }
+
+    fn decode(bytes: &mut &[u8]) -> Result<Self, DecodeError> {
+        Ok(Self { inputs: Codec::decode(bytes)?, completed: Codec::decode(bytes)? })
+    }
+}
+
+/// Just enough of an `ExecutionLog` to rerun the link that failed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MinimalLog {
+    pub stage: usize,
+    pub input: Vec<u8>,
+}
+
+impl MinimalLog {
+    /// Decodes the recorded input and runs link `N` of `T` on it alone. Fails if the log is for a
+    /// different link, or its input doesn't decode as `T`'s input for link `N`.
+    pub fn replay<const N: usize, T>(&self) -> Result<T::Out<'static>, DecodeError>
+    where
+        T: Chain<N> + InRange<N, <T as Length>::Len>,
+        T::In<'static>: Codec,
+    {
+        if self.stage != N {
+            return Err(DecodeError);
+        }
+        Ok(T::chain(T::In::from_bytes(&self.input)?))
+    }
+}
+
+impl Codec for MinimalLog {
+    fn encode(&self, out: &mut Vec<u8>) {
+        self.stage.encode(out);
+        self.input.encode(out);
+    }
+
+    fn decode(bytes: &mut &[u8]) -> Result<Self, DecodeError> {
+        Ok(Self { stage: Codec::decode(bytes)?, input: Codec::decode(bytes)? })
+    }
+}
+
+/// Trims a log down to the input of the link that failed, or `None` if the cascade finished.
+pub fn minimize(log: ExecutionLog) -> Option<MinimalLog> {
+    let stage = log.failed_stage()?;
+    let input = log.inputs.into_iter().nth(stage)?;
+    Some(MinimalLog { stage, input })
+}
+
+fn record<'a, const N: usize, T>(input: T::In<'a>, log: &mut ExecutionLog) -> T::Out<'a>
+where
+    T: Chain<N> + InRange<N, <T as Length>::Len>,
+    T::In<'a>: Codec,
+{
+    log.inputs.push(input.to_bytes());
+    let out = T::chain(input);
+    log.completed += 1;
+    out
+}
+
+/// Same as `Link<N>`, but writes the input of every link to an `ExecutionLog` before running it.
+pub trait RecordedLink<const N: usize>: Link<N> {
+    fn recorded_link<'a>(input: Self::In<'a>, log: &mut ExecutionLog) -> Self::Out<'a>;
+}
+
+impl<T: Chain<0>> RecordedLink<1> for T
+where
+    for<'a> <T as Chain<0>>::In<'a>: Codec,
+{
+    fn recorded_link<'a>(input: Self::In<'a>, log: &mut ExecutionLog) -> Self::Out<'a> {
+        record::<0, T>(input, log)
+    }
+}
+
+seq!(N in 2..=32 {
+    impl<T> RecordedLink<N> for T
+    where
+        T: Chain<0>,
+        for<'a> T: RecordedLink<{N - 1}, In<'a> = <T as Chain<0>>::In<'a>>,
+        for<'a> T: Chain<{N - 1}, In<'a> = <T as Link<{N - 1}>>::Out<'a>>,
+        for<'a> <T as Chain<{N - 1}>>::In<'a>: Codec,
+    {
+        fn recorded_link<'a>(input: Self::In<'a>, log: &mut ExecutionLog) -> Self::Out<'a> {
+            let out = <T as RecordedLink<{N - 1}>>::recorded_link(input, log);
+            record::<{N - 1}, T>(out, log)
+        }
+    }
+});
+
+pub trait RecordedCascade: Cascade {
+    fn cascade_recorded<'a>(input: Self::In<'a>, log: &mut ExecutionLog) -> Self::Out<'a>;
+}
+
+impl<const N: usize, T: RecordedLink<N> + Length<Len = L<N>>> RecordedCascade for T {
+    fn cascade_recorded<'a>(input: Self::In<'a>, log: &mut ExecutionLog) -> Self::Out<'a> {
+        <T as RecordedLink<N>>::recorded_link(input, log)
+    }
+}
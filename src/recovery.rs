@@ -0,0 +1,212 @@
+use seq_macro::seq;
+
+use crate::{InRange, Length, L};
+
+/// A `Chain<N>` that can fail. Every link of a chain has to agree on the same `Error`, which is
+/// what the cascade fails with if a link can't recover.
+pub trait TryChain<const N: usize>
+where
+    Self: InRange<N, <Self as Length>::Len>,
+{
+    type Error;
+    type In<'a>;
+    type Out<'a>;
+
+    fn try_chain(input: Self::In<'_>) -> Result<Self::Out<'_>, Self::Error>;
+}
+
+/// Declares what link `N` does when it fails, by picking one of `Propagate`, `Retry<TIMES>`,
+/// `IdempotentRetry<TIMES>`, `UseDefault`, `Skip` or `Alternate` as its `Policy`.
+pub trait RecoveryPolicy<const N: usize>: TryChain<N>
+where
+    Self: InRange<N, <Self as Length>::Len>,
+{
+    type Policy: Recover<Self, N>;
+}
+
+/// How a recovery policy runs link `N` of `T`.
+pub trait Recover<T: ?Sized, const N: usize>
+where
+    T: TryChain<N> + InRange<N, <T as Length>::Len>,
+{
+    fn recover(input: T::In<'_>) -> Result<T::Out<'_>, T::Error>;
+}
+
+/// Fails the cascade with the link's error.
+pub struct Propagate;
+
+impl<const N: usize, T> Recover<T, N> for Propagate
+where
+    T: TryChain<N> + InRange<N, <T as Length>::Len>,
+{
+    fn recover(input: T::In<'_>) -> Result<T::Out<'_>, T::Error> {
+        T::try_chain(input)
+    }
+}
+
+/// Runs the link again on a copy of the same input up to `TIMES` more times, propagating the
+/// last error if none of them succeed.
+pub struct Retry<const TIMES: usize>;
+
+impl<const N: usize, const TIMES: usize, T> Recover<T, N> for Retry<TIMES>
+where
+    T: TryChain<N> + InRange<N, <T as Length>::Len>,
+    for<'a> T::In<'a>: Clone,
+{
+    fn recover(input: T::In<'_>) -> Result<T::Out<'_>, T::Error> {
+        for _ in 0..TIMES {
+            if let Ok(out) = T::try_chain(input.clone()) {
+                return Ok(out);
+            }
+        }
+        T::try_chain(input)
+    }
+}
+
+/// Carries on with the default value of the link's output.
+pub struct UseDefault;
+
+impl<const N: usize, T> Recover<T, N> for UseDefault
+where
+    T: TryChain<N> + InRange<N, <T as Length>::Len>,
+    for<'a> T::Out<'a>: Default,
+{
+    fn recover(input: T::In<'_>) -> Result<T::Out<'_>, T::Error> {
+        Ok(T::try_chain(input).unwrap_or_default())
+    }
+}
+
+/// Carries on with the link's input, as if it was never there. Only for links whose output is
+/// the same type as their input.
+pub struct Skip;
+
+impl<const N: usize, T> Recover<T, N> for Skip
+where
+    T: TryChain<N> + InRange<N, <T as Length>::Len>,
+    for<'a> T: TryChain<N, Out<'a> = <T as TryChain<N>>::In<'a>>,
+    for<'a> T::In<'a>: Clone,
+{
+    fn recover(input: T::In<'_>) -> Result<T::Out<'_>, T::Error> {
+        Ok(T::try_chain(input.clone()).unwrap_or(input))
+    }
+}
+
+/// A second way of doing link `N`, used by the `Alternate` policy.
+pub trait AlternateChain<const N: usize>: TryChain<N>
+where
+    Self: InRange<N, <Self as Length>::Len>,
+{
+    fn alternate(input: Self::In<'_>) -> Result<Self::Out<'_>, Self::Error>;
+}
+
+/// Runs `AlternateChain::alternate` on the same input, propagating its error if it fails too.
+pub struct Alternate;
+
+impl<const N: usize, T> Recover<T, N> for Alternate
+where
+    T: AlternateChain<N> + InRange<N, <T as Length>::Len>,
+    for<'a> T::In<'a>: Clone,
+{
+    fn recover(input: T::In<'_>) -> Result<T::Out<'_>, T::Error> {
+        T::try_chain(input.clone()).or_else(|_| T::alternate(input))
+    }
+}
+
+/// A side-effecting link, e.g. one calling an external API, that can pass an idempotency key
+/// along with its side effect so whatever is on the other end can tell a retry from a new call.
+/// Used by the `IdempotentRetry` policy.
+pub trait IdempotentChain<const N: usize>: TryChain<N>
+where
+    Self: InRange<N, <Self as Length>::Len>,
+{
+    type Key;
+
+    /// The key for one run of the link, made once before the first attempt and shared by every
+    /// retry of it.
+    fn idempotency_key(input: &Self::In<'_>) -> Self::Key;
+
+    fn try_chain_keyed<'a>(input: Self::In<'a>, key: &Self::Key) -> Result<Self::Out<'a>, Self::Error>;
+}
+
+/// Like `Retry<TIMES>`, but every attempt goes through `IdempotentChain::try_chain_keyed` with the
+/// same key, so a side effect that went through on an attempt that still failed, e.g. by timing
+/// out, isn't repeated by the next one.
+pub struct IdempotentRetry<const TIMES: usize>;
+
+impl<const N: usize, const TIMES: usize, T> Recover<T, N> for IdempotentRetry<TIMES>
+where
+    T: IdempotentChain<N> + InRange<N, <T as Length>::Len>,
+    for<'a> T::In<'a>: Clone,
+{
+    fn recover(input: T::In<'_>) -> Result<T::Out<'_>, T::Error> {
+        let key = T::idempotency_key(&input);
+        for _ in 0..TIMES {
+            if let Ok(out) = T::try_chain_keyed(input.clone(), &key) {
+                return Ok(out);
+            }
+        }
+        T::try_chain_keyed(input, &key)
+    }
+}
+
+fn recover<const N: usize, T>(input: T::In<'_>) -> Result<T::Out<'_>, T::Error>
+where
+    T: RecoveryPolicy<N> + InRange<N, <T as Length>::Len>,
+{
+    <T::Policy as Recover<T, N>>::recover(input)
+}
+
+pub trait RecoveryLink<const N: usize> {
+    type Error;
+    type In<'a>;
+    type Out<'a>;
+
+    fn recovery_link(input: Self::In<'_>) -> Result<Self::Out<'_>, Self::Error>;
+}
+
+impl<T: RecoveryPolicy<0>> RecoveryLink<1> for T {
+    type Error = <T as TryChain<0>>::Error;
+    type In<'a> = <T as TryChain<0>>::In<'a>;
+    type Out<'a> = <T as TryChain<0>>::Out<'a>;
+
+    fn recovery_link(input: Self::In<'_>) -> Result<Self::Out<'_>, Self::Error> {
+        recover::<0, T>(input)
+    }
+}
+
+seq!(N in 2..=32 {
+    impl<T> RecoveryLink<N> for T
+    where
+        T: RecoveryPolicy<0>,
+        for<'a> T: RecoveryLink<{N - 1}, Error = <T as TryChain<0>>::Error, In<'a> = <T as TryChain<0>>::In<'a>>,
+        for<'a> T: RecoveryPolicy<{N - 1}, Error = <T as TryChain<0>>::Error, In<'a> = <T as RecoveryLink<{N - 1}>>::Out<'a>>,
+    {
+        type Error = <T as TryChain<0>>::Error;
+        type In<'a> = <T as TryChain<0>>::In<'a>;
+        type Out<'a> = <T as TryChain<{N - 1}>>::Out<'a>;
+
+        fn recovery_link(input: Self::In<'_>) -> Result<Self::Out<'_>, Self::Error> {
+            let out = <T as RecoveryLink<{N - 1}>>::recovery_link(input)?;
+            recover::<{N - 1}, T>(out)
+        }
+    }
+});
+
+pub trait RecoveringCascade {
+    type Error;
+    type In<'a>;
+    type Out<'a>;
+
+    /// Cascades `input`, with every link that fails handled by its `RecoveryPolicy`.
+    fn cascade_recovering(input: Self::In<'_>) -> Result<Self::Out<'_>, Self::Error>;
+}
+
+impl<const N: usize, T: RecoveryLink<N> + Length<Len = L<N>>> RecoveringCascade for T {
+    type Error = <T as RecoveryLink<N>>::Error;
+    type In<'a> = <T as RecoveryLink<N>>::In<'a>;
+    type Out<'a> = <T as RecoveryLink<N>>::Out<'a>;
+
+    fn cascade_recovering(input: Self::In<'_>) -> Result<Self::Out<'_>, Self::Error> {
+        <T as RecoveryLink<N>>::recovery_link(input)
+    }
+}
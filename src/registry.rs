@@ -0,0 +1,137 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+
+pub(crate) type Stage<T> = Box<dyn Fn(T) -> T>;
+type Factory<T> = Box<dyn Fn(&Param) -> Result<Stage<T>, String>>;
+
+/// The parameters a stage is made from, the same whether they came from a YAML workflow or the
+/// arguments of a query.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub enum Param {
+    #[default]
+    Null,
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    String(String),
+    List(Vec<Param>),
+    /// Named parameters, in the order they were written.
+    Map(Vec<(String, Param)>),
+}
+
+impl Param {
+    /// The parameter named `key`, if this is a map that has one.
+    pub fn get(&self, key: &str) -> Option<&Param> {
+        match self {
+            Param::Map(entries) => entries.iter().find(|(name, _)| name == key).map(|(_, param)| param),
+            _ => None,
+        }
+    }
+
+    pub fn is_null(&self) -> bool {
+        matches!(self, Param::Null)
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            Param::Bool(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn as_i64(&self) -> Option<i64> {
+        match self {
+            Param::Int(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn as_u64(&self) -> Option<u64> {
+        self.as_i64().and_then(|value| u64::try_from(value).ok())
+    }
+
+    /// Integers count as floats too.
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            Param::Int(value) => Some(*value as f64),
+            Param::Float(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Param::String(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    pub fn as_list(&self) -> Option<&[Param]> {
+        match self {
+            Param::List(values) => Some(values),
+            _ => None,
+        }
+    }
+}
+
+/// Why a stage couldn't be made from a `StageRegistry`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum StageError {
+    Unknown(String),
+    /// The stage's factory rejected the parameters it was given.
+    Params { stage: String, reason: String },
+}
+
+impl fmt::Display for StageError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StageError::Unknown(stage) => write!(f, "no stage registered as `{stage}`"),
+            StageError::Params { stage, reason } => write!(f, "bad parameters for stage `{stage}`: {reason}"),
+        }
+    }
+}
+
+impl Error for StageError {}
+
+/// The stages a pipeline can be assembled from by name, each registered along with a factory
+/// that makes the stage from its `Param`s, so pipelines can be put together from config or a
+/// query without touching code. Like every `DynCascade` the stages all take and return the same
+/// type `T`.
+pub struct StageRegistry<T> {
+    factories: HashMap<String, Factory<T>>,
+}
+
+impl<T: 'static> StageRegistry<T> {
+    pub fn new() -> Self {
+        Self { factories: HashMap::new() }
+    }
+
+    /// Registers a stage that has no parameters.
+    pub fn stage(self, name: &str, stage: impl Fn(T) -> T + Clone + 'static) -> Self {
+        self.factory(name, move |_| Ok(stage.clone()))
+    }
+
+    /// Registers a stage made from its parameters, replacing whatever was registered as `name`
+    /// before. The factory returns why the parameters don't work when they don't.
+    pub fn factory<S: Fn(T) -> T + 'static>(
+        mut self,
+        name: &str,
+        factory: impl Fn(&Param) -> Result<S, String> + 'static,
+    ) -> Self {
+        let factory = move |params: &Param| factory(params).map(|stage| Box::new(stage) as Stage<T>);
+        self.factories.insert(name.to_owned(), Box::new(factory));
+        self
+    }
+
+    pub(crate) fn make(&self, name: &str, params: &Param) -> Result<Stage<T>, StageError> {
+        let factory = self.factories.get(name).ok_or_else(|| StageError::Unknown(name.to_owned()))?;
+        factory(params).map_err(|reason| StageError::Params { stage: name.to_owned(), reason })
+    }
+}
+
+impl<T: 'static> Default for StageRegistry<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,19 @@
+use crate::{Cascade, Homogeneous};
+
+/// Runtime counterpart to cascading a fixed number of times, for a homogeneous chain (e.g. a
+/// `Sequence`) whose output can be fed straight back in as its input.
+pub trait RepeatedCascade: Homogeneous {
+    /// Cascades `times` times in a row, each time on the output of the last. Zero times returns
+    /// `input` untouched.
+    fn cascade_repeat(input: Self::Item, times: usize) -> Self::Item;
+}
+
+impl<T> RepeatedCascade for T
+where
+    T: Homogeneous,
+    for<'a> T: Cascade<In<'a> = T::Item, Out<'a> = T::Item>,
+{
+    fn cascade_repeat(input: Self::Item, times: usize) -> Self::Item {
+        (0..times).fold(input, |item, _| T::cascade(item))
+    }
+}
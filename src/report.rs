@@ -0,0 +1,33 @@
+use std::fmt::Write;
+
+use crate::ProfiledCascade;
+
+/// Human readable names for the links of a chain, used by `cascade_report`. Links without a name
+/// are reported by index alone, so `impl Named for T {}` is enough to opt in.
+pub trait Named {
+    fn stage_name(_index: usize) -> Option<&'static str> {
+        None
+    }
+}
+
+pub trait ReportedCascade: ProfiledCascade + Named {
+    /// Cascades as usual, also returning a report with one line per link, giving its index,
+    /// name, input and output types and how long it took, followed by the total.
+    fn cascade_report(input: Self::In<'_>) -> (Self::Out<'_>, String) {
+        let (out, timings) = Self::cascade_profiled(input);
+        let mut report = String::new();
+        for timing in &timings {
+            let stage = &timing.stage;
+            let _ = write!(report, "stage {}", stage.index);
+            if let Some(name) = Self::stage_name(stage.index) {
+                let _ = write!(report, " ({name})");
+            }
+            let _ = writeln!(report, ": {} -> {} in {:?}", stage.input, stage.output, timing.elapsed);
+        }
+        let total: std::time::Duration = timings.iter().map(|timing| timing.elapsed).sum();
+        let _ = write!(report, "total: {total:?}");
+        (out, report)
+    }
+}
+
+impl<T: ProfiledCascade + Named> ReportedCascade for T {}
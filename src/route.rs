@@ -0,0 +1,79 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::hash::Hash;
+
+use crate::{CascadeOf, StageCoverage};
+
+/// Dispatches every input to one of several pipelines of the same shape, picked by the key that
+/// `route` finds in the input itself, like the type field of a message.
+pub struct Router<K, I, O, R = fn(&I) -> Option<K>> {
+    route: R,
+    routes: HashMap<K, fn(I) -> O>,
+}
+
+impl<K: Eq + Hash, I, O, R: Fn(&I) -> Option<K>> Router<K, I, O, R> {
+    /// A router without any routes yet. `route` returns `None` for inputs that don't have a key.
+    pub fn new(route: R) -> Self {
+        Self { route, routes: HashMap::new() }
+    }
+
+    /// Sends inputs keyed `key` through `P`, replacing whatever was routed there before.
+    pub fn route<P: CascadeOf<I, O>>(mut self, key: K) -> Self {
+        self.routes.insert(key, P::cascade);
+        self
+    }
+
+    /// Sends everything that doesn't match a route through `P`.
+    pub fn or_default<P: CascadeOf<I, O>>(self) -> RouteOrDefault<K, I, O, R> {
+        RouteOrDefault { router: self, default: P::cascade }
+    }
+
+    /// The keys with a route, in no particular order.
+    pub fn keys(&self) -> impl Iterator<Item = &K> {
+        self.routes.keys()
+    }
+
+    /// Cascades `input` through the pipeline its key is routed to, handing it back if it has no
+    /// key or nothing is routed there.
+    pub fn cascade(&self, input: I) -> Result<O, I> {
+        match (self.route)(&input).and_then(|key| self.routes.get(&key)) {
+            Some(pipeline) => Ok(pipeline(input)),
+            None => Err(input),
+        }
+    }
+}
+
+/// A `Router` with a catch-all pipeline for inputs that don't match any route, so that
+/// unanticipated inputs are still handled instead of failing to route.
+pub struct RouteOrDefault<K, I, O, R = fn(&I) -> Option<K>> {
+    router: Router<K, I, O, R>,
+    default: fn(I) -> O,
+}
+
+impl<K: Eq + Hash, I, O, R: Fn(&I) -> Option<K>> RouteOrDefault<K, I, O, R> {
+    pub fn cascade(&self, input: I) -> O {
+        self.router.cascade(input).unwrap_or_else(self.default)
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &K> {
+        self.router.keys()
+    }
+
+    /// Same as `cascade`, also recording a hit in `coverage` for the route taken, named after its
+    /// key, or `default`.
+    pub fn cascade_covered(&self, input: I, coverage: &mut StageCoverage) -> O
+    where
+        K: Display,
+    {
+        match self.key(&input) {
+            Some(key) => coverage.hit(key.to_string()),
+            None => coverage.hit("default"),
+        }
+        self.cascade(input)
+    }
+
+    /// The key `input` would be routed by, or `None` if it'd go through the default pipeline.
+    pub fn key(&self, input: &I) -> Option<K> {
+        (self.router.route)(input).filter(|key| self.router.routes.contains_key(key))
+    }
+}
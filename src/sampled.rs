@@ -0,0 +1,65 @@
+use std::collections::hash_map::RandomState;
+use std::hash::BuildHasher;
+
+use crate::{Observer, StageInfo};
+
+/// Small seeded generator, plenty for sampling and shuffling but not for anything that has to be
+/// unpredictable.
+pub(crate) fn splitmix64(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+/// Forwards to `O` for only about `PERCENT` out of every 100 cascades, so expensive
+/// instrumentation can stay on in high throughput services. The decision is made when link 0
+/// starts and holds for the whole cascade, so sampled runs are always observed in full.
+pub struct Sampled<O, const PERCENT: u8> {
+    observer: O,
+    state: u64,
+    active: bool,
+}
+
+impl<O: Observer, const PERCENT: u8> Sampled<O, PERCENT> {
+    /// Seeded from the same per-process randomness as `HashMap`.
+    pub fn new(observer: O) -> Self {
+        Self::with_seed(observer, RandomState::new().hash_one(0u8))
+    }
+
+    /// Same as `new`, but with a fixed seed so the sampled runs are reproducible.
+    pub fn with_seed(observer: O, seed: u64) -> Self {
+        const { assert!(PERCENT <= 100, "PERCENT can't be more than 100") };
+        Self { observer, state: seed, active: false }
+    }
+
+    pub fn inner(&self) -> &O {
+        &self.observer
+    }
+
+    pub fn into_inner(self) -> O {
+        self.observer
+    }
+
+    fn next(&mut self) -> u64 {
+        splitmix64(&mut self.state)
+    }
+}
+
+impl<O: Observer, const PERCENT: u8> Observer for Sampled<O, PERCENT> {
+    fn before(&mut self, stage: &StageInfo) {
+        if stage.index == 0 {
+            self.active = self.next() % 100 < PERCENT as u64;
+        }
+        if self.active {
+            self.observer.before(stage);
+        }
+    }
+
+    fn after(&mut self, stage: &StageInfo) {
+        if self.active {
+            self.observer.after(stage);
+        }
+    }
+}
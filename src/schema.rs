@@ -0,0 +1,52 @@
+use std::error::Error;
+use std::fmt;
+
+use crate::Cascade;
+
+/// A definition of what well-formed values look like, kept separately from the pipeline itself,
+/// e.g. shared with whatever produces or consumes the data. Closures returning why a value is
+/// malformed are schemas too.
+pub trait Schema<T: ?Sized> {
+    fn check(&self, value: &T) -> Result<(), String>;
+}
+
+impl<T: ?Sized, F: Fn(&T) -> Result<(), String>> Schema<T> for F {
+    fn check(&self, value: &T) -> Result<(), String> {
+        self(value)
+    }
+}
+
+/// Which boundary of a cascade rejected a value, and why.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SchemaError {
+    Input(String),
+    Output(String),
+}
+
+impl fmt::Display for SchemaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SchemaError::Input(reason) => write!(f, "input doesn't match its schema: {reason}"),
+            SchemaError::Output(reason) => write!(f, "output doesn't match its schema: {reason}"),
+        }
+    }
+}
+
+impl Error for SchemaError {}
+
+pub trait SchemaValidatedCascade: Cascade {
+    /// Cascades `input` only if it matches `in_schema`, so malformed data is rejected before any
+    /// link runs, then checks the output against `out_schema` before handing it back.
+    fn cascade_schema_validated<'a>(
+        in_schema: &impl Schema<Self::In<'a>>,
+        out_schema: &impl Schema<Self::Out<'a>>,
+        input: Self::In<'a>,
+    ) -> Result<Self::Out<'a>, SchemaError> {
+        in_schema.check(&input).map_err(SchemaError::Input)?;
+        let out = Self::cascade(input);
+        out_schema.check(&out).map_err(SchemaError::Output)?;
+        Ok(out)
+    }
+}
+
+impl<T: Cascade> SchemaValidatedCascade for T {}
@@ -0,0 +1,28 @@
+use seq_macro::seq;
+
+pub struct Yes;
+pub struct No;
+
+/// Type level `M == N`, for wrappers that only change the behavior of link `N` and pass every
+/// other link through. A plain `if M == N` works when every link has the same types, but
+/// otherwise the two branches can't typecheck, so the wrapper dispatches on `Is` instead and
+/// provides one impl for `Yes` and another for `No`.
+pub trait Select<const M: usize, const N: usize> {
+    type Is;
+}
+
+// only the diagonal is `Yes`, and every pair below it gets mirrored so the whole 32x32 grid
+// is covered without needing `M != N` at the macro level
+seq!(M in 0..32 {
+    impl Select<M, M> for () {
+        type Is = Yes;
+    }
+    seq!(N in 0..M {
+        impl Select<M, N> for () {
+            type Is = No;
+        }
+        impl Select<N, M> for () {
+            type Is = No;
+        }
+    });
+});
@@ -0,0 +1,90 @@
+use seq_macro::seq;
+
+use crate::{FilterCascade, FilterChain, FilterLink, InRange, Length, L};
+
+/// How many inputs a filter link let through and how many it dropped.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct StageSelectivity {
+    pub passed: u64,
+    pub dropped: u64,
+}
+
+impl StageSelectivity {
+    /// Fraction of the inputs that reached this link which it let through, or `None` if no
+    /// input ever reached it.
+    pub fn pass_rate(&self) -> Option<f64> {
+        let seen = self.passed + self.dropped;
+        (seen > 0).then(|| self.passed as f64 / seen as f64)
+    }
+}
+
+/// Per link selectivity of a filter cascade over a batch, indexed by link. Filters that drop the
+/// most are best moved to the front, where they save every later link from running.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Selectivity {
+    pub stages: Vec<StageSelectivity>,
+}
+
+impl Selectivity {
+    fn record(&mut self, stage: usize, passed: bool) {
+        match passed {
+            true => self.stages[stage].passed += 1,
+            false => self.stages[stage].dropped += 1,
+        }
+    }
+}
+
+fn filter<'a, const N: usize, T>(input: T::In<'a>, selectivity: &mut Selectivity) -> Option<T::Out<'a>>
+where
+    T: FilterChain<N> + InRange<N, <T as Length>::Len>,
+{
+    let out = T::filter(input);
+    selectivity.record(N, out.is_some());
+    out
+}
+
+/// Same as `FilterLink<N>`, but counts what every link passes and drops.
+pub trait SelectivityLink<const N: usize>: FilterLink<N> {
+    fn selectivity_link<'a>(input: Self::In<'a>, selectivity: &mut Selectivity) -> Option<Self::Out<'a>>;
+}
+
+impl<T: FilterChain<0>> SelectivityLink<1> for T {
+    fn selectivity_link<'a>(input: Self::In<'a>, selectivity: &mut Selectivity) -> Option<Self::Out<'a>> {
+        filter::<0, T>(input, selectivity)
+    }
+}
+
+seq!(N in 2..=32 {
+    impl<T> SelectivityLink<N> for T
+    where
+        T: FilterChain<0>,
+        for<'a> T: SelectivityLink<{N - 1}, In<'a> = <T as FilterChain<0>>::In<'a>>,
+        for<'a> T: FilterChain<{N - 1}, In<'a> = <T as FilterLink<{N - 1}>>::Out<'a>>,
+    {
+        fn selectivity_link<'a>(input: Self::In<'a>, selectivity: &mut Selectivity) -> Option<Self::Out<'a>> {
+            let out = <T as SelectivityLink<{N - 1}>>::selectivity_link(input, selectivity)?;
+            filter::<{N - 1}, T>(out, selectivity)
+        }
+    }
+});
+
+pub trait SelectivityCascade: FilterCascade {
+    /// Filter cascades every input, collecting what makes it through along with how selective
+    /// each link was.
+    fn cascade_iter_with_selectivity<'a>(
+        inputs: impl IntoIterator<Item = Self::In<'a>>,
+    ) -> (Vec<Self::Out<'a>>, Selectivity);
+}
+
+impl<const N: usize, T: SelectivityLink<N> + Length<Len = L<N>>> SelectivityCascade for T {
+    fn cascade_iter_with_selectivity<'a>(
+        inputs: impl IntoIterator<Item = Self::In<'a>>,
+    ) -> (Vec<Self::Out<'a>>, Selectivity) {
+        let mut selectivity = Selectivity { stages: vec![StageSelectivity::default(); N] };
+        let outputs = inputs
+            .into_iter()
+            .filter_map(|input| <T as SelectivityLink<N>>::selectivity_link(input, &mut selectivity))
+            .collect();
+        (outputs, selectivity)
+    }
+}
@@ -0,0 +1,130 @@
+use seq_macro::seq;
+
+use crate::{Chain, InRange, Length, L};
+
+/// The single `Item` type shared by every `Sequence<N>` impl of a type. Since it lives outside of
+/// `Sequence<N>` it can only be implemented once, so there's no way for one stage to pick a
+/// different type than the others.
+pub trait Homogeneous {
+    type Item;
+}
+
+/// A `Chain<N>` whose `In` and `Out` are both forced to be `Homogeneous::Item`.
+///
+/// Stages can't change the type mid-sequence:
+/// ```compile_fail
+/// use chain_link::*;
+///
+/// struct Words;
+/// impl Homogeneous for Words {
+///     type Item = String;
+/// }
+/// impl Length for Words {
+///     type Len = L<2>;
+/// }
+/// impl Sequence<0> for Words {
+///     fn step(item: String) -> String {
+///         item.to_uppercase()
+///     }
+/// }
+/// impl Sequence<1> for Words {
+///     fn step(item: String) -> usize {
+///         item.len()
+///     }
+/// }
+/// ```
+///
+/// And since a type only gets one `Item`, a second one is rejected too:
+/// ```compile_fail
+/// use chain_link::*;
+///
+/// struct Words;
+/// impl Homogeneous for Words {
+///     type Item = String;
+/// }
+/// impl Homogeneous for Words {
+///     type Item = usize;
+/// }
+/// ```
+///
+/// Sneaking a plain `Chain<N>` into the middle still cascades, but it's no longer a sequence, so
+/// `Sequential` isn't implemented:
+/// ```compile_fail
+/// use chain_link::*;
+///
+/// struct Words;
+/// impl Homogeneous for Words {
+///     type Item = String;
+/// }
+/// impl Length for Words {
+///     type Len = L<2>;
+/// }
+/// impl Sequence<0> for Words {
+///     fn step(item: String) -> String {
+///         item.to_uppercase()
+///     }
+/// }
+/// impl Chain<1> for Words {
+///     type In<'a> = String;
+///     type Out<'a> = usize;
+///
+///     fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+///         input.len()
+///     }
+/// }
+/// let _ = Words::steps();
+/// ```
+pub trait Sequence<const N: usize>
+where
+    Self: Homogeneous + InRange<N, <Self as Length>::Len>,
+{
+    fn step(item: Self::Item) -> Self::Item;
+}
+
+impl<const N: usize, T: Sequence<N>> Chain<N> for T
+where
+    T: InRange<N, <T as Length>::Len>,
+{
+    type In<'a> = T::Item;
+    type Out<'a> = T::Item;
+
+    fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+        <T as Sequence<N>>::step(input)
+    }
+}
+
+/// Sequence counterpart to `Link<N>`, collecting the steps from 0..N.
+pub trait StepLink<const N: usize>: Homogeneous {
+    fn step_link() -> Vec<fn(Self::Item) -> Self::Item>;
+}
+
+impl<T: Sequence<0>> StepLink<1> for T {
+    fn step_link() -> Vec<fn(Self::Item) -> Self::Item> {
+        vec![<T as Sequence<0>>::step]
+    }
+}
+
+seq!(N in 2..=32 {
+    impl<T> StepLink<N> for T
+    where
+        T: StepLink<{N - 1}> + Sequence<{N - 1}>,
+    {
+        fn step_link() -> Vec<fn(Self::Item) -> Self::Item> {
+            let mut steps = <T as StepLink<{N - 1}>>::step_link();
+            steps.push(<T as Sequence<{N - 1}>>::step);
+            steps
+        }
+    }
+});
+
+/// Implemented for anything where every stage in 0..Length is a `Sequence<N>`. Cascading still
+/// goes through `Cascade`, this just exposes the steps at runtime now that they share a type.
+pub trait Sequential: Homogeneous {
+    fn steps() -> Vec<fn(Self::Item) -> Self::Item>;
+}
+
+impl<const N: usize, T: StepLink<N> + Length<Len = L<N>>> Sequential for T {
+    fn steps() -> Vec<fn(Self::Item) -> Self::Item> {
+        <T as StepLink<N>>::step_link()
+    }
+}
@@ -0,0 +1,50 @@
+use std::future::Future;
+use std::marker::PhantomData;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use tower_service::Service;
+
+use crate::AsyncCascade;
+
+/// Exposes an `AsyncCascade` as a `tower::Service` so it can slot into middleware stacks. The
+/// cascade's output has to be a `Result`, whose `Ok` becomes the `Response` and whose `Err`
+/// becomes the `Error`.
+pub struct CascadeService<T>(PhantomData<fn() -> T>);
+
+impl<T> CascadeService<T> {
+    pub fn new() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<T> Default for CascadeService<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Clone for CascadeService<T> {
+    fn clone(&self) -> Self {
+        Self::new()
+    }
+}
+
+impl<T, Req, Res, E> Service<Req> for CascadeService<T>
+where
+    T: 'static,
+    for<'a> T: AsyncCascade<In<'a> = Req, Out<'a> = Result<Res, E>>,
+    Req: 'static,
+{
+    type Response = Res;
+    type Error = E;
+    type Future = Pin<Box<dyn Future<Output = Result<Res, E>> + Send>>;
+
+    fn poll_ready(&mut self, _: &mut Context<'_>) -> Poll<Result<(), E>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, req: Req) -> Self::Future {
+        Box::pin(T::async_cascade(req))
+    }
+}
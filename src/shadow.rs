@@ -0,0 +1,127 @@
+use std::fmt::{self, Debug};
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{self, SyncSender};
+use std::thread;
+
+use crate::CascadeOf;
+
+/// The same input gave different outputs in the old and new pipelines.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Divergence<A, B> {
+    pub input: A,
+    pub old: B,
+    pub new: B,
+}
+
+/// Runs the comparisons of `cascade_shadow` one at a time on a background thread of its own. At
+/// most `capacity` of them wait their turn, and once that many are queued further ones are
+/// dropped rather than holding up the caller, since shadow traffic is only ever a sample.
+pub struct ShadowWorker {
+    jobs: SyncSender<Box<dyn FnOnce() + Send>>,
+    dropped: AtomicUsize,
+}
+
+impl ShadowWorker {
+    /// Starts the worker thread, which stops once the worker is dropped and the queue is empty.
+    pub fn new(capacity: usize) -> Self {
+        let (jobs, queue) = mpsc::sync_channel::<Box<dyn FnOnce() + Send>>(capacity);
+        thread::spawn(move || {
+            for job in queue {
+                // a panic in the new pipeline only loses that one comparison
+                let _ = panic::catch_unwind(AssertUnwindSafe(job));
+            }
+        });
+        Self { jobs, dropped: AtomicUsize::new(0) }
+    }
+
+    /// How many comparisons were dropped because the queue was full.
+    pub fn dropped(&self) -> usize {
+        self.dropped.load(Ordering::Relaxed)
+    }
+
+    fn submit(&self, job: Box<dyn FnOnce() + Send>) {
+        if self.jobs.try_send(job).is_err() {
+            self.dropped.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+}
+
+/// Runs `Old` on `input` and returns its output right away, while `New` runs on the same input
+/// on `worker`. If the outputs differ, `on_divergence` gets called from the worker's thread. A
+/// panic in `New` never reaches the caller.
+pub fn cascade_shadow<Old, New, A, B>(
+    worker: &ShadowWorker,
+    input: A,
+    on_divergence: impl FnOnce(Divergence<A, B>) + Send + 'static,
+) -> B
+where
+    Old: CascadeOf<A, B>,
+    New: CascadeOf<A, B> + 'static,
+    A: Clone + Send + 'static,
+    B: Clone + PartialEq + Send + 'static,
+{
+    let shadow_input = input.clone();
+    let old = Old::cascade(input);
+    let shadow_old = old.clone();
+    worker.submit(Box::new(move || {
+        let new = New::cascade(shadow_input.clone());
+        if new != shadow_old {
+            on_divergence(Divergence { input: shadow_input, old: shadow_old, new });
+        }
+    }));
+    old
+}
+
+/// Every input of a corpus where two versions of a pipeline disagreed, found by `diff_versions`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DiffReport<A, B> {
+    /// How many inputs were compared.
+    pub total: usize,
+    /// The position of each divergent input in the corpus, along with both outputs.
+    pub divergences: Vec<(usize, Divergence<A, B>)>,
+}
+
+impl<A, B> DiffReport<A, B> {
+    /// Whether both versions agreed on every input.
+    pub fn is_clean(&self) -> bool {
+        self.divergences.is_empty()
+    }
+
+    pub fn matched(&self) -> usize {
+        self.total - self.divergences.len()
+    }
+}
+
+/// One line for the summary, then one per divergent input.
+impl<A: Debug, B: Debug> fmt::Display for DiffReport<A, B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} of {} inputs diverged", self.divergences.len(), self.total)?;
+        for (index, divergence) in &self.divergences {
+            write!(f, "\n#{index} {:?}: old {:?}, new {:?}", divergence.input, divergence.old, divergence.new)?;
+        }
+        Ok(())
+    }
+}
+
+/// Runs every input of `corpus` through both `Old` and `New`, reporting each one where their
+/// outputs differ. Unlike `cascade_shadow` this runs offline and in the foreground, to check a
+/// migration against a whole corpus, e.g. one recorded with `Corpus::record`, before switching.
+pub fn diff_versions<Old, New, A, B>(corpus: impl IntoIterator<Item = A>) -> DiffReport<A, B>
+where
+    Old: CascadeOf<A, B>,
+    New: CascadeOf<A, B>,
+    A: Clone,
+    B: PartialEq,
+{
+    let mut report = DiffReport { total: 0, divergences: Vec::new() };
+    for (index, input) in corpus.into_iter().enumerate() {
+        report.total += 1;
+        let old = Old::cascade(input.clone());
+        let new = New::cascade(input.clone());
+        if old != new {
+            report.divergences.push((index, Divergence { input, old, new }));
+        }
+    }
+    report
+}
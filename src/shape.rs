@@ -0,0 +1,17 @@
+use crate::Cascade;
+
+/// Any cascade from `A` to `B`, for writing code that accepts pipelines by shape:
+/// ```
+/// use chain_link::*;
+///
+/// fn shout<P: CascadeOf<String, String>>(input: &str) -> String {
+///     P::cascade(input.to_owned()) + "!"
+/// }
+/// ```
+///
+/// Spelled out, this is `for<'a> Cascade<In<'a> = A, Out<'a> = B>`, which pipelines whose types
+/// borrow can't satisfy with a single `A`. Those need the bound written by hand, e.g.
+/// `P: for<'a> Cascade<In<'a> = &'a str, Out<'a> = usize>`.
+pub trait CascadeOf<A, B>: for<'a> Cascade<In<'a> = A, Out<'a> = B> {}
+
+impl<A, B, T> CascadeOf<A, B> for T where T: for<'a> Cascade<In<'a> = A, Out<'a> = B> {}
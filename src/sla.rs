@@ -0,0 +1,74 @@
+use std::collections::BTreeMap;
+use std::time::{Duration, Instant};
+
+use crate::{ObservedCascade, Observer, StageInfo};
+
+/// The longest each link is allowed to take. Links without one can take as long as they like.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Slas {
+    limits: BTreeMap<usize, Duration>,
+}
+
+impl Slas {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Allows link `stage` at most `max`.
+    pub fn stage(mut self, stage: usize, max: Duration) -> Self {
+        self.limits.insert(stage, max);
+        self
+    }
+
+    pub fn limit(&self, stage: usize) -> Option<Duration> {
+        self.limits.get(&stage).copied()
+    }
+}
+
+/// A link that took longer than its SLA allowed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SlaViolation {
+    pub stage: StageInfo,
+    pub limit: Duration,
+    pub elapsed: Duration,
+}
+
+/// Observer that times every link and reports the ones over their SLA.
+pub struct SlaObserver<'a, F> {
+    slas: &'a Slas,
+    on_violation: F,
+    started: Option<Instant>,
+}
+
+impl<'a, F: FnMut(SlaViolation)> SlaObserver<'a, F> {
+    pub fn new(slas: &'a Slas, on_violation: F) -> Self {
+        Self { slas, on_violation, started: None }
+    }
+}
+
+impl<F: FnMut(SlaViolation)> Observer for SlaObserver<'_, F> {
+    fn before(&mut self, _: &StageInfo) {
+        self.started = Some(Instant::now());
+    }
+
+    fn after(&mut self, stage: &StageInfo) {
+        let (Some(started), Some(limit)) = (self.started.take(), self.slas.limit(stage.index)) else {
+            return;
+        };
+        let elapsed = started.elapsed();
+        if elapsed > limit {
+            (self.on_violation)(SlaViolation { stage: *stage, limit, elapsed });
+        }
+    }
+}
+
+pub trait SlaCascade: ObservedCascade {
+    /// Cascades as usual, calling `on_violation` for every link that took longer than `slas`
+    /// allows it, e.g. to raise an alert. Links aren't interrupted, the callback runs as soon as
+    /// the slow link finishes.
+    fn cascade_with_sla<'a>(slas: &Slas, on_violation: impl FnMut(SlaViolation), input: Self::In<'a>) -> Self::Out<'a> {
+        Self::observed_cascade(input, &mut SlaObserver::new(slas, on_violation))
+    }
+}
+
+impl<T: ObservedCascade> SlaCascade for T {}
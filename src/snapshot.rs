@@ -0,0 +1,38 @@
+#[doc(hidden)]
+pub use insta as __insta;
+
+/// Snapshot tests what a pipeline cascades `input` to, with `insta`. The output is compared by its
+/// `Debug` form, either against a snapshot file next to the test, named after the test function,
+/// or against one written inline after an `@`, which `cargo insta review` fills in:
+///
+/// ```
+/// use chain_link::*;
+///
+/// struct Double;
+///
+/// impl Chain<0> for Double {
+///     type In<'a> = u32;
+///     type Out<'a> = u32;
+///
+///     fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+///         input * 2
+///     }
+/// }
+///
+/// impl Length for Double {
+///     type Len = L<1>;
+/// }
+///
+/// assert_cascade_snapshot!(Double, 21, @"42");
+/// ```
+#[macro_export]
+macro_rules! assert_cascade_snapshot {
+    ($pipeline:ty, $input:expr $(,)?) => {{
+        let output = <$pipeline as $crate::Cascade>::cascade($input);
+        $crate::__insta::assert_debug_snapshot!(output)
+    }};
+    ($pipeline:ty, $input:expr, @$snapshot:literal $(,)?) => {{
+        let output = <$pipeline as $crate::Cascade>::cascade($input);
+        $crate::__insta::assert_debug_snapshot!(output, @$snapshot)
+    }};
+}
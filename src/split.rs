@@ -0,0 +1,113 @@
+use seq_macro::seq;
+
+use crate::{Chain, Codec, DecodeError, Length, Link, No, Select, Yes, L};
+
+/// Runs links `P..N` of a chain, picking up where `Link<P>` left off.
+pub trait ResumeLink<const P: usize, const N: usize> {
+    type In<'a>;
+    type Out<'a>;
+
+    fn resume_link(input: Self::In<'_>) -> Self::Out<'_>;
+}
+
+/// Implementation detail of `ResumeLink`, where `Is` says whether link `N - 1` is the first one
+/// to resume from, ending the recursion.
+pub trait ResumeFrom<const P: usize, const N: usize, Is> {
+    type In<'a>;
+    type Out<'a>;
+
+    fn resume_from(input: Self::In<'_>) -> Self::Out<'_>;
+}
+
+// spelled out for `N = 1`, where there is no `No` case since nothing comes before link 0
+impl<const P: usize, T> ResumeLink<P, 1> for T
+where
+    (): Select<P, 0>,
+    T: ResumeFrom<P, 1, <() as Select<P, 0>>::Is>,
+{
+    type In<'a> = <T as ResumeFrom<P, 1, <() as Select<P, 0>>::Is>>::In<'a>;
+    type Out<'a> = <T as ResumeFrom<P, 1, <() as Select<P, 0>>::Is>>::Out<'a>;
+
+    fn resume_link(input: Self::In<'_>) -> Self::Out<'_> {
+        <T as ResumeFrom<P, 1, <() as Select<P, 0>>::Is>>::resume_from(input)
+    }
+}
+
+impl<const P: usize, T: Chain<0>> ResumeFrom<P, 1, Yes> for T {
+    type In<'a> = <T as Chain<0>>::In<'a>;
+    type Out<'a> = <T as Chain<0>>::Out<'a>;
+
+    fn resume_from(input: Self::In<'_>) -> Self::Out<'_> {
+        <T as Chain<0>>::chain(input)
+    }
+}
+
+seq!(N in 2..=32 {
+    impl<const P: usize, T> ResumeLink<P, N> for T
+    where
+        (): Select<P, {N - 1}>,
+        T: ResumeFrom<P, N, <() as Select<P, {N - 1}>>::Is>,
+    {
+        type In<'a> = <T as ResumeFrom<P, N, <() as Select<P, {N - 1}>>::Is>>::In<'a>;
+        type Out<'a> = <T as ResumeFrom<P, N, <() as Select<P, {N - 1}>>::Is>>::Out<'a>;
+
+        fn resume_link(input: Self::In<'_>) -> Self::Out<'_> {
+            <T as ResumeFrom<P, N, <() as Select<P, {N - 1}>>::Is>>::resume_from(input)
+        }
+    }
+
+    impl<const P: usize, T: Chain<{N - 1}>> ResumeFrom<P, N, Yes> for T {
+        type In<'a> = <T as Chain<{N - 1}>>::In<'a>;
+        type Out<'a> = <T as Chain<{N - 1}>>::Out<'a>;
+
+        fn resume_from(input: Self::In<'_>) -> Self::Out<'_> {
+            <T as Chain<{N - 1}>>::chain(input)
+        }
+    }
+
+    impl<const P: usize, T> ResumeFrom<P, N, No> for T
+    where
+        T: ResumeLink<P, {N - 1}>,
+        for<'a> T: Chain<{N - 1}, In<'a> = <T as ResumeLink<P, {N - 1}>>::Out<'a>>,
+    {
+        type In<'a> = <T as ResumeLink<P, {N - 1}>>::In<'a>;
+        type Out<'a> = <T as Chain<{N - 1}>>::Out<'a>;
+
+        fn resume_from(input: Self::In<'_>) -> Self::Out<'_> {
+            let out = <T as ResumeLink<P, {N - 1}>>::resume_link(input);
+            <T as Chain<{N - 1}>>::chain(out)
+        }
+    }
+});
+
+/// Splits a cascade at pivot `P`, so links `0..P` can run in one place and `P..N` somewhere else,
+/// with the output of link `P - 1` encoded in between.
+pub trait SplitCascade<const P: usize> {
+    type In<'a>;
+    type Out<'a>;
+
+    /// Runs links `0..P` and encodes the result.
+    fn cascade_to_pivot(input: Self::In<'_>) -> Vec<u8>;
+
+    /// Decodes what `cascade_to_pivot` produced and runs the remaining links.
+    fn resume_from_pivot(pivot: &[u8]) -> Result<Self::Out<'static>, DecodeError>;
+}
+
+impl<const P: usize, const N: usize, T> SplitCascade<P> for T
+where
+    T: Link<P> + Length<Len = L<N>>,
+    for<'a> <T as Link<P>>::Out<'a>: Codec,
+    for<'a> T: ResumeLink<P, N, In<'a> = <T as Link<P>>::Out<'a>>,
+{
+    type In<'a> = <T as Link<P>>::In<'a>;
+    type Out<'a> = <T as ResumeLink<P, N>>::Out<'a>;
+
+    fn cascade_to_pivot(input: Self::In<'_>) -> Vec<u8> {
+        <T as Link<P>>::link(input).to_bytes()
+    }
+
+    fn resume_from_pivot(pivot: &[u8]) -> Result<Self::Out<'static>, DecodeError> {
+        let input = <<T as Link<P>>::Out<'static> as Codec>::from_bytes(pivot)?;
+        Ok(<T as ResumeLink<P, N>>::resume_link(input))
+    }
+}
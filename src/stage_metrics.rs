@@ -0,0 +1,94 @@
+use std::any::type_name;
+use std::panic::{self, AssertUnwindSafe};
+use std::time::Instant;
+
+use prometheus::{HistogramOpts, HistogramVec, IntCounterVec, Opts, Registry};
+
+use crate::type_flow::strip_paths;
+use crate::{ObservedCascade, Observer, StageInfo};
+
+/// Per-link Prometheus metrics, all labelled with the `pipeline` type and the link's `stage`
+/// index: a `chain_link_stage_invocations_total` counter, a `chain_link_stage_errors_total`
+/// counter of links that panicked, and a `chain_link_stage_duration_seconds` histogram. Register
+/// them once and share them between every pipeline reporting to the same registry.
+#[derive(Clone, Debug)]
+pub struct StageMetrics {
+    invocations: IntCounterVec,
+    errors: IntCounterVec,
+    duration: HistogramVec,
+}
+
+const LABELS: [&str; 2] = ["pipeline", "stage"];
+
+impl StageMetrics {
+    pub fn register(registry: &Registry) -> prometheus::Result<Self> {
+        let metrics = Self {
+            invocations: IntCounterVec::new(
+                Opts::new("chain_link_stage_invocations_total", "Number of times each link of a cascade ran"),
+                &LABELS,
+            )?,
+            errors: IntCounterVec::new(
+                Opts::new("chain_link_stage_errors_total", "Number of times each link of a cascade panicked"),
+                &LABELS,
+            )?,
+            duration: HistogramVec::new(
+                HistogramOpts::new("chain_link_stage_duration_seconds", "Time spent in each link of a cascade"),
+                &LABELS,
+            )?,
+        };
+        registry.register(Box::new(metrics.invocations.clone()))?;
+        registry.register(Box::new(metrics.errors.clone()))?;
+        registry.register(Box::new(metrics.duration.clone()))?;
+        Ok(metrics)
+    }
+
+    /// Observer reporting every link of `T`.
+    pub fn observer<T>(&self) -> PrometheusObserver<'_> {
+        PrometheusObserver { metrics: self, pipeline: strip_paths(type_name::<T>()), running: None }
+    }
+}
+
+pub struct PrometheusObserver<'a> {
+    metrics: &'a StageMetrics,
+    pipeline: String,
+    running: Option<(usize, Instant)>,
+}
+
+impl PrometheusObserver<'_> {
+    /// Counts the link that was running as an error, for when it panicked.
+    pub fn failed(&mut self) {
+        if let Some((stage, _)) = self.running.take() {
+            self.metrics.errors.with_label_values(&[self.pipeline.as_str(), &stage.to_string()]).inc();
+        }
+    }
+}
+
+impl Observer for PrometheusObserver<'_> {
+    fn before(&mut self, stage: &StageInfo) {
+        let labels = [self.pipeline.as_str(), &stage.index.to_string()];
+        self.metrics.invocations.with_label_values(&labels).inc();
+        self.running = Some((stage.index, Instant::now()));
+    }
+
+    fn after(&mut self, _: &StageInfo) {
+        if let Some((stage, started)) = self.running.take() {
+            let labels = [self.pipeline.as_str(), &stage.to_string()];
+            self.metrics.duration.with_label_values(&labels).observe(started.elapsed().as_secs_f64());
+        }
+    }
+}
+
+pub trait PrometheusCascade: ObservedCascade + Sized {
+    /// Cascades as usual, reporting every link to `metrics`. If a link panics it's counted as an
+    /// error before the panic carries on.
+    fn cascade_with_metrics<'a>(input: Self::In<'a>, metrics: &StageMetrics) -> Self::Out<'a> {
+        let mut observer = metrics.observer::<Self>();
+        let out = panic::catch_unwind(AssertUnwindSafe(|| Self::observed_cascade(input, &mut observer)));
+        out.unwrap_or_else(|payload| {
+            observer.failed();
+            panic::resume_unwind(payload)
+        })
+    }
+}
+
+impl<T: ObservedCascade> PrometheusCascade for T {}
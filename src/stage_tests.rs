@@ -0,0 +1,87 @@
+use crate::sealed::Len;
+use crate::{Chain, InRange, Length};
+
+#[doc(hidden)]
+pub use seq_macro::seq as __seq;
+
+/// Runs link `N` of `T` on its own, which is what the stubs from `gen_stage_tests!` are meant to
+/// be filled in with.
+pub fn run_stage<const N: usize, T>(input: T::In<'_>) -> T::Out<'_>
+where
+    T: Chain<N> + InRange<N, <T as Length>::Len>,
+{
+    T::chain(input)
+}
+
+#[doc(hidden)]
+pub const fn __stage_count<T: Length>() -> usize {
+    <T::Len as Len>::LEN
+}
+
+/// Scaffolds an ignored `stage_N` test per link of a pipeline, each pointing at
+/// `run_stage::<N, Pipeline>` and left as a `todo!()` to be filled in, plus a `STAGE_TESTS` const
+/// with how many stages there are.
+///
+/// A macro can't see the `Length` impl, so it's given the number of links as well, and fails to
+/// compile if that doesn't match. The stubs are emitted as-is where the macro is invoked, so give
+/// each pipeline its own module.
+///
+/// ```
+/// use chain_link::*;
+///
+/// struct Pipeline;
+///
+/// impl Chain<0> for Pipeline {
+///     type In<'a> = u32;
+///     type Out<'a> = u32;
+///
+///     fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+///         input + 1
+///     }
+/// }
+///
+/// impl Length for Pipeline {
+///     type Len = L<1>;
+/// }
+///
+/// gen_stage_tests!(Pipeline, 1);
+/// assert_eq!(STAGE_TESTS, Pipeline::len());
+/// ```
+///
+/// ```compile_fail
+/// # use chain_link::*;
+/// # struct Pipeline;
+/// # impl Chain<0> for Pipeline {
+/// #     type In<'a> = u32;
+/// #     type Out<'a> = u32;
+/// #     fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+/// #         input + 1
+/// #     }
+/// # }
+/// # impl Length for Pipeline {
+/// #     type Len = L<1>;
+/// # }
+/// gen_stage_tests!(Pipeline, 2);
+/// ```
+#[macro_export]
+macro_rules! gen_stage_tests {
+    ($pipeline:ty, $len:literal) => {
+        const _: () = ::core::assert!(
+            $crate::__stage_count::<$pipeline>() == $len,
+            "gen_stage_tests! was given a different number of stages than the pipeline's `Length`",
+        );
+
+        #[allow(dead_code)]
+        const STAGE_TESTS: usize = $len;
+
+        $crate::__seq!(N in 0..$len {
+            #[test]
+            #[ignore = "TODO: not written yet"]
+            #[allow(dead_code)]
+            fn stage_~N() {
+                let _run = $crate::run_stage::<N, $pipeline>;
+                ::core::todo!("assert what stage {} of {} does", N, ::core::stringify!($pipeline))
+            }
+        });
+    };
+}
@@ -0,0 +1,115 @@
+use seq_macro::seq;
+
+use crate::{Codec, DecodeError, InRange, Length, L};
+
+/// A `Chain<N>` that keeps its own `State` between cascades. Since links don't have a `self`,
+/// the state lives in a `Stateful<T>` and gets handed to the link each time it runs.
+pub trait StatefulChain<const N: usize>
+where
+    Self: InRange<N, <Self as Length>::Len>,
+{
+    type State: Default;
+    type In<'a>;
+    type Out<'a>;
+
+    fn chain<'a>(state: &mut Self::State, input: Self::In<'a>) -> Self::Out<'a>;
+}
+
+/// `States` nests every link's state from 0..N as `((((), S0), S1), S2)`, so each link only has
+/// to peel off its own.
+pub trait StatefulLink<const N: usize> {
+    type States: Default;
+    type In<'a>;
+    type Out<'a>;
+
+    fn stateful_link<'a>(states: &mut Self::States, input: Self::In<'a>) -> Self::Out<'a>;
+}
+
+impl<T: StatefulChain<0>> StatefulLink<1> for T {
+    type States = ((), <T as StatefulChain<0>>::State);
+    type In<'a> = <T as StatefulChain<0>>::In<'a>;
+    type Out<'a> = <T as StatefulChain<0>>::Out<'a>;
+
+    fn stateful_link<'a>(states: &mut Self::States, input: Self::In<'a>) -> Self::Out<'a> {
+        <T as StatefulChain<0>>::chain(&mut states.1, input)
+    }
+}
+
+seq!(N in 2..=32 {
+    impl<T> StatefulLink<N> for T
+    where
+        T: StatefulChain<0>,
+        for<'a> T: StatefulLink<{N - 1}, In<'a> = <T as StatefulChain<0>>::In<'a>>,
+        for<'a> T: StatefulChain<{N - 1}, In<'a> = <T as StatefulLink<{N - 1}>>::Out<'a>>,
+    {
+        type States = (<T as StatefulLink<{N - 1}>>::States, <T as StatefulChain<{N - 1}>>::State);
+        type In<'a> = <T as StatefulChain<0>>::In<'a>;
+        type Out<'a> = <T as StatefulChain<{N - 1}>>::Out<'a>;
+
+        fn stateful_link<'a>(states: &mut Self::States, input: Self::In<'a>) -> Self::Out<'a> {
+            let out = <T as StatefulLink<{N - 1}>>::stateful_link(&mut states.0, input);
+            <T as StatefulChain<{N - 1}>>::chain(&mut states.1, out)
+        }
+    }
+});
+
+pub trait StatefulCascade {
+    type States: Default;
+    type In<'a>;
+    type Out<'a>;
+
+    fn stateful_cascade<'a>(states: &mut Self::States, input: Self::In<'a>) -> Self::Out<'a>;
+}
+
+impl<const N: usize, T: StatefulLink<N> + Length<Len = L<N>>> StatefulCascade for T {
+    type States = <T as StatefulLink<N>>::States;
+    type In<'a> = <T as StatefulLink<N>>::In<'a>;
+    type Out<'a> = <T as StatefulLink<N>>::Out<'a>;
+
+    fn stateful_cascade<'a>(states: &mut Self::States, input: Self::In<'a>) -> Self::Out<'a> {
+        <T as StatefulLink<N>>::stateful_link(states, input)
+    }
+}
+
+/// Holds the state of every link of `T` between cascades.
+pub struct Stateful<T: StatefulCascade> {
+    states: T::States,
+}
+
+impl<T: StatefulCascade> Stateful<T> {
+    pub fn new() -> Self {
+        Self { states: T::States::default() }
+    }
+
+    pub fn cascade<'a>(&mut self, input: T::In<'a>) -> T::Out<'a> {
+        T::stateful_cascade(&mut self.states, input)
+    }
+
+    pub fn states(&self) -> &T::States {
+        &self.states
+    }
+
+    /// Captures the state of every link, to be restored later with `restore`.
+    pub fn snapshot(&self) -> Vec<u8>
+    where
+        T::States: Codec,
+    {
+        self.states.to_bytes()
+    }
+
+    /// Replaces the state of every link with a previous `snapshot`. On failure the current state
+    /// is left untouched.
+    pub fn restore(&mut self, snapshot: &[u8]) -> Result<(), DecodeError>
+    where
+        T::States: Codec,
+    {
+        self.states = T::States::from_bytes(snapshot)?;
+        Ok(())
+    }
+}
+
+impl<T: StatefulCascade> Default for Stateful<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
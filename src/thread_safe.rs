@@ -0,0 +1,102 @@
+use seq_macro::seq;
+
+use crate::{Cascade, Chain, Length, Link, L};
+
+/// Implemented when the input of link 0 and the output of every link in 0..N are `Send + Sync`.
+pub trait ThreadSafeLink<const N: usize>: Link<N> {}
+
+impl<T: Chain<0>> ThreadSafeLink<1> for T
+where
+    for<'a> <T as Chain<0>>::In<'a>: Send + Sync,
+    for<'a> <T as Chain<0>>::Out<'a>: Send + Sync,
+{
+}
+
+seq!(N in 2..=32 {
+    impl<T> ThreadSafeLink<N> for T
+    where
+        T: Chain<0>,
+        for<'a> T: ThreadSafeLink<{N - 1}, In<'a> = <T as Chain<0>>::In<'a>>,
+        for<'a> T: Chain<{N - 1}, In<'a> = <T as Link<{N - 1}>>::Out<'a>>,
+        for<'a> <T as Chain<{N - 1}>>::Out<'a>: Send + Sync,
+    {
+    }
+});
+
+/// A pipeline that's `Send + Sync` itself, and where everything flowing between its links is
+/// too, so it can be shared across threads. Checked at compile time with `assert_thread_safe!`.
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` isn't thread safe",
+    note = "the pipeline, its input and the output of every link all need to be `Send + Sync`"
+)]
+pub trait ThreadSafe: Cascade + Send + Sync {}
+
+impl<const N: usize, T> ThreadSafe for T
+where
+    T: ThreadSafeLink<N> + Length<Len = L<N>> + Send + Sync,
+{
+}
+
+/// Fails to compile unless the pipeline is `ThreadSafe`, pointing at whatever isn't.
+///
+/// ```
+/// use chain_link::*;
+///
+/// struct Pipeline;
+///
+/// impl Chain<0> for Pipeline {
+///     type In<'a> = &'a str;
+///     type Out<'a> = std::sync::Arc<str>;
+///
+///     fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+///         input.into()
+///     }
+/// }
+///
+/// impl Length for Pipeline {
+///     type Len = L<1>;
+/// }
+///
+/// assert_thread_safe!(Pipeline);
+/// ```
+///
+/// A link handing an `Rc` to the next one isn't:
+///
+/// ```compile_fail
+/// use chain_link::*;
+///
+/// struct Pipeline;
+///
+/// impl Chain<0> for Pipeline {
+///     type In<'a> = &'a str;
+///     type Out<'a> = std::rc::Rc<str>;
+///
+///     fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+///         input.into()
+///     }
+/// }
+///
+/// impl Chain<1> for Pipeline {
+///     type In<'a> = std::rc::Rc<str>;
+///     type Out<'a> = usize;
+///
+///     fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+///         input.len()
+///     }
+/// }
+///
+/// impl Length for Pipeline {
+///     type Len = L<2>;
+/// }
+///
+/// assert_thread_safe!(Pipeline);
+/// ```
+#[macro_export]
+macro_rules! assert_thread_safe {
+    ($pipeline:ty) => {
+        const _: fn() = || {
+            fn thread_safe<T: $crate::ThreadSafe>() {}
+            thread_safe::<$pipeline>();
+        };
+    };
+}
@@ -0,0 +1,65 @@
+use std::cell::RefCell;
+use std::fmt;
+
+use crate::ContextCascade;
+
+/// Context for running a cascade speculatively. Instead of performing their side effects right
+/// away, links hand them to `defer`, and they only happen once the whole cascade's output has
+/// been validated.
+#[derive(Default)]
+pub struct Transaction {
+    effects: RefCell<Vec<Box<dyn FnOnce()>>>,
+}
+
+impl Transaction {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Holds `effect` back until the transaction commits. Effects run in the order they were
+    /// deferred.
+    pub fn defer(&self, effect: impl FnOnce() + 'static) {
+        self.effects.borrow_mut().push(Box::new(effect));
+    }
+
+    /// How many effects are waiting on the commit.
+    pub fn pending(&self) -> usize {
+        self.effects.borrow().len()
+    }
+
+    fn commit(self) {
+        self.effects.into_inner().into_iter().for_each(|effect| effect());
+    }
+}
+
+impl fmt::Debug for Transaction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Transaction").field("pending", &self.pending()).finish()
+    }
+}
+
+pub trait TransactionalCascade: ContextCascade<Context = Transaction> {
+    /// Cascades `input` in a fresh `Transaction`, then checks the output with `validate`. If it
+    /// passes, every deferred effect is committed and the output returned. Otherwise they're all
+    /// dropped, and `rollback` is called to undo anything links couldn't defer.
+    fn cascade_transactional<'a, E>(
+        input: Self::In<'a>,
+        validate: impl FnOnce(&Self::Out<'a>) -> Result<(), E>,
+        rollback: impl FnOnce(&E),
+    ) -> Result<Self::Out<'a>, E> {
+        let transaction = Transaction::new();
+        let out = Self::cascade_with(&transaction, input);
+        match validate(&out) {
+            Ok(()) => {
+                transaction.commit();
+                Ok(out)
+            }
+            Err(error) => {
+                rollback(&error);
+                Err(error)
+            }
+        }
+    }
+}
+
+impl<T: ContextCascade<Context = Transaction>> TransactionalCascade for T {}
@@ -0,0 +1,120 @@
+use std::collections::HashMap;
+use std::hash::{BuildHasherDefault, DefaultHasher, Hash};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use crate::{Chain, Clock, InRange, Length, No, Select, Yes};
+
+/// Outputs of a stage by key, each kept for `ttl` after it was produced. Expired entries are
+/// never returned, and are dropped the next time a value is stored.
+pub struct TtlCache<K, V, C> {
+    ttl: Duration,
+    entries: Mutex<HashMap<K, (V, Duration), BuildHasherDefault<DefaultHasher>>>,
+    clock: C,
+}
+
+impl<K: Hash + Eq, V: Clone, C: Clock> TtlCache<K, V, C> {
+    pub const fn new(ttl: Duration, clock: C) -> Self {
+        Self { ttl, entries: Mutex::new(HashMap::with_hasher(BuildHasherDefault::new())), clock }
+    }
+
+    /// The value stored for `key`, unless it's older than the ttl.
+    pub fn get(&self, key: &K) -> Option<V> {
+        let now = self.clock.now();
+        let entries = self.entries.lock().unwrap();
+        entries.get(key).filter(|(_, expires)| now < *expires).map(|(value, _)| value.clone())
+    }
+
+    pub fn insert(&self, key: K, value: V) {
+        let now = self.clock.now();
+        let mut entries = self.entries.lock().unwrap();
+        entries.retain(|_, (_, expires)| now < *expires);
+        entries.insert(key, (value, now + self.ttl));
+    }
+
+    /// Number of entries held, including expired ones that haven't been dropped yet.
+    pub fn len(&self) -> usize {
+        self.entries.lock().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn clear(&self) {
+        self.entries.lock().unwrap().clear();
+    }
+}
+
+/// Says which inputs of link `N` produce the same output for a `TtlMemoized` pipeline, and
+/// provides the cache shared by every run. `Value` has to be the output of link `N`, which can't
+/// borrow from its input since it has to outlive the cascade that produced it.
+pub trait TtlMemoize<const N: usize>: Chain<N>
+where
+    Self: InRange<N, <Self as Length>::Len>,
+{
+    type Key: Hash + Eq + 'static;
+    type Value: Clone + 'static;
+    type Clock: Clock + 'static;
+
+    fn key(input: &Self::In<'_>) -> Self::Key;
+
+    fn cache() -> &'static TtlCache<Self::Key, Self::Value, Self::Clock>;
+}
+
+/// Wraps a chain so that link `N` reuses its earlier output for the same key as long as that
+/// output is younger than the cache's ttl, and runs again once it's gone stale. Every other link
+/// behaves as usual.
+pub struct TtlMemoized<T, const N: usize>(T);
+
+impl<T: Length, const N: usize> Length for TtlMemoized<T, N> {
+    type Len = T::Len;
+}
+
+impl<const M: usize, const N: usize, T> Chain<M> for TtlMemoized<T, N>
+where
+    (): Select<M, N>,
+    T: Chain<M> + OrMemoized<M, <() as Select<M, N>>::Is>,
+    Self: InRange<M, Self::Len>,
+{
+    type In<'a> = <T as Chain<M>>::In<'a>;
+    type Out<'a> = <T as Chain<M>>::Out<'a>;
+
+    fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+        <T as OrMemoized<M, <() as Select<M, N>>::Is>>::or_memoized(input)
+    }
+}
+
+/// Implementation detail of `TtlMemoized`, consulting the cache only for the selected link.
+pub trait OrMemoized<const M: usize, Is>: Chain<M>
+where
+    Self: InRange<M, <Self as Length>::Len>,
+{
+    fn or_memoized(input: Self::In<'_>) -> Self::Out<'_>;
+}
+
+impl<const M: usize, T: Chain<M>> OrMemoized<M, No> for T
+where
+    T: InRange<M, <T as Length>::Len>,
+{
+    fn or_memoized(input: Self::In<'_>) -> Self::Out<'_> {
+        <T as Chain<M>>::chain(input)
+    }
+}
+
+impl<const M: usize, T: TtlMemoize<M>> OrMemoized<M, Yes> for T
+where
+    T: InRange<M, <T as Length>::Len>,
+    for<'a> T: Chain<M, Out<'a> = <T as TtlMemoize<M>>::Value>,
+{
+    fn or_memoized(input: Self::In<'_>) -> Self::Out<'_> {
+        let cache = T::cache();
+        let key = T::key(&input);
+        if let Some(value) = cache.get(&key) {
+            return value;
+        }
+        let out = <T as Chain<M>>::chain(input);
+        cache.insert(key, out.clone());
+        out
+    }
+}
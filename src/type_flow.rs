@@ -0,0 +1,71 @@
+use seq_macro::seq;
+
+use crate::{Cascade, Chain, Length, Link, StageInfo, L};
+
+/// Strips the module paths from a type name, so `a::b::Outer<c::Inner>` becomes `Outer<Inner>`.
+pub(crate) fn strip_paths(type_name: &str) -> String {
+    let mut short = String::new();
+    let mut segment = String::new();
+    for c in type_name.chars() {
+        if c.is_alphanumeric() || c == '_' || c == ':' {
+            segment.push(c);
+        } else {
+            short.push_str(segment.rsplit("::").next().unwrap_or_default());
+            segment.clear();
+            short.push(c);
+        }
+    }
+    short.push_str(segment.rsplit("::").next().unwrap_or_default());
+    short
+}
+
+/// Collects the `StageInfo` of every link from 0..N.
+pub trait StageInfoLink<const N: usize>: Link<N> {
+    fn stage_info_link() -> Vec<StageInfo>;
+}
+
+impl<T: Chain<0>> StageInfoLink<1> for T {
+    fn stage_info_link() -> Vec<StageInfo> {
+        vec![StageInfo::of::<0, T>()]
+    }
+}
+
+seq!(N in 2..=32 {
+    impl<T> StageInfoLink<N> for T
+    where
+        T: Chain<0>,
+        for<'a> T: StageInfoLink<{N - 1}, In<'a> = <T as Chain<0>>::In<'a>>,
+        for<'a> T: Chain<{N - 1}, In<'a> = <T as Link<{N - 1}>>::Out<'a>>,
+    {
+        fn stage_info_link() -> Vec<StageInfo> {
+            let mut stages = <T as StageInfoLink<{N - 1}>>::stage_info_link();
+            stages.push(StageInfo::of::<{N - 1}, T>());
+            stages
+        }
+    }
+});
+
+/// Describes the types flowing through a cascade without running it, e.g. for generated docs.
+/// `type_name` isn't usable in const contexts yet, so these are plain functions.
+pub trait TypeFlow: Cascade {
+    fn stage_infos() -> Vec<StageInfo>;
+
+    /// The input type followed by every link's output type, without module paths, like
+    /// `f32 -> i32 -> u32 -> String`.
+    fn type_flow() -> String {
+        let stages = Self::stage_infos();
+        let first = stages.first().map(|stage| stage.input);
+        first
+            .into_iter()
+            .chain(stages.iter().map(|stage| stage.output))
+            .map(strip_paths)
+            .collect::<Vec<_>>()
+            .join(" -> ")
+    }
+}
+
+impl<const N: usize, T: StageInfoLink<N> + Length<Len = L<N>>> TypeFlow for T {
+    fn stage_infos() -> Vec<StageInfo> {
+        <T as StageInfoLink<N>>::stage_info_link()
+    }
+}
@@ -0,0 +1,67 @@
+use std::marker::PhantomData;
+
+use crate::{Chain, Length, L};
+
+/// One stage of a `TypedPipeline`, defined on a marker type of its own rather than as one of
+/// many `Chain<N>` impls on a shared type.
+pub trait StageDef {
+    type In;
+    type Out;
+
+    fn run(input: Self::In) -> Self::Out;
+}
+
+/// Pipeline defined by a tuple of `StageDef` markers, `TypedPipeline<(A, B, C)>` being `A` then
+/// `B` then `C`. `Length` and every `Chain<N>` come from the tuple, so there are no indexes to
+/// keep track of, and stages that don't fit together simply don't cascade:
+/// ```compile_fail
+/// use chain_link::*;
+///
+/// struct Len;
+/// impl StageDef for Len {
+///     type In = String;
+///     type Out = usize;
+///     fn run(input: String) -> usize {
+///         input.len()
+///     }
+/// }
+/// TypedPipeline::<(Len, Len)>::cascade("oops".to_owned());
+/// ```
+///
+/// Tuples of up to 12 stages are supported.
+pub struct TypedPipeline<S>(PhantomData<S>);
+
+macro_rules! typed_pipeline {
+    ($len:literal: $($index:tt $stage:ident),+) => {
+        impl<$($stage: StageDef),+> Length for TypedPipeline<($($stage,)+)> {
+            type Len = L<$len>;
+        }
+        typed_pipeline!(@chains [$($stage),+] $($index $stage),+);
+    };
+    (@chains $all:tt $($index:tt $stage:ident),+) => {
+        $(typed_pipeline!(@chain $all $index $stage);)+
+    };
+    (@chain [$($all:ident),+] $index:tt $stage:ident) => {
+        impl<$($all: StageDef),+> Chain<$index> for TypedPipeline<($($all,)+)> {
+            type In<'a> = $stage::In;
+            type Out<'a> = $stage::Out;
+
+            fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+                $stage::run(input)
+            }
+        }
+    };
+}
+
+typed_pipeline!(1: 0 S0);
+typed_pipeline!(2: 0 S0, 1 S1);
+typed_pipeline!(3: 0 S0, 1 S1, 2 S2);
+typed_pipeline!(4: 0 S0, 1 S1, 2 S2, 3 S3);
+typed_pipeline!(5: 0 S0, 1 S1, 2 S2, 3 S3, 4 S4);
+typed_pipeline!(6: 0 S0, 1 S1, 2 S2, 3 S3, 4 S4, 5 S5);
+typed_pipeline!(7: 0 S0, 1 S1, 2 S2, 3 S3, 4 S4, 5 S5, 6 S6);
+typed_pipeline!(8: 0 S0, 1 S1, 2 S2, 3 S3, 4 S4, 5 S5, 6 S6, 7 S7);
+typed_pipeline!(9: 0 S0, 1 S1, 2 S2, 3 S3, 4 S4, 5 S5, 6 S6, 7 S7, 8 S8);
+typed_pipeline!(10: 0 S0, 1 S1, 2 S2, 3 S3, 4 S4, 5 S5, 6 S6, 7 S7, 8 S8, 9 S9);
+typed_pipeline!(11: 0 S0, 1 S1, 2 S2, 3 S3, 4 S4, 5 S5, 6 S6, 7 S7, 8 S8, 9 S9, 10 S10);
+typed_pipeline!(12: 0 S0, 1 S1, 2 S2, 3 S3, 4 S4, 5 S5, 6 S6, 7 S7, 8 S8, 9 S9, 10 S10, 11 S11);
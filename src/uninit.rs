@@ -0,0 +1,88 @@
+use std::mem::MaybeUninit;
+use std::ptr;
+
+use crate::Cascade;
+
+/// Write-only view of an uninitialized buffer that only lets values be appended, so it always
+/// knows exactly which prefix of the buffer is initialized. If it's dropped before being filled
+/// (e.g. the filling stage panics), the values written so far are dropped rather than leaked.
+pub struct OutBuf<'b, T> {
+    buf: &'b mut [MaybeUninit<T>],
+    filled: usize,
+}
+
+impl<'b, T> OutBuf<'b, T> {
+    pub fn new(buf: &'b mut [MaybeUninit<T>]) -> Self {
+        Self { buf, filled: 0 }
+    }
+
+    /// Panics if the buffer is already full.
+    pub fn push(&mut self, value: T) {
+        assert!(!self.is_full(), "OutBuf overflow: capacity is {}", self.buf.len());
+        self.buf[self.filled].write(value);
+        self.filled += 1;
+    }
+
+    pub fn filled(&self) -> usize {
+        self.filled
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.buf.len()
+    }
+
+    pub fn remaining(&self) -> usize {
+        self.buf.len() - self.filled
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.filled == self.buf.len()
+    }
+
+    /// Hands back the buffer as initialized. Panics unless every slot was written.
+    pub fn finish(mut self) -> &'b mut [T] {
+        assert!(
+            self.is_full(),
+            "OutBuf only initialized {} of {} elements",
+            self.filled,
+            self.buf.len(),
+        );
+        // the values are now owned by the returned slice, so our `Drop` mustn't touch them
+        self.filled = 0;
+        let buf = std::mem::take(&mut self.buf);
+        // SAFETY: every element was written by `push`, `MaybeUninit<T>` has the same layout as `T`
+        unsafe { &mut *(buf as *mut [MaybeUninit<T>] as *mut [T]) }
+    }
+}
+
+impl<T> Drop for OutBuf<'_, T> {
+    fn drop(&mut self) {
+        let init = &mut self.buf[..self.filled];
+        // SAFETY: exactly the first `filled` elements were written by `push` and not handed out
+        unsafe { ptr::drop_in_place(init as *mut [MaybeUninit<T>] as *mut [T]) }
+    }
+}
+
+/// Final stage of a cascade that writes its output straight into a caller-provided buffer,
+/// which is useful for large outputs where zero-initializing the buffer first isn't free.
+pub trait Fill: Cascade {
+    type Elem;
+
+    /// Must push exactly `buf.capacity()` elements.
+    fn fill(output: Self::Out<'_>, buf: &mut OutBuf<'_, Self::Elem>);
+}
+
+pub trait CascadeInto: Fill {
+    /// Cascades, then fills `buf` from the output. Panics if `fill` leaves the buffer partially
+    /// initialized, in which case whatever it did write gets dropped.
+    fn cascade_into<'b>(
+        input: Self::In<'_>,
+        buf: &'b mut [MaybeUninit<Self::Elem>],
+    ) -> &'b mut [Self::Elem] {
+        let mut out = OutBuf::new(buf);
+        Self::fill(Self::cascade(input), &mut out);
+        out.finish()
+    }
+}
+
+impl<T: Fill> CascadeInto for T {}
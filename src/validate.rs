@@ -0,0 +1,103 @@
+use std::fmt;
+
+use seq_macro::seq;
+
+use crate::sampled::splitmix64;
+use crate::{Homogeneous, InRange, Length, L};
+
+/// One of several independent checks on the same `Item`, which shouldn't care what order they
+/// run in. Every failing check is reported, not just the first.
+pub trait Validate<const N: usize>: Homogeneous
+where
+    Self: InRange<N, <Self as Length>::Len>,
+{
+    /// Names order the checks when they run canonically, and identify the ones that failed.
+    const NAME: &'static str;
+
+    fn validate(item: &Self::Item) -> Result<(), String>;
+}
+
+/// A check that failed, and why.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct ValidationError {
+    pub validator: &'static str,
+    pub message: String,
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.validator, self.message)
+    }
+}
+
+type Check<T> = (&'static str, fn(&T) -> Result<(), String>);
+
+/// Collects the checks from 0..N.
+pub trait ValidateLink<const N: usize>: Homogeneous {
+    fn validate_link() -> Vec<Check<Self::Item>>;
+}
+
+impl<T: Validate<0>> ValidateLink<1> for T {
+    fn validate_link() -> Vec<Check<Self::Item>> {
+        vec![(<T as Validate<0>>::NAME, <T as Validate<0>>::validate)]
+    }
+}
+
+seq!(N in 2..=32 {
+    impl<T> ValidateLink<N> for T
+    where
+        T: ValidateLink<{N - 1}> + Validate<{N - 1}>,
+    {
+        fn validate_link() -> Vec<Check<Self::Item>> {
+            let mut checks = <T as ValidateLink<{N - 1}>>::validate_link();
+            checks.push((<T as Validate<{N - 1}>>::NAME, <T as Validate<{N - 1}>>::validate));
+            checks
+        }
+    }
+});
+
+fn run<T>(checks: &[Check<T>], item: &T) -> Result<(), Vec<ValidationError>> {
+    let mut errors: Vec<_> = checks
+        .iter()
+        .filter_map(|(validator, check)| {
+            check(item).err().map(|message| ValidationError { validator, message })
+        })
+        .collect();
+    errors.sort();
+    match errors.is_empty() {
+        true => Ok(()),
+        false => Err(errors),
+    }
+}
+
+/// Implemented for anything where every link in 0..Length is a `Validate<N>`.
+pub trait ValidateAll: Homogeneous {
+    /// Every check, sorted by name.
+    fn checks() -> Vec<Check<Self::Item>>;
+
+    /// Runs every check in order of name, failing with all the errors, also sorted by name.
+    fn validate_all(item: &Self::Item) -> Result<(), Vec<ValidationError>> {
+        run(&Self::checks(), item)
+    }
+
+    /// Same as `validate_all`, but runs the checks in an order shuffled by `seed`. For tests
+    /// making sure no check depends on another having run first, since whatever order they ran
+    /// in, the result should be the same as `validate_all`'s.
+    fn validate_all_shuffled(item: &Self::Item, seed: u64) -> Result<(), Vec<ValidationError>> {
+        let mut checks = Self::checks();
+        let mut state = seed;
+        for i in (1..checks.len()).rev() {
+            let j = (splitmix64(&mut state) % (i as u64 + 1)) as usize;
+            checks.swap(i, j);
+        }
+        run(&checks, item)
+    }
+}
+
+impl<const N: usize, T: ValidateLink<N> + Length<Len = L<N>>> ValidateAll for T {
+    fn checks() -> Vec<Check<Self::Item>> {
+        let mut checks = <T as ValidateLink<N>>::validate_link();
+        checks.sort_by_key(|(name, _)| *name);
+        checks
+    }
+}
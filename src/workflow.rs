@@ -0,0 +1,89 @@
+use std::error::Error;
+use std::fmt;
+
+use serde_yaml::Value;
+
+use crate::{DynCascade, Param, StageError, StageRegistry};
+
+/// Why a YAML workflow couldn't be turned into a pipeline.
+#[derive(Debug)]
+pub enum WorkflowError {
+    Yaml(serde_yaml::Error),
+    /// Valid YAML that isn't shaped like a workflow.
+    Format(String),
+    Stage(StageError),
+}
+
+impl fmt::Display for WorkflowError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WorkflowError::Yaml(error) => write!(f, "invalid workflow yaml: {error}"),
+            WorkflowError::Format(reason) => write!(f, "invalid workflow: {reason}"),
+            WorkflowError::Stage(error) => error.fmt(f),
+        }
+    }
+}
+
+impl Error for WorkflowError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            WorkflowError::Yaml(error) => Some(error),
+            WorkflowError::Stage(error) => Some(error),
+            WorkflowError::Format(_) => None,
+        }
+    }
+}
+
+impl<T: 'static> StageRegistry<T> {
+    /// Parses a workflow and makes each of its stages, without running anything. A workflow lists
+    /// the stages to run in order:
+    ///
+    /// ```yaml
+    /// stages:
+    ///   - name: trim
+    ///   - name: repeat
+    ///     params:
+    ///       times: 2
+    /// ```
+    ///
+    /// Stages without `params` get `Param::Null`.
+    pub fn workflow(&self, yaml: &str) -> Result<DynCascade<T>, WorkflowError> {
+        let workflow: Value = serde_yaml::from_str(yaml).map_err(WorkflowError::Yaml)?;
+        let stages = workflow
+            .get("stages")
+            .and_then(Value::as_sequence)
+            .ok_or_else(|| WorkflowError::Format("expected a list of `stages`".to_owned()))?;
+
+        let mut pipeline = DynCascade::new();
+        for (index, stage) in stages.iter().enumerate() {
+            let name = stage
+                .get("name")
+                .and_then(Value::as_str)
+                .ok_or_else(|| WorkflowError::Format(format!("stage {index} has no `name`")))?;
+            let params = param(stage.get("params").unwrap_or(&Value::Null))?;
+            pipeline.push(self.make(name, &params).map_err(WorkflowError::Stage)?);
+        }
+        Ok(pipeline)
+    }
+}
+
+fn param(value: &Value) -> Result<Param, WorkflowError> {
+    Ok(match value {
+        Value::Null => Param::Null,
+        Value::Bool(value) => Param::Bool(*value),
+        Value::Number(number) => match number.as_i64() {
+            Some(integer) => Param::Int(integer),
+            None => Param::Float(number.as_f64().unwrap_or(f64::NAN)),
+        },
+        Value::String(value) => Param::String(value.clone()),
+        Value::Sequence(values) => Param::List(values.iter().map(param).collect::<Result<_, _>>()?),
+        Value::Mapping(entries) => {
+            let entries = entries.iter().map(|(key, value)| match key.as_str() {
+                Some(key) => Ok((key.to_owned(), param(value)?)),
+                None => Err(WorkflowError::Format(format!("parameter names have to be strings, not {key:?}"))),
+            });
+            Param::Map(entries.collect::<Result<_, _>>()?)
+        }
+        Value::Tagged(tagged) => param(&tagged.value)?,
+    })
+}
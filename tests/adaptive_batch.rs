@@ -0,0 +1,61 @@
+#[cfg(test)]
+pub mod tests {
+
+    use chain_link::*;
+
+    /// Every batch costs 2ms plus 0.1ms per input on the manual clock, so the largest batch that
+    /// fits the 10ms target is 80. The batcher doubles its way up from 1, then closes in on it.
+    #[test]
+    fn adaptive_batched() {
+        use std::time::Duration;
+
+        static CLOCK: ManualClock = ManualClock::new();
+
+        struct Insert;
+
+        impl Chain<0> for Insert {
+            type In<'a> = Vec<u32>;
+            type Out<'a> = Vec<u32>;
+
+            fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+                CLOCK.advance(Duration::from_micros(2_000 + 100 * input.len() as u64));
+                input.into_iter().map(|row| row * 2).collect()
+            }
+        }
+
+        impl Length for Insert {
+            type Len = L<1>;
+        }
+
+        let mut batcher = AdaptiveBatched::<Insert, _, _>::new(Duration::from_millis(10)).with_clock(&CLOCK);
+        let mut sizes = Vec::new();
+        let mut outputs = Vec::new();
+        for row in 0..2_000 {
+            if let Some(out) = batcher.push(row) {
+                sizes.push(out.len());
+                outputs.extend(out);
+            }
+        }
+        outputs.extend(batcher.flush());
+        assert_eq!(outputs, (0..2_000).map(|row| row * 2).collect::<Vec<_>>());
+        assert_eq!(sizes[..8], [1, 2, 4, 8, 16, 32, 61, 75]);
+        assert!(sizes[9..].iter().all(|&size| size == 79));
+        assert_eq!(batcher.batch_size(), 79);
+
+        let mut capped = AdaptiveBatched::<Insert, _, _>::new(Duration::from_millis(10))
+            .with_limits(4, 16)
+            .with_clock(&CLOCK);
+        assert_eq!(batcher.pending(), 0);
+        assert_eq!(capped.batch_size(), 4);
+        (0..100).for_each(|row| drop(capped.push(row)));
+        assert_eq!(capped.batch_size(), 16);
+
+        // doubling a size this big would overflow, and allocating it would fail
+        let mut huge = AdaptiveBatched::<Insert, _, _>::new(Duration::from_millis(1))
+            .with_limits(usize::MAX - 1, usize::MAX)
+            .with_clock(&CLOCK);
+        assert_eq!(huge.push(1), None);
+        assert_eq!(huge.flush(), [2]);
+        assert_eq!(huge.batch_size(), usize::MAX - 1);
+    }
+}
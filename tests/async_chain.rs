@@ -0,0 +1,826 @@
+#[cfg(test)]
+pub mod tests {
+
+    use std::future::Future;
+    use std::pin::pin;
+    use std::sync::Arc;
+    use std::task::{Context, Poll, Wake, Waker};
+    use std::thread::{self, Thread};
+
+    use chain_link::*;
+
+    /// Minimal executor so the tests don't need to pull in a runtime.
+    struct ThreadWaker(Thread);
+
+    impl Wake for ThreadWaker {
+        fn wake(self: Arc<Self>) {
+            self.0.unpark();
+        }
+    }
+
+    fn block_on<F: Future>(future: F) -> F::Output {
+        let mut future = pin!(future);
+        let waker = Waker::from(Arc::new(ThreadWaker(thread::current())));
+        let mut cx = Context::from_waker(&waker);
+        loop {
+            match future.as_mut().poll(&mut cx) {
+                Poll::Ready(output) => return output,
+                Poll::Pending => thread::park(),
+            }
+        }
+    }
+
+    /// Stays pending for `polls` polls, waking itself each time, to wait on something without
+    /// needing a timer.
+    async fn pending_for(polls: usize) {
+        let mut left = polls;
+        std::future::poll_fn(|cx| match left {
+            0 => Poll::Ready(()),
+            _ => {
+                left -= 1;
+                cx.waker().wake_by_ref();
+                Poll::Pending
+            }
+        })
+        .await
+    }
+
+    /// Parses, then checks the parsed number is even, awaiting in between.
+    struct Parse;
+
+    impl AsyncChain<0> for Parse {
+        type In<'a> = &'a str;
+        type Out<'a> = Result<i64, String>;
+
+        async fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+            input.trim().parse().map_err(|_| format!("not a number: {input}"))
+        }
+    }
+
+    impl AsyncChain<1> for Parse {
+        type In<'a> = Result<i64, String>;
+        type Out<'a> = Result<i64, String>;
+
+        async fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+            input.and_then(|n| match n % 2 {
+                0 => Ok(n / 2),
+                _ => Err(format!("not even: {n}")),
+            })
+        }
+    }
+
+    impl Length for Parse {
+        type Len = L<2>;
+    }
+
+    #[test]
+    fn async_cascade() {
+        assert_eq!(block_on(Parse::async_cascade(" 42")), Ok(21));
+        assert_eq!(block_on(Parse::async_cascade("7")), Err("not even: 7".to_owned()));
+    }
+
+    /// Stages registered at runtime by two plugins, one of which has to wait on something first.
+    #[test]
+    fn dyn_async_cascade() {
+        let mut pipeline = DynAsyncCascade::new().then(|text: String| async move { text.trim().to_owned() });
+        let suffix = "!".to_owned();
+        pipeline.push(move |text: String| {
+            let suffix = suffix.clone();
+            async move {
+                pending_for(1).await;
+                format!("{text}{suffix}")
+            }
+        });
+
+        assert_eq!(pipeline.len(), 2);
+        assert_eq!(block_on(pipeline.cascade("  hello ".to_owned())), "hello!");
+        assert_eq!(block_on(DynAsyncCascade::new().cascade(1)), 1);
+    }
+
+    /// Link 1 is throttled to 10 calls a second, so back to back cascades see it run 100ms apart
+    /// on the manual clock, while link 0 runs whenever it's called.
+    #[test]
+    fn rate_limited_link() {
+        use std::sync::Mutex;
+        use std::time::Duration;
+
+        static CLOCK: ManualClock = ManualClock::new();
+        static LIMITER: RateLimiter<&ManualClock> = RateLimiter::new(10, &CLOCK);
+        static CALLS: Mutex<Vec<(usize, Duration)>> = Mutex::new(Vec::new());
+
+        struct Api;
+
+        impl RateLimit for Api {
+            type Clock = &'static ManualClock;
+
+            fn limiter() -> &'static RateLimiter<Self::Clock> {
+                &LIMITER
+            }
+        }
+
+        impl AsyncChain<0> for Api {
+            type In<'a> = u32;
+            type Out<'a> = u32;
+
+            async fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+                CALLS.lock().unwrap().push((0, CLOCK.now()));
+                input + 1
+            }
+        }
+
+        impl AsyncChain<1> for Api {
+            type In<'a> = u32;
+            type Out<'a> = u32;
+
+            async fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+                CALLS.lock().unwrap().push((1, CLOCK.now()));
+                input * 2
+            }
+        }
+
+        impl Length for Api {
+            type Len = L<2>;
+        }
+
+        for n in 0..4 {
+            assert_eq!(block_on(RateLimited::<Api, 1>::async_cascade(n)), (n + 1) * 2);
+        }
+        let calls = CALLS.lock().unwrap().clone();
+        let limited: Vec<_> = calls.iter().filter(|(link, _)| *link == 1).map(|(_, at)| *at).collect();
+        assert_eq!(limited, [0, 100, 200, 300].map(Duration::from_millis));
+        let unlimited: Vec<_> = calls.iter().filter(|(link, _)| *link == 0).map(|(_, at)| *at).collect();
+        assert_eq!(unlimited, [0, 0, 100, 200].map(Duration::from_millis));
+
+        CLOCK.advance(Duration::from_secs(1));
+        let before = CLOCK.now();
+        block_on(RateLimited::<Api, 1>::async_cascade(0));
+        assert_eq!(CLOCK.now(), before);
+    }
+
+    #[test]
+    #[should_panic(expected = "at least one acquisition a second")]
+    fn rate_limiter_without_a_rate() {
+        RateLimiter::new(0, SystemClock);
+    }
+
+    /// Parsing and formatting are cheap and sync, while the lookup in the middle actually has to
+    /// wait on something, simulated here by yielding once.
+    #[test]
+    fn maybe_async_stage_kinds() {
+
+        struct YieldOnce(bool);
+
+        impl Future for YieldOnce {
+            type Output = ();
+
+            fn poll(mut self: std::pin::Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+                if self.0 {
+                    return Poll::Ready(());
+                }
+                self.0 = true;
+                cx.waker().wake_by_ref();
+                Poll::Pending
+            }
+        }
+
+        struct Lookup;
+
+        impl AsyncChain<0> for Lookup {
+            type In<'a> = &'a str;
+            type Out<'a> = u32;
+
+            async fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+                input.parse().unwrap()
+            }
+        }
+
+        impl AsyncChain<1> for Lookup {
+            type In<'a> = u32;
+            type Out<'a> = &'static str;
+
+            async fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+                YieldOnce(false).await;
+                ["zero", "one", "two"][input as usize]
+            }
+        }
+
+        impl AsyncChain<2> for Lookup {
+            type In<'a> = &'static str;
+            type Out<'a> = String;
+
+            async fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+                format!("<{input}>")
+            }
+        }
+
+        impl MaybeAsyncChain<0> for Lookup {
+            const KIND: StageKind = StageKind::Sync;
+        }
+
+        impl MaybeAsyncChain<1> for Lookup {
+            const KIND: StageKind = StageKind::Async;
+        }
+
+        impl MaybeAsyncChain<2> for Lookup {
+            const KIND: StageKind = StageKind::Sync;
+        }
+
+        impl Length for Lookup {
+            type Len = L<3>;
+        }
+
+        let kinds: [StageKind; 3] = Lookup::stage_kinds();
+        assert_eq!(kinds, [StageKind::Sync, StageKind::Async, StageKind::Sync]);
+        assert_eq!(block_on(Lookup::async_cascade("2")), "<two>");
+    }
+
+    /// A long running workflow saved after its first link, then picked up again from the saved
+    /// bytes as if by a new process, without redoing the link that already ran.
+    #[test]
+    fn async_checkpoint_resume() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        static RESERVED: AtomicUsize = AtomicUsize::new(0);
+
+        struct YieldOnce(bool);
+
+        impl Future for YieldOnce {
+            type Output = ();
+
+            fn poll(mut self: std::pin::Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+                if self.0 {
+                    return Poll::Ready(());
+                }
+                self.0 = true;
+                cx.waker().wake_by_ref();
+                Poll::Pending
+            }
+        }
+
+        struct Order;
+
+        impl AsyncChain<0> for Order {
+            type In<'a> = String;
+            type Out<'a> = (String, u32);
+
+            async fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+                YieldOnce(false).await;
+                RESERVED.fetch_add(1, Ordering::Relaxed);
+                (input, 3)
+            }
+        }
+
+        impl AsyncChain<1> for Order {
+            type In<'a> = (String, u32);
+            type Out<'a> = (String, u32);
+
+            async fn chain((item, count): Self::In<'_>) -> Self::Out<'_> {
+                YieldOnce(false).await;
+                (item, count * 250)
+            }
+        }
+
+        impl AsyncChain<2> for Order {
+            type In<'a> = (String, u32);
+            type Out<'a> = String;
+
+            async fn chain((item, cents): Self::In<'_>) -> Self::Out<'_> {
+                format!("{item}: {}.{:02}", cents / 100, cents % 100)
+            }
+        }
+
+        impl Length for Order {
+            type Len = L<3>;
+        }
+
+        let mut workflow = Order::into_async_checkpoint("widgets".to_owned());
+        assert_eq!(block_on(workflow.resume()), Ok(Some(0)));
+        let saved = workflow.to_bytes();
+        drop(workflow);
+
+        let restored = AsyncCheckpoint::<Order>::from_bytes(&saved).unwrap();
+        assert_eq!(restored.completed(), 1);
+        assert!(!restored.is_complete());
+        assert_eq!(block_on(restored.finish()), Ok("widgets: 7.50".to_owned()));
+        assert_eq!(RESERVED.load(Ordering::Relaxed), 1);
+
+        let finished = Order::into_async_checkpoint("bolts".to_owned());
+        assert_eq!(block_on(finished.finish()), Ok("bolts: 7.50".to_owned()));
+        assert!(AsyncCheckpoint::<Order>::from_bytes(&saved[..saved.len() - 1]).is_err());
+        assert!(AsyncCheckpoint::<Order>::from_bytes(&(4usize, Vec::<u8>::new()).to_bytes()).is_err());
+    }
+
+    /// Keystrokes arrive 10ms apart, faster than the 50ms interval, so only the final query gets
+    /// searched, 50ms after it was typed.
+    #[test]
+    fn debounce_burst() {
+        use std::sync::Mutex;
+        use std::time::Duration;
+
+        static RUNS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+        struct Search;
+
+        impl AsyncChain<0> for Search {
+            type In<'a> = &'a str;
+            type Out<'a> = usize;
+
+            async fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+                RUNS.lock().unwrap().push(input.to_owned());
+                input.len()
+            }
+        }
+
+        impl Length for Search {
+            type Len = L<1>;
+        }
+
+        let clock = ManualClock::new();
+        let mut debounce = Debounce::<Search, _>::new(Duration::from_millis(50), &clock);
+        for query in ["r", "ru", "rus", "rust"] {
+            debounce.push(query);
+            clock.advance(Duration::from_millis(10));
+            assert!(!debounce.is_settled());
+        }
+
+        assert_eq!(block_on(debounce.settled()), Some(4));
+        assert_eq!(clock.now(), Duration::from_millis(80));
+        assert_eq!(*RUNS.lock().unwrap(), ["rust"]);
+        assert!(!debounce.is_pending());
+        assert_eq!(block_on(debounce.settled()), None);
+
+        debounce.push("go");
+        clock.advance(Duration::from_millis(50));
+        assert!(debounce.is_settled());
+        assert_eq!(block_on(debounce.settled()), Some(2));
+        assert_eq!(clock.now(), Duration::from_millis(130));
+    }
+
+    /// The upstream fails for a while, tripping the breaker after 2 failures out of the last 3
+    /// calls. While open, link 1 isn't called at all, and once the 1s cooldown passes a single
+    /// successful trial call closes it again.
+    #[test]
+    fn circuit_breaker() {
+        use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+        use std::time::Duration;
+
+        static CLOCK: ManualClock = ManualClock::new();
+        static BREAKER: Breaker<&ManualClock> = Breaker::new(2, 3, Duration::from_secs(1), &CLOCK);
+        static DOWN: AtomicBool = AtomicBool::new(true);
+        static CALLS: AtomicUsize = AtomicUsize::new(0);
+
+        #[derive(Debug, PartialEq)]
+        enum FetchError {
+            Down,
+            Open,
+        }
+
+        impl From<BreakerOpen> for FetchError {
+            fn from(_: BreakerOpen) -> Self {
+                FetchError::Open
+            }
+        }
+
+        struct Fetch;
+
+        impl CircuitBreak for Fetch {
+            type Clock = &'static ManualClock;
+
+            fn breaker() -> &'static Breaker<Self::Clock> {
+                &BREAKER
+            }
+        }
+
+        impl AsyncChain<0> for Fetch {
+            type In<'a> = u32;
+            type Out<'a> = String;
+
+            async fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+                format!("/items/{input}")
+            }
+        }
+
+        impl AsyncChain<1> for Fetch {
+            type In<'a> = String;
+            type Out<'a> = Result<usize, FetchError>;
+
+            async fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+                CALLS.fetch_add(1, Ordering::SeqCst);
+                match DOWN.load(Ordering::SeqCst) {
+                    true => Err(FetchError::Down),
+                    false => Ok(input.len()),
+                }
+            }
+        }
+
+        impl Length for Fetch {
+            type Len = L<2>;
+        }
+
+        type Guarded = CircuitBreaker<Fetch, 1>;
+
+        assert_eq!(block_on(Guarded::async_cascade(1)), Err(FetchError::Down));
+        assert!(!BREAKER.is_open());
+        assert_eq!(block_on(Guarded::async_cascade(2)), Err(FetchError::Down));
+        assert!(BREAKER.is_open());
+
+        DOWN.store(false, Ordering::SeqCst);
+        assert_eq!(block_on(Guarded::async_cascade(3)), Err(FetchError::Open));
+        assert_eq!(CALLS.load(Ordering::SeqCst), 2);
+
+        CLOCK.advance(Duration::from_secs(1));
+        assert!(!BREAKER.is_open());
+        assert_eq!(block_on(Guarded::async_cascade(4)), Ok(8));
+        assert_eq!(block_on(Guarded::async_cascade(5)), Ok(8));
+        assert_eq!(CALLS.load(Ordering::SeqCst), 4);
+
+        // a call that started before the breaker opened can't close it, and after the cooldown
+        // only one caller gets to be the trial
+        static SHARED: Breaker<&ManualClock> = Breaker::new(1, 1, Duration::from_secs(1), &CLOCK);
+        let early = SHARED.admit().unwrap();
+        SHARED.admit().unwrap().record(true);
+        early.record(false);
+        assert!(SHARED.is_open());
+        CLOCK.advance(Duration::from_secs(1));
+        let trial = SHARED.admit().unwrap();
+        assert!(trial.is_trial());
+        assert!(SHARED.admit().is_none());
+        drop(trial);
+        SHARED.admit().unwrap().record(false);
+        assert!(!SHARED.is_open());
+
+        assert!(std::panic::catch_unwind(|| Breaker::new(0, 3, Duration::ZERO, &CLOCK)).is_err());
+        assert!(std::panic::catch_unwind(|| Breaker::new(4, 3, Duration::ZERO, &CLOCK)).is_err());
+    }
+
+    /// The first quote arrives in time and gets cached. The second never arrives, so after the
+    /// 200ms timeout the dashboard shows the cached quote instead of hanging.
+    #[test]
+    fn timeout_with_last_good() {
+        use std::future::pending;
+        use std::sync::atomic::{AtomicBool, Ordering};
+        use std::time::Duration;
+
+        static CLOCK: ManualClock = ManualClock::new();
+        static CACHE: LastGoodCache<u32, &ManualClock> = LastGoodCache::new(Duration::from_millis(200), &CLOCK);
+        static STALLED: AtomicBool = AtomicBool::new(false);
+
+        struct Dashboard;
+
+        impl AsyncChain<0> for Dashboard {
+            type In<'a> = &'a str;
+            type Out<'a> = u32;
+
+            async fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+                if STALLED.load(Ordering::SeqCst) {
+                    pending::<()>().await;
+                }
+                input.len() as u32 * 100
+            }
+        }
+
+        impl AsyncChain<1> for Dashboard {
+            type In<'a> = u32;
+            type Out<'a> = String;
+
+            async fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+                format!("${input}")
+            }
+        }
+
+        impl LastGood<0> for Dashboard {
+            type Value = u32;
+            type Clock = &'static ManualClock;
+
+            fn last_good() -> &'static LastGoodCache<u32, Self::Clock> {
+                &CACHE
+            }
+        }
+
+        impl Length for Dashboard {
+            type Len = L<2>;
+        }
+
+        type Resilient = TimeoutWithLastGood<Dashboard, 0>;
+
+        assert_eq!(block_on(Resilient::async_cascade("ACME")), "$400");
+        assert_eq!(CACHE.last(), Some(400));
+        assert_eq!(CLOCK.now(), Duration::ZERO);
+
+        STALLED.store(true, Ordering::SeqCst);
+        assert_eq!(block_on(Resilient::async_cascade("ACME CORP")), "$400");
+        assert_eq!(CLOCK.now(), Duration::from_millis(200));
+    }
+
+    /// Log lines get formatted and written in batches of 3, or whatever has piled up after
+    /// 100ms.
+    #[test]
+    fn batch_sink() {
+        use std::time::Duration;
+
+        struct Format;
+
+        impl Chain<0> for Format {
+            type In<'a> = (u32, &'a str);
+            type Out<'a> = String;
+
+            fn chain((level, message): Self::In<'_>) -> Self::Out<'_> {
+                format!("[{level}] {message}")
+            }
+        }
+
+        impl Length for Format {
+            type Len = L<1>;
+        }
+
+        let clock = ManualClock::new();
+        let mut sink = BatchSink::<Format, _>::new(3, Duration::from_millis(100), &clock);
+
+        // size trigger
+        assert_eq!(sink.push((1, "a")), None);
+        assert_eq!(sink.push((2, "b")), None);
+        assert_eq!(sink.push((1, "c")), Some(vec!["[1] a".to_owned(), "[2] b".to_owned(), "[1] c".to_owned()]));
+        assert!(sink.is_empty());
+
+        // time trigger, checked on the next push
+        assert_eq!(sink.push((3, "d")), None);
+        clock.advance(Duration::from_millis(60));
+        assert_eq!(sink.tick(), None);
+        clock.advance(Duration::from_millis(40));
+        assert_eq!(sink.push((1, "e")), Some(vec!["[3] d".to_owned(), "[1] e".to_owned()]));
+
+        // time trigger, waited on
+        assert_eq!(sink.push((2, "f")), None);
+        clock.advance(Duration::from_millis(30));
+        assert_eq!(block_on(sink.flushed()), Some(vec!["[2] f".to_owned()]));
+        assert_eq!(clock.now(), Duration::from_millis(200));
+        assert_eq!(block_on(sink.flushed()), None);
+    }
+
+    /// Link 1 looks a product up in the cache and the database at the same time. The database
+    /// takes longer, so it's what the critical path goes through, and link 1 dominates the run.
+    #[test]
+    fn critical_path() {
+        struct Product;
+
+        impl AsyncChain<0> for Product {
+            type In<'a> = &'a str;
+            type Out<'a> = u32;
+
+            async fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+                input.trim_start_matches('#').parse().unwrap()
+            }
+        }
+
+        impl AsyncChain<1> for Product {
+            type In<'a> = u32;
+            type Out<'a> = (Option<String>, String);
+
+            async fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+                let cache = async { None };
+                let database = async {
+                    pending_for(10).await;
+                    format!("product {input}")
+                };
+                fan_out((arm("cache", cache), arm("database", database))).await
+            }
+        }
+
+        impl AsyncChain<2> for Product {
+            type In<'a> = (Option<String>, String);
+            type Out<'a> = String;
+
+            async fn chain((cached, fetched): Self::In<'_>) -> Self::Out<'_> {
+                cached.unwrap_or(fetched)
+            }
+        }
+
+        impl Length for Product {
+            type Len = L<3>;
+        }
+
+        let (out, path) = block_on(Product::cascade_with_critical_path("#7"));
+        assert_eq!(out, "product 7");
+        assert_eq!(path.steps.iter().map(|step| step.stage).collect::<Vec<_>>(), [0, 1, 2]);
+        assert!(path.steps[0].arms.is_empty());
+        assert!(path.steps[2].arms.is_empty());
+
+        let arms = &path.steps[1].arms;
+        assert_eq!(arms.len(), 1);
+        assert_eq!(arms[0].name, "database");
+        assert!(arms[0].elapsed <= path.steps[1].elapsed);
+        assert_eq!(path.dominant().map(|step| step.stage), Some(1));
+        assert!(path.total() >= arms[0].elapsed);
+    }
+
+    /// Every link notes when it started on the manual clock. Running with the same seed twice
+    /// starts the links at the same offsets, while another seed shifts them around.
+    #[test]
+    fn jittered_schedule() {
+        use std::sync::Mutex;
+        use std::time::Duration;
+
+        static CLOCK: ManualClock = ManualClock::new();
+        static STARTS: Mutex<Vec<Duration>> = Mutex::new(Vec::new());
+
+        struct Steps;
+
+        impl AsyncChain<0> for Steps {
+            type In<'a> = u32;
+            type Out<'a> = u32;
+
+            async fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+                STARTS.lock().unwrap().push(CLOCK.now());
+                input + 1
+            }
+        }
+
+        impl AsyncChain<1> for Steps {
+            type In<'a> = u32;
+            type Out<'a> = u32;
+
+            async fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+                STARTS.lock().unwrap().push(CLOCK.now());
+                input * 2
+            }
+        }
+
+        impl AsyncChain<2> for Steps {
+            type In<'a> = u32;
+            type Out<'a> = String;
+
+            async fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+                STARTS.lock().unwrap().push(CLOCK.now());
+                input.to_string()
+            }
+        }
+
+        impl Length for Steps {
+            type Len = L<3>;
+        }
+
+        let schedule = |seed| {
+            let jitter = Jitter::new(seed).with_max(Duration::from_millis(20)).with_clock(&CLOCK);
+            let started = CLOCK.now();
+            STARTS.lock().unwrap().clear();
+            assert_eq!(block_on(Steps::async_cascade_with_jitter(&jitter, 4)), "10");
+            let starts: Vec<_> = STARTS.lock().unwrap().iter().map(|&at| at - started).collect();
+            assert_eq!(CLOCK.now() - started, jitter.delays(3).into_iter().sum());
+            starts
+        };
+
+        let first = schedule(7);
+        assert_eq!(first.len(), 3);
+        assert!(first.iter().all(|&at| at <= Duration::from_millis(60)));
+        assert_eq!(schedule(7), first);
+        assert_ne!(schedule(8), first);
+
+        // on the system clock, only the output can be checked, and Miri won't let the timer thread
+        // that sleeping starts outlive the test
+        #[cfg(not(miri))]
+        assert_eq!(block_on(Steps::async_cascade_jittered(7, 4)), "10");
+    }
+
+    /// Every lookup has to wait a few times before it's done, so they pile up as far as the
+    /// limit of 3 allows, but no further.
+    #[cfg(feature = "futures")]
+    #[test]
+    fn async_cascade_buffered() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        use futures::StreamExt;
+
+        static IN_FLIGHT: AtomicUsize = AtomicUsize::new(0);
+        static MOST_IN_FLIGHT: AtomicUsize = AtomicUsize::new(0);
+
+        /// Pending for the first `0` polls.
+        struct Yield(usize);
+
+        impl Future for Yield {
+            type Output = ();
+
+            fn poll(mut self: std::pin::Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+                if self.0 == 0 {
+                    return Poll::Ready(());
+                }
+                self.0 -= 1;
+                cx.waker().wake_by_ref();
+                Poll::Pending
+            }
+        }
+
+        struct Lookup;
+
+        impl AsyncChain<0> for Lookup {
+            type In<'a> = usize;
+            type Out<'a> = usize;
+
+            async fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+                let in_flight = IN_FLIGHT.fetch_add(1, Ordering::SeqCst) + 1;
+                MOST_IN_FLIGHT.fetch_max(in_flight, Ordering::SeqCst);
+                Yield(3).await;
+                IN_FLIGHT.fetch_sub(1, Ordering::SeqCst);
+                input * 10
+            }
+        }
+
+        impl Length for Lookup {
+            type Len = L<1>;
+        }
+
+        let outputs: Vec<_> = block_on(Lookup::async_cascade_buffered(0..10, 3).collect());
+        assert_eq!(outputs, (0..10).map(|n| n * 10).collect::<Vec<_>>());
+        assert_eq!(MOST_IN_FLIGHT.load(Ordering::SeqCst), 3);
+        assert_eq!(IN_FLIGHT.load(Ordering::SeqCst), 0);
+
+        // with no room at all nothing would ever run
+        assert!(std::panic::catch_unwind(|| Lookup::async_cascade_buffered(0..1, 0)).is_err());
+    }
+
+    #[cfg(feature = "tower")]
+    #[test]
+    fn tower_service() {
+        use tower_service::Service;
+
+        struct Double;
+
+        impl AsyncChain<0> for Double {
+            type In<'a> = String;
+            type Out<'a> = Result<i64, String>;
+
+            async fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+                input.parse().map_err(|_| format!("not a number: {input}"))
+            }
+        }
+
+        impl AsyncChain<1> for Double {
+            type In<'a> = Result<i64, String>;
+            type Out<'a> = Result<i64, String>;
+
+            async fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+                input.map(|n| n * 2)
+            }
+        }
+
+        impl Length for Double {
+            type Len = L<2>;
+        }
+
+        let mut service = CascadeService::<Double>::new();
+        let waker = Waker::from(Arc::new(ThreadWaker(thread::current())));
+        assert!(service.poll_ready(&mut Context::from_waker(&waker)).is_ready());
+        assert_eq!(block_on(service.call("21".to_owned())), Ok(42));
+        assert_eq!(block_on(service.call("x".to_owned())), Err("not a number: x".to_owned()));
+    }
+
+    #[cfg(feature = "axum")]
+    #[test]
+    fn axum_handler() {
+        use axum::body::{to_bytes, Body};
+        use axum::http::{header, Request, StatusCode};
+        use axum::routing::post;
+        use axum::Router;
+        use tower_service::Service;
+
+        /// Averages the numbers in the body.
+        struct Average;
+
+        impl AsyncChain<0> for Average {
+            type In<'a> = Vec<f64>;
+            type Out<'a> = (f64, usize);
+
+            async fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+                (input.iter().sum(), input.len())
+            }
+        }
+
+        impl AsyncChain<1> for Average {
+            type In<'a> = (f64, usize);
+            type Out<'a> = Option<f64>;
+
+            async fn chain((sum, count): Self::In<'_>) -> Self::Out<'_> {
+                (count > 0).then(|| sum / count as f64)
+            }
+        }
+
+        impl Length for Average {
+            type Len = L<2>;
+        }
+
+        let mut router = Router::new().route("/average", post(cascade_handler::<Average>));
+        let mut request = |body: &'static str| {
+            let request = Request::post("/average")
+                .header(header::CONTENT_TYPE, "application/json")
+                .body(Body::from(body))
+                .unwrap();
+            let response = block_on(router.call(request)).unwrap();
+            let status = response.status();
+            let body = block_on(to_bytes(response.into_body(), usize::MAX)).unwrap();
+            (status, String::from_utf8(body.to_vec()).unwrap())
+        };
+
+        assert_eq!(request("[1, 2, 4.5]"), (StatusCode::OK, "2.5".to_owned()));
+        assert_eq!(request("[]"), (StatusCode::OK, "null".to_owned()));
+        assert_eq!(request("\"nope\"").0, StatusCode::UNPROCESSABLE_ENTITY);
+    }
+}
@@ -0,0 +1,191 @@
+#[cfg(test)]
+pub mod tests {
+
+    use chain_link::*;
+
+    struct Parse;
+
+    impl Chain<0> for Parse {
+        type In<'a> = String;
+        type Out<'a> = Vec<i64>;
+
+        fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+            input.split_whitespace().filter_map(|word| word.parse().ok()).collect()
+        }
+    }
+
+    impl Length for Parse {
+        type Len = L<1>;
+    }
+
+    #[test]
+    fn typestate_builder() {
+        let offset = 10;
+        let pipeline = PipelineBuilder::<&str>::new()
+            .stage(str::to_owned)
+            .then::<Parse, _>()
+            .stage(|numbers| numbers.into_iter().sum::<i64>())
+            .stage(move |sum| sum + offset)
+            .stage(|total| format!("total: {total}"))
+            .build();
+        assert_eq!(pipeline.cascade("1 2 x 3"), "total: 16");
+        assert_eq!(pipeline.cascade(""), "total: 10");
+
+        let identity = PipelineBuilder::<u8>::default().build();
+        assert_eq!(identity.cascade(7), 7);
+
+        // without any closures the built pipeline is a `Cascade` of its own
+        fn total<P: CascadeOf<String, Vec<i64>>>(_: &P, input: &str) -> i64 {
+            P::cascade(input.to_owned()).into_iter().sum()
+        }
+        let parse = PipelineBuilder::<String>::new().then::<Parse, _>().build();
+        assert_eq!(total(&parse, "4 x 5"), 9);
+        assert_eq!(parse.cascade("6".to_owned()), [6]);
+    }
+
+    /// A link that counts in `i32` followed by one that totals in `i64`, which only line up once
+    /// the count is widened, and a `String` going into a `Box<str>` link.
+    #[test]
+    fn auto_convert() {
+
+        struct Stats;
+
+        impl Chain<0> for Stats {
+            type In<'a> = &'a str;
+            type Out<'a> = i32;
+
+            fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+                input.split(',').count() as i32
+            }
+        }
+
+        impl Chain<1> for Stats {
+            type In<'a> = i64;
+            type Out<'a> = String;
+
+            fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+                format!("{} items", input * 1_000_000_000)
+            }
+        }
+
+        impl Chain<2> for Stats {
+            type In<'a> = Box<str>;
+            type Out<'a> = usize;
+
+            fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+                input.len()
+            }
+        }
+
+        impl Length for Stats {
+            type Len = L<3>;
+        }
+
+        assert_eq!(AutoConvert::<Stats>::cascade("a,b,c"), "3000000000 items".len());
+    }
+
+    /// The same bytes read three different ways, with the stages either side left alone.
+    #[test]
+    fn stage_variants() {
+
+        #[derive(Clone, Copy, Debug)]
+        enum Decode {
+            Utf8,
+            Latin1,
+            Hex,
+        }
+
+        impl StageVariant for Decode {
+            type In = Vec<u8>;
+            type Out = String;
+
+            const VARIANTS: &'static [Self] = &[Decode::Utf8, Decode::Latin1, Decode::Hex];
+
+            fn run(&self, input: Vec<u8>) -> String {
+                match self {
+                    Decode::Utf8 => String::from_utf8_lossy(&input).into_owned(),
+                    Decode::Latin1 => input.iter().map(|&b| b as char).collect(),
+                    Decode::Hex => input.iter().map(|b| format!("{b:02x}")).collect(),
+                }
+            }
+        }
+
+        let outputs: Vec<String> = Decode::VARIANTS
+            .iter()
+            .map(|&decode| {
+                let pipeline = PipelineBuilder::<&[u8]>::new()
+                    .stage(<[u8]>::to_vec)
+                    .variant(decode)
+                    .stage(|text| format!("[{text}]"))
+                    .build();
+                pipeline.cascade("é".as_bytes())
+            })
+            .collect();
+        assert_eq!(outputs, ["[é]", "[Ã©]", "[c3a9]"]);
+    }
+
+    macro_rules! pipeline {
+        ($name:ident, $run:expr) => {
+            struct $name;
+
+            impl Chain<0> for $name {
+                type In<'a> = String;
+                type Out<'a> = String;
+
+                fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+                    $run(input)
+                }
+            }
+
+            impl Length for $name {
+                type Len = L<1>;
+            }
+        };
+    }
+
+    pipeline!(Ping, |_| "pong".to_owned());
+    pipeline!(Shout, |input: String| input["shout:".len()..].to_uppercase());
+    pipeline!(DeadLetter, |input| format!("unhandled: {input}"));
+
+    /// Messages are routed on the part before the colon. Kinds nobody anticipated, and messages
+    /// without a kind at all, fall through to the default pipeline instead of failing.
+    #[test]
+    fn route_or_default() {
+        let kind = |message: &String| message.split_once(':').map(|(kind, _)| kind.to_owned());
+        let router = Router::new(kind).route::<Ping>("ping".to_owned()).route::<Shout>("shout".to_owned());
+        assert_eq!(router.cascade("shout:hi".to_owned()), Ok("HI".to_owned()));
+        assert_eq!(router.cascade("pong:".to_owned()), Err("pong:".to_owned()));
+
+        let router = router.or_default::<DeadLetter>();
+        assert_eq!(router.cascade("ping:".to_owned()), "pong");
+        assert_eq!(router.cascade("shout:hey".to_owned()), "HEY");
+        assert_eq!(router.cascade("pong:".to_owned()), "unhandled: pong:");
+        assert_eq!(router.cascade("no kind".to_owned()), "unhandled: no kind");
+        assert_eq!(router.key(&"ping:".to_owned()), Some("ping".to_owned()));
+        assert_eq!(router.key(&"pong:".to_owned()), None);
+    }
+
+    /// The suite only ever sends pings and unknown messages, so coverage points out that the
+    /// shout route was never exercised.
+    #[test]
+    fn stage_coverage() {
+        let kind = |message: &String| message.split_once(':').map(|(kind, _)| kind.to_owned());
+        let router = Router::new(kind)
+            .route::<Ping>("ping".to_owned())
+            .route::<Shout>("shout".to_owned())
+            .or_default::<DeadLetter>();
+
+        let mut coverage = StageCoverage::new();
+        coverage.expect_routes(&router);
+        coverage.expect_pipeline::<Parse>();
+        for message in ["ping:", "ping:again", "pong:"] {
+            router.cascade_covered(message.to_owned(), &mut coverage);
+        }
+        assert_eq!(coverage.unexercised(), ["Parse[0]", "shout"]);
+
+        Parse::observed_cascade("1 2".to_owned(), &mut coverage.observer::<Parse>());
+        assert_eq!(coverage.unexercised(), ["shout"]);
+        assert_eq!(coverage.hits("ping"), 2);
+        assert_eq!(coverage.hits("default"), 1);
+    }
+}
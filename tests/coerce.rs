@@ -0,0 +1,64 @@
+#[cfg(test)]
+pub mod tests {
+
+    use std::sync::Mutex;
+
+    use chain_link::*;
+
+    static WARNINGS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+    /// Rounds a measurement to a whole number of units.
+    struct Measure;
+
+    impl Chain<0> for Measure {
+        type In<'a> = f32;
+        type Out<'a> = i64;
+
+        fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+            input.round() as i64
+        }
+    }
+
+    impl Chain<1> for Measure {
+        type In<'a> = i64;
+        type Out<'a> = String;
+
+        fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+            format!("{input} units")
+        }
+    }
+
+    impl Length for Measure {
+        type Len = L<2>;
+    }
+
+    impl Lenient for Measure {
+        fn coerced(warning: &str) {
+            WARNINGS.lock().unwrap().push(warning.to_owned());
+        }
+    }
+
+    #[test]
+    fn cascade_lenient() {
+        assert_eq!(Measure::cascade_lenient(" 2.5 "), Ok("3 units".to_owned()));
+        assert_eq!(Measure::cascade_lenient(1.25f32), Ok("1 units".to_owned()));
+        assert!(WARNINGS.lock().unwrap().is_empty());
+
+        // too many digits for an f32, so it's rounded but still goes through
+        assert_eq!(Measure::cascade_lenient("16777217".to_owned()), Ok("16777216 units".to_owned()));
+        assert_eq!(*WARNINGS.lock().unwrap(), ["coerced `16777217` into f32 16777216, losing precision"]);
+
+        assert_eq!(
+            Measure::cascade_lenient("twelve").unwrap_err().to_string(),
+            "can't coerce `twelve` into f32",
+        );
+    }
+
+    #[test]
+    fn coerce_from() {
+        assert_eq!(u8::coerce_from("7"), Ok(Coerced { value: 7, warning: None }));
+        assert_eq!(i32::coerce_from("1e3").map(|c| c.value), Ok(1_000));
+        assert!(i32::coerce_from("3.7").unwrap().warning.is_some());
+        assert_eq!(u8::coerce_from("300").map(|c| c.value), Ok(255));
+    }
+}
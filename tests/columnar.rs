@@ -0,0 +1,66 @@
+#[cfg(test)]
+#[cfg(feature = "arrow")]
+pub mod tests {
+
+    use std::sync::Arc;
+
+    use arrow::array::{ArrayRef, Float64Array, Int64Array, StringArray};
+    use arrow::compute::kernels::substring::substring;
+    use arrow::compute::kernels::{cast, cmp, numeric};
+    use arrow::datatypes::DataType;
+    use arrow::error::ArrowError;
+    use arrow::record_batch::RecordBatch;
+
+    use chain_link::*;
+
+    /// Prices every order line, then keeps the lines worth more than 10, a column at a time.
+    struct Orders;
+
+    impl Chain<0> for Orders {
+        type In<'a> = RecordBatch;
+        type Out<'a> = Result<RecordBatch, ArrowError>;
+
+        fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+            let quantity = cast::cast(input.column_by_name("quantity").unwrap(), &DataType::Float64)?;
+            let total = numeric::mul(input.column_by_name("price").unwrap(), &quantity)?;
+            input.with_column("total", total)
+        }
+    }
+
+    impl Chain<1> for Orders {
+        type In<'a> = Result<RecordBatch, ArrowError>;
+        type Out<'a> = Result<RecordBatch, ArrowError>;
+
+        fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+            input?
+                .filter_rows(|batch| cmp::gt(batch.column_by_name("total").unwrap(), &Float64Array::new_scalar(10.0)))?
+                .map_column("item", |item| substring(item, 0, Some(3)))
+        }
+    }
+
+    impl Length for Orders {
+        type Len = L<2>;
+    }
+
+    #[test]
+    fn record_batch_cascade() {
+        let batch = RecordBatch::try_from_iter([
+            ("item", Arc::new(StringArray::from(vec!["apples", "pears", "plums"])) as ArrayRef),
+            ("price", Arc::new(Float64Array::from(vec![2.5, 4.0, 1.0])) as ArrayRef),
+            ("quantity", Arc::new(Int64Array::from(vec![6, 2, 12])) as ArrayRef),
+        ])
+        .unwrap();
+
+        let actual = Orders::cascade(batch).unwrap();
+        assert_eq!(actual.num_rows(), 2);
+        assert_eq!(actual.num_columns(), 4);
+        let items = actual.column_as::<StringArray>("item").unwrap();
+        assert_eq!(items.iter().flatten().collect::<Vec<_>>(), ["app", "plu"]);
+        let totals = actual.column_as::<Float64Array>("total").unwrap();
+        assert_eq!(totals.values(), &[15.0, 12.0]);
+
+        let error = actual.column_as::<Int64Array>("total").unwrap_err();
+        assert!(error.to_string().contains("column `total` is Float64"));
+        assert!(actual.map_column("missing", |column| Ok(column.clone())).is_err());
+    }
+}
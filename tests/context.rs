@@ -0,0 +1,217 @@
+#[cfg(test)]
+pub mod tests {
+
+    use chain_link::*;
+
+    /// Totals up a list of prices, then formats the total for whichever locale it runs under.
+    struct Invoice;
+
+    impl ContextChain<0> for Invoice {
+        type Context = Locale;
+        type In<'a> = &'a [f64];
+        type Out<'a> = f64;
+
+        fn chain<'a>(_: &Locale, input: Self::In<'a>) -> Self::Out<'a> {
+            input.iter().sum()
+        }
+    }
+
+    impl ContextChain<1> for Invoice {
+        type Context = Locale;
+        type In<'a> = f64;
+        type Out<'a> = String;
+
+        fn chain<'a>(locale: &Locale, input: Self::In<'a>) -> Self::Out<'a> {
+            locale.format(input, 2)
+        }
+    }
+
+    impl Length for Invoice {
+        type Len = L<2>;
+    }
+
+    #[test]
+    fn cascade_with_locale() {
+        let prices = [1_000.5, 233.75, 1_000_000.0];
+        assert_eq!(Invoice::cascade_with_locale(Locale::EN_US, &prices), "1,001,234.25");
+        assert_eq!(Invoice::cascade_with_locale(Locale::DE_DE, &prices), "1.001.234,25");
+        assert_eq!(Invoice::cascade_with(&Locale::default(), &[]), "0.00");
+    }
+
+    #[test]
+    fn locale_format() {
+        assert_eq!(Locale::DE_DE.format(-1234.5, 1), "-1.234,5");
+        assert_eq!(Locale::EN_US.format(999.0, 0), "999");
+        assert_eq!(Locale::EN_US.format(-0.001, 2), "0.00");
+        assert_eq!(Locale::FR_FR.format(12345.0, 0), "12\u{202f}345");
+    }
+
+    /// Squares every number, recording which threads did the work, so the test can check that
+    /// no more of them were used than asked for.
+    /// A transfer only writes to the ledger once the resulting balance has been checked. Holds
+    /// are placed right away, so a rejected transfer has to release its hold in the rollback.
+    #[test]
+    fn cascade_transactional() {
+        use std::sync::atomic::{AtomicI64, Ordering};
+        use std::sync::Mutex;
+
+        static LEDGER: Mutex<Vec<String>> = Mutex::new(Vec::new());
+        static HELD: AtomicI64 = AtomicI64::new(0);
+        const BALANCE: i64 = 100;
+
+        struct Transfer;
+
+        impl ContextChain<0> for Transfer {
+            type Context = Transaction;
+            type In<'a> = &'a str;
+            type Out<'a> = i64;
+
+            fn chain<'a>(transaction: &Transaction, input: Self::In<'a>) -> Self::Out<'a> {
+                let amount: i64 = input.parse().unwrap();
+                transaction.defer(move || LEDGER.lock().unwrap().push(format!("debit {amount}")));
+                amount
+            }
+        }
+
+        impl ContextChain<1> for Transfer {
+            type Context = Transaction;
+            type In<'a> = i64;
+            type Out<'a> = i64;
+
+            fn chain<'a>(transaction: &Transaction, input: Self::In<'a>) -> Self::Out<'a> {
+                HELD.fetch_add(input, Ordering::SeqCst);
+                transaction.defer(move || LEDGER.lock().unwrap().push(format!("credit {input}")));
+                BALANCE - input
+            }
+        }
+
+        impl Length for Transfer {
+            type Len = L<2>;
+        }
+
+        let validate = |balance: &i64| match *balance >= 0 {
+            true => Ok(()),
+            false => Err("insufficient funds"),
+        };
+
+        let rejected = Transfer::cascade_transactional("150", validate, |_| {
+            HELD.fetch_sub(150, Ordering::SeqCst);
+        });
+        assert_eq!(rejected, Err("insufficient funds"));
+        assert!(LEDGER.lock().unwrap().is_empty());
+        assert_eq!(HELD.load(Ordering::SeqCst), 0);
+
+        let accepted = Transfer::cascade_transactional("30", validate, |_| unreachable!());
+        assert_eq!(accepted, Ok(70));
+        assert_eq!(*LEDGER.lock().unwrap(), ["debit 30", "credit 30"]);
+        assert_eq!(HELD.load(Ordering::SeqCst), 30);
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn parallelism() {
+        use std::collections::HashSet;
+        use std::sync::Mutex;
+        use std::thread::ThreadId;
+
+        static THREADS: Mutex<Option<HashSet<ThreadId>>> = Mutex::new(None);
+
+        struct Squares;
+
+        impl ContextChain<0> for Squares {
+            type Context = Parallelism;
+            type In<'a> = u64;
+            type Out<'a> = Vec<u64>;
+
+            fn chain<'a>(_: &Parallelism, input: Self::In<'a>) -> Self::Out<'a> {
+                (0..input).collect()
+            }
+        }
+
+        impl ContextChain<1> for Squares {
+            type Context = Parallelism;
+            type In<'a> = Vec<u64>;
+            type Out<'a> = (Vec<u64>, usize);
+
+            fn chain<'a>(parallelism: &Parallelism, input: Self::In<'a>) -> Self::Out<'a> {
+                let squares = parallelism.map(input, |n| {
+                    THREADS.lock().unwrap().get_or_insert_default().insert(std::thread::current().id());
+                    n * n
+                });
+                (squares, parallelism.install(rayon::current_num_threads))
+            }
+        }
+
+        impl Length for Squares {
+            type Len = L<2>;
+        }
+
+        for threads in [1, 3] {
+            let parallelism = Parallelism::new(threads);
+            for _ in 0..2 {
+                *THREADS.lock().unwrap() = None;
+                let (squares, degree) = Squares::cascade_with_parallelism(&parallelism, 1_000);
+                assert_eq!(squares.len(), 1_000);
+                assert_eq!(squares[999], 998_001);
+                assert_eq!(degree, threads);
+                let used = THREADS.lock().unwrap().take().unwrap().len();
+                assert!((1..=threads).contains(&used), "{used} threads used out of {threads}");
+            }
+        }
+    }
+
+    /// Builds a scratch copy of every word in link 0's allocator to find the longest one. None of
+    /// the copies outlive the cascade.
+    #[cfg(feature = "bumpalo")]
+    #[test]
+    fn bump_allocators() {
+        use std::cell::Cell;
+
+        thread_local! {
+            static USED: Cell<usize> = const { Cell::new(0) };
+        }
+
+        struct Longest;
+
+        impl ContextChain<0> for Longest {
+            type Context = Allocators;
+            type In<'a> = &'a str;
+            type Out<'a> = usize;
+
+            fn chain<'a>(allocators: &Allocators, input: Self::In<'a>) -> Self::Out<'a> {
+                let bump = allocators.bump(0);
+                let words: Vec<&str> =
+                    input.split(' ').map(|word| &*bump.alloc_str(&word.to_lowercase())).collect();
+                USED.set(bump.allocated_bytes());
+                words.iter().map(|word| word.chars().count()).max().unwrap_or(0)
+            }
+        }
+
+        impl ContextChain<1> for Longest {
+            type Context = Allocators;
+            type In<'a> = usize;
+            type Out<'a> = String;
+
+            fn chain<'a>(_: &Allocators, input: Self::In<'a>) -> Self::Out<'a> {
+                format!("longest word has {input} letters")
+            }
+        }
+
+        impl Length for Longest {
+            type Len = L<2>;
+        }
+
+        let mut allocators = Allocators::new(2);
+        let out = Longest::cascade_with_allocators(&mut allocators, "Ein Fluß ÜBERQUERT");
+        assert_eq!(out, "longest word has 9 letters");
+        assert!(USED.get() > 0);
+        assert_eq!(allocators.used(0), 0);
+        assert_eq!(allocators.used(1), 0);
+
+        // without the reset, the scratch copies would still be there
+        Longest::cascade_with(&allocators, "a bb ccc");
+        assert!(allocators.used(0) >= "abbccc".len());
+        allocators.reset();
+        assert_eq!(allocators.used(0), 0);
+    }
+}
@@ -0,0 +1,81 @@
+#[cfg(test)]
+pub mod tests {
+
+    use chain_link::*;
+
+    struct Countdown;
+
+    impl Chain<0> for Countdown {
+        type In<'a> = &'a str;
+        type Out<'a> = i32;
+
+        fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+            input.trim().parse().unwrap_or_default()
+        }
+    }
+
+    impl Contract<0> for Countdown {
+        fn requires(input: &&str) -> Result<(), &'static str> {
+            match input.trim().is_empty() {
+                true => Err("not_blank"),
+                false => Ok(()),
+            }
+        }
+    }
+
+    impl Chain<1> for Countdown {
+        type In<'a> = i32;
+        type Out<'a> = i32;
+
+        fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+            input - 1
+        }
+    }
+
+    impl Contract<1> for Countdown {
+        fn ensures(output: &i32) -> Result<(), &'static str> {
+            match *output >= 0 {
+                true => Ok(()),
+                false => Err("non_negative"),
+            }
+        }
+    }
+
+    impl Chain<2> for Countdown {
+        type In<'a> = i32;
+        type Out<'a> = String;
+
+        fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+            format!("{input} left")
+        }
+    }
+
+    impl Contract<2> for Countdown {}
+
+    impl Length for Countdown {
+        type Len = L<3>;
+    }
+
+    impl Named for Countdown {
+        fn stage_name(index: usize) -> Option<&'static str> {
+            (index == 1).then_some("decrement")
+        }
+    }
+
+    fn violation(input: &'static str) -> String {
+        let payload = std::panic::catch_unwind(|| Countdown::cascade_checked(input)).unwrap_err();
+        payload.downcast::<String>().map(|message| *message).unwrap_or_default()
+    }
+
+    #[test]
+    fn contracts() {
+        assert_eq!(Countdown::cascade_checked("3"), "2 left");
+        // unchecked, nothing stops the countdown going negative
+        assert_eq!(Countdown::cascade("0"), "-1 left");
+
+        if cfg!(debug_assertions) {
+            assert_eq!(violation("0"), "stage 1 (decrement): postcondition `non_negative` failed");
+            assert_eq!(violation(" "), "stage 0: precondition `not_blank` failed");
+        }
+    }
+}
@@ -0,0 +1,97 @@
+#[cfg(test)]
+pub mod tests {
+
+    use chain_link::*;
+
+    /// Normalizes user names the way signup does.
+    struct Signup;
+
+    impl Chain<0> for Signup {
+        type In<'a> = String;
+        type Out<'a> = String;
+
+        fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+            input.trim().to_lowercase()
+        }
+    }
+
+    impl Chain<1> for Signup {
+        type In<'a> = String;
+        type Out<'a> = String;
+
+        fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+            input.replace(' ', "_")
+        }
+    }
+
+    impl Length for Signup {
+        type Len = L<2>;
+    }
+
+    /// Same as `Signup`, except someone dropped the trim.
+    struct Regressed;
+
+    impl Chain<0> for Regressed {
+        type In<'a> = String;
+        type Out<'a> = String;
+
+        fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+            input.to_lowercase()
+        }
+    }
+
+    impl Chain<1> for Regressed {
+        type In<'a> = String;
+        type Out<'a> = String;
+
+        fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+            input.replace(' ', "_")
+        }
+    }
+
+    impl Length for Regressed {
+        type Len = L<2>;
+    }
+
+    // writes the corpus to a temp file, which Miri's isolation doesn't allow
+    #[cfg_attr(miri, ignore)]
+    #[test]
+    fn replay_corpus() {
+        let mut corpus = Corpus::new();
+        for name in ["Ann Lee", "  bo ", "CY"] {
+            corpus.record::<Signup>(name.to_owned());
+        }
+        assert_eq!(corpus.entries.len(), 3);
+
+        let path = std::env::temp_dir().join(format!("chain_link_corpus_{}.bin", std::process::id()));
+        corpus.save(&path).unwrap();
+        let replayed = chain_link::replay_corpus::<Signup>(&path).unwrap();
+        let regressed = chain_link::replay_corpus::<Regressed>(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(replayed.is_empty());
+        assert_eq!(regressed, [1]);
+        assert!(chain_link::replay_corpus::<Signup>(&path).is_err());
+    }
+
+    #[test]
+    fn diff_versions_report() {
+        let mut corpus = Corpus::new();
+        for name in ["Ann Lee", "  bo ", "CY"] {
+            corpus.record::<Signup>(name.to_owned());
+        }
+        let inputs: Vec<String> = corpus.inputs().unwrap();
+
+        let report = diff_versions::<Signup, Regressed, _, _>(inputs.clone());
+        assert_eq!(report.total, 3);
+        assert_eq!(report.matched(), 2);
+        assert_eq!(report.divergences, [(1, Divergence {
+            input: "  bo ".to_owned(),
+            old: "bo".to_owned(),
+            new: "__bo_".to_owned(),
+        })]);
+        assert_eq!(report.to_string(), "1 of 3 inputs diverged\n#1 \"  bo \": old \"bo\", new \"__bo_\"");
+
+        assert!(diff_versions::<Signup, Signup, _, _>(inputs).is_clean());
+    }
+}
@@ -0,0 +1,64 @@
+#[cfg(test)]
+pub mod tests {
+
+    use std::panic;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    use chain_link::*;
+
+    /// Link 1 sneaks in a global counter, so no two runs agree.
+    #[test]
+    fn nondeterminism_is_caught() {
+
+        static CALLS: AtomicU32 = AtomicU32::new(0);
+
+        struct Leaky;
+
+        impl Chain<0> for Leaky {
+            type In<'a> = u32;
+            type Out<'a> = u32;
+
+            fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+                input * 2
+            }
+        }
+
+        impl Chain<1> for Leaky {
+            type In<'a> = u32;
+            type Out<'a> = String;
+
+            fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+                format!("{input}-{}", CALLS.fetch_add(1, Ordering::SeqCst))
+            }
+        }
+
+        impl Length for Leaky {
+            type Len = L<2>;
+        }
+
+        struct Pure;
+
+        impl Chain<0> for Pure {
+            type In<'a> = u32;
+            type Out<'a> = String;
+
+            fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+                format!("{}", input * 2)
+            }
+        }
+
+        impl Length for Pure {
+            type Len = L<1>;
+        }
+
+        assert_eq!(Pure::cascade_verify_deterministic(21), "42");
+        assert_eq!(Pure::try_cascade_deterministic(21), Ok("42".to_owned()));
+
+        let err = Leaky::try_cascade_deterministic(21).unwrap_err();
+        assert_eq!(err, Nondeterministic { first: "42-0".to_owned(), second: "42-1".to_owned() });
+        assert_eq!(err.to_string(), r#"cascade is nondeterministic: "42-0" != "42-1""#);
+
+        let panicked = panic::catch_unwind(|| Leaky::cascade_verify_deterministic(21));
+        assert!(panicked.is_err());
+    }
+}
@@ -0,0 +1,59 @@
+#[cfg(test)]
+pub mod tests {
+
+    use std::collections::BTreeMap;
+
+    use chain_link::*;
+
+    /// Parses a list of numbers then tallies how many times each one was seen, so changing one
+    /// number in the input should only touch the two tallies involved.
+    #[test]
+    fn cascade_diff_minimal() {
+
+        struct Tally;
+
+        impl Chain<0> for Tally {
+            type In<'a> = &'a str;
+            type Out<'a> = Vec<u32>;
+
+            fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+                input.split(',').map(|n| n.trim().parse().unwrap()).collect()
+            }
+        }
+
+        impl Chain<1> for Tally {
+            type In<'a> = Vec<u32>;
+            type Out<'a> = BTreeMap<u32, usize>;
+
+            fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+                let mut tally = BTreeMap::new();
+                for n in input {
+                    *tally.entry(n).or_default() += 1;
+                }
+                tally
+            }
+        }
+
+        impl Length for Tally {
+            type Len = L<2>;
+        }
+
+        let prev = Tally::cascade("1, 2, 2, 3, 4");
+        let (out, diff) = Tally::cascade_diff(&prev, "1, 2, 3, 3, 4");
+        assert_eq!(diff, [MapEdit::Insert(2, 1), MapEdit::Insert(3, 2)]);
+
+        let mut patched = prev.clone();
+        patched.apply(diff);
+        assert_eq!(patched, out);
+
+        let (_, diff) = Tally::cascade_diff(&prev, "1, 2, 2, 3");
+        assert_eq!(diff, [MapEdit::Remove(4)]);
+        let (_, diff) = Tally::cascade_diff(&prev, "1, 2, 2, 3, 4");
+        assert!(diff.is_empty());
+
+        let old = vec![1, 2, 3];
+        let new = vec![1, 5, 3, 4];
+        assert_eq!(new.diff(&old), [Edit::Set(1, 5), Edit::Push(4)]);
+        assert_eq!(old.diff(&new), [Edit::Set(1, 2), Edit::Truncate(3)]);
+    }
+}
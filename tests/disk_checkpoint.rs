@@ -0,0 +1,80 @@
+#[cfg(test)]
+#[cfg(feature = "disk_checkpoint")]
+pub mod tests {
+
+    use std::panic::{self, AssertUnwindSafe};
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+    use chain_link::*;
+
+    static CRASH: AtomicBool = AtomicBool::new(false);
+    static EXPENSIVE_RUNS: AtomicUsize = AtomicUsize::new(0);
+
+    /// Link 1 is the expensive one that shouldn't rerun after a crash in link 2.
+    struct Batch;
+
+    impl Chain<0> for Batch {
+        type In<'a> = u32;
+        type Out<'a> = Vec<u32>;
+
+        fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+            (1..=input).collect()
+        }
+    }
+
+    impl Chain<1> for Batch {
+        type In<'a> = Vec<u32>;
+        type Out<'a> = Vec<u64>;
+
+        fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+            EXPENSIVE_RUNS.fetch_add(1, Ordering::SeqCst);
+            input.into_iter().map(|n| (n as u64).pow(2)).collect()
+        }
+    }
+
+    impl Chain<2> for Batch {
+        type In<'a> = Vec<u64>;
+        type Out<'a> = u64;
+
+        fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+            if CRASH.load(Ordering::SeqCst) {
+                panic!("simulated crash");
+            }
+            input.iter().sum()
+        }
+    }
+
+    impl Length for Batch {
+        type Len = L<3>;
+    }
+
+    // Miri's isolation doesn't allow touching the filesystem
+    #[cfg_attr(miri, ignore)]
+    #[test]
+    fn crash_and_resume() {
+        let dir = std::env::temp_dir().join(format!("chain_link_checkpoints_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        assert_eq!(Batch::resume_from_checkpoint(&dir).unwrap(), None);
+
+        CRASH.store(true, Ordering::SeqCst);
+        let crashed = panic::catch_unwind(AssertUnwindSafe(|| Batch::cascade_checkpointed(4, &dir)));
+        assert!(crashed.is_err());
+        assert_eq!(EXPENSIVE_RUNS.load(Ordering::SeqCst), 1);
+
+        // "restart" the process
+        CRASH.store(false, Ordering::SeqCst);
+        assert_eq!(Batch::resume_from_checkpoint(&dir).unwrap(), Some(30));
+        assert_eq!(EXPENSIVE_RUNS.load(Ordering::SeqCst), 1);
+
+        // the final output is checkpointed too, so resuming again is free
+        assert_eq!(Batch::resume_from_checkpoint(&dir).unwrap(), Some(30));
+
+        // a fresh run starts over rather than picking up the old checkpoints
+        assert_eq!(Batch::cascade_checkpointed(2, &dir).unwrap(), 5);
+        assert_eq!(EXPENSIVE_RUNS.load(Ordering::SeqCst), 2);
+
+        std::fs::write(dir.join("stage-2.bin"), [1, 2, 3]).unwrap();
+        assert_eq!(Batch::resume_from_checkpoint(&dir).unwrap_err().kind(), std::io::ErrorKind::InvalidData);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,50 @@
+#[cfg(test)]
+pub mod tests {
+
+    use chain_link::*;
+
+    /// f(x) = (x² + 1)³ split into three stages, so f'(x) = 6x(x² + 1)².
+    #[test]
+    fn cascade_grad_polynomial() {
+
+        struct Polynomial;
+
+        impl Homogeneous for Polynomial {
+            type Item = Dual;
+        }
+
+        impl Sequence<0> for Polynomial {
+            fn step(x: Dual) -> Dual {
+                x * x
+            }
+        }
+
+        impl Sequence<1> for Polynomial {
+            fn step(x: Dual) -> Dual {
+                x + 1.0
+            }
+        }
+
+        impl Sequence<2> for Polynomial {
+            fn step(x: Dual) -> Dual {
+                x.powi(3)
+            }
+        }
+
+        impl Length for Polynomial {
+            type Len = L<3>;
+        }
+
+        for x in [-1.5, 0.0, 2.0, 3.25] {
+            let (value, derivative) = Polynomial::cascade_grad(x);
+            let expected = (x * x + 1.0f64).powi(3);
+            let expected_derivative = 6.0 * x * (x * x + 1.0f64).powi(2);
+            assert!((value - expected).abs() < 1e-9);
+            assert!((derivative - expected_derivative).abs() < 1e-9);
+        }
+        // known values at x = 2, within tolerance since powi's precision is unspecified
+        let (value, derivative) = Polynomial::cascade_grad(2.0);
+        assert!((value - 125.0).abs() < 1e-9);
+        assert!((derivative - 300.0).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,219 @@
+#[cfg(test)]
+pub mod tests {
+
+    use chain_link::*;
+
+    #[test]
+    fn dyn_cascade() {
+        let pipeline = DynCascade::new()
+            .then(|n: i32| n + 1)
+            .then(|n| n * 10)
+            .then(|n| n - 3);
+        assert_eq!(pipeline.len(), 3);
+        assert_eq!(pipeline.cascade(4), 47);
+        assert_eq!(DynCascade::default().cascade(4), 4);
+    }
+
+    #[test]
+    fn flat_cascade() {
+
+        struct Steps;
+
+        impl Homogeneous for Steps {
+            type Item = i32;
+        }
+
+        impl Sequence<0> for Steps {
+            fn step(n: i32) -> i32 {
+                n + 1
+            }
+        }
+
+        impl Sequence<1> for Steps {
+            fn step(n: i32) -> i32 {
+                n * 10
+            }
+        }
+
+        impl Length for Steps {
+            type Len = L<2>;
+        }
+
+        let flat: FlatCascade<i32> = [|n| n + 1, |n| n * 10, |n| n - 3]
+            .map(|stage| stage as fn(i32) -> i32)
+            .into_iter()
+            .collect();
+        let boxed = DynCascade::from(flat.clone());
+        assert_eq!(flat.len(), 3);
+        for n in -5..5 {
+            assert_eq!(flat.cascade(n), boxed.cascade(n));
+        }
+
+        let flat = FlatCascade::of::<Steps>();
+        assert_eq!(flat.cascade(4), Steps::cascade(4));
+    }
+
+    /// Pipelines are built from a list of stage names, and the second request for the same list
+    /// reuses the first pipeline rather than building it again.
+    #[test]
+    fn pipeline_cache_reuses_builds() {
+        let mut builds = 0;
+        let mut cache = PipelineCache::new(|config: &Vec<&str>| {
+            builds += 1;
+            let mut pipeline = DynCascade::new();
+            for stage in config {
+                match *stage {
+                    "double" => pipeline.push(|n: i32| n * 2),
+                    "negate" => pipeline.push(|n: i32| -n),
+                    other => panic!("unknown stage {other}"),
+                }
+            }
+            pipeline
+        });
+
+        let config = vec!["double", "negate"];
+        assert_eq!(cache.get_or_build(&config).cascade(3), -6);
+        assert_eq!(cache.get_or_build(&config.clone()).cascade(4), -8);
+        assert_eq!(cache.get_or_build(&vec!["negate"]).cascade(4), -4);
+        assert_eq!(cache.len(), 2);
+        drop(cache);
+        assert_eq!(builds, 2);
+    }
+
+    /// Newton's method for sqrt(2), where the closure keeps picking a stage until the value
+    /// converges. Overshooting stages get halved first, just to exercise picking different stages.
+    #[test]
+    fn unfold_to_convergence() {
+        fn newton(x: f64) -> f64 {
+            x - (x * x - 2.0) / (2.0 * x)
+        }
+        fn halve(x: f64) -> f64 {
+            x / 2.0
+        }
+
+        let mut stages = 0;
+        let mut unfold = Unfold::new(|x: &f64| {
+            if (x * x - 2.0).abs() < 1e-12 {
+                return None;
+            }
+            stages += 1;
+            let stage: fn(f64) -> f64 = if *x > 100.0 { halve } else { newton };
+            Some(stage)
+        });
+        let actual = unfold.cascade(1_000.0);
+        assert!((actual - 2f64.sqrt()).abs() < 1e-12);
+        assert!(stages > 3);
+    }
+
+    #[test]
+    fn dag_runs_in_dependency_order() {
+        let dag = DagBuilder::new()
+            .stage("parse", &[], |inputs: Vec<i32>| inputs[0])
+            .stage("double", &["parse"], |inputs| inputs[0] * 2)
+            .stage("square", &["parse"], |inputs| inputs[0] * inputs[0])
+            .stage("sum", &["double", "square"], |inputs| inputs.iter().sum())
+            .build()
+            .unwrap();
+        assert_eq!(dag.order(), ["parse", "double", "square", "sum"]);
+        assert_eq!(dag.cascade(3), 15);
+    }
+
+    #[test]
+    fn dag_cycle_is_a_build_error() {
+        let result = DagBuilder::new()
+            .stage("load", &[], |inputs: Vec<i32>| inputs[0])
+            .stage("a", &["load", "c"], |inputs| inputs[0])
+            .stage("b", &["a"], |inputs| inputs[0])
+            .stage("c", &["b"], |inputs| inputs[0])
+            .build();
+        let error = result.err().unwrap();
+        assert_eq!(error, DagError::Cycle(vec!["a".into(), "c".into(), "b".into(), "a".into()]));
+        assert_eq!(error.to_string(), "stages depend on each other in a cycle: a -> c -> b -> a");
+
+        let result = DagBuilder::new().stage("a", &["missing"], |inputs: Vec<i32>| inputs[0]).build();
+        assert_eq!(
+            result.err().unwrap(),
+            DagError::UnknownDependency { stage: "a".into(), dependency: "missing".into() }
+        );
+    }
+
+    #[cfg(feature = "yaml")]
+    #[test]
+    fn yaml_workflow() {
+        let registry = StageRegistry::new()
+            .stage("trim", |text: String| text.trim().to_owned())
+            .factory("repeat", |params| {
+                let times = params.get("times").and_then(|times| times.as_u64());
+                let times = times.ok_or("`times` is required")? as usize;
+                let separator = params.get("separator").and_then(|separator| separator.as_str());
+                let separator = separator.unwrap_or("").to_owned();
+                Ok(move |text: String| vec![text; times].join(&separator))
+            });
+
+        let pipeline = registry
+            .workflow(
+                "
+stages:
+  - name: trim
+  - name: repeat
+    params:
+      times: 3
+      separator: ', '
+",
+            )
+            .unwrap();
+        assert_eq!(pipeline.len(), 2);
+        assert_eq!(pipeline.cascade("  hey ".to_owned()), "hey, hey, hey");
+
+        let error = registry.workflow("stages: [{name: shout}]").err().unwrap();
+        assert_eq!(error.to_string(), "no stage registered as `shout`");
+        let error = registry.workflow("stages: [{name: repeat}]").err().unwrap();
+        assert_eq!(error.to_string(), "bad parameters for stage `repeat`: `times` is required");
+        assert!(matches!(registry.workflow("stages: {name: trim}"), Err(WorkflowError::Format(_))));
+        assert!(matches!(registry.workflow("stages: ["), Err(WorkflowError::Yaml(_))));
+    }
+
+    #[test]
+    fn query_pipeline() {
+        let registry = StageRegistry::new()
+            .stage("trim", |text: String| text.trim().to_owned())
+            .stage("upper", |text: String| text.to_uppercase())
+            .stage("reverse", |text: String| text.chars().rev().collect())
+            .factory("pad", |params| {
+                let width = params.get("width").and_then(|width| width.as_u64());
+                let width = width.ok_or("`width` is required")? as usize;
+                let fill = params.get("fill").and_then(|fill| fill.as_str()).unwrap_or(" ").to_owned();
+                Ok(move |text: String| {
+                    let padding = width.saturating_sub(text.chars().count());
+                    format!("{}{text}", fill.repeat(padding))
+                })
+            });
+
+        let pipeline = registry.query("{ trim upper }").unwrap();
+        assert_eq!(pipeline.len(), 2);
+        assert_eq!(pipeline.cascade("  abc ".to_owned()), "ABC");
+
+        let pipeline = registry
+            .query(
+                r#"
+query Label {
+  trim
+  # padded to a fixed width for the printer
+  pad(width: 6, fill: "*"),
+}
+"#,
+            )
+            .unwrap();
+        assert_eq!(pipeline.cascade(" abc ".to_owned()), "***abc");
+
+        let error = registry.query("{ trim shout }").err().unwrap();
+        assert_eq!(error, QueryError::Stage(StageError::Unknown("shout".to_owned())));
+        assert_eq!(error.to_string(), "no stage registered as `shout`");
+        let error = registry.query("{ pad(fill: \"-\") }").err().unwrap();
+        assert_eq!(error.to_string(), "bad parameters for stage `pad`: `width` is required");
+        let error = registry.query("{ trim { upper } }").err().unwrap();
+        assert_eq!(error.to_string(), "invalid query: stage `trim` can't have a selection of its own");
+        assert!(matches!(registry.query("{ trim"), Err(QueryError::Syntax(_))));
+        assert!(matches!(registry.query("mutation { trim }"), Err(QueryError::Syntax(_))));
+    }
+}
@@ -0,0 +1,89 @@
+#[cfg(test)]
+pub mod tests {
+
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use chain_link::*;
+
+    static DETECTS: AtomicUsize = AtomicUsize::new(0);
+    static ACCELERATED: AtomicUsize = AtomicUsize::new(0);
+
+    /// Parses bytes, then sums them. The sum has a chunked "SIMD" version for CPUs that have the
+    /// feature, and the plain loop for everything else.
+    struct Checksum<const HAS_FEATURE: bool>;
+
+    impl<const HAS_FEATURE: bool> Chain<0> for Checksum<HAS_FEATURE> {
+        type In<'a> = &'a str;
+        type Out<'a> = Vec<u8>;
+
+        fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+            input.bytes().collect()
+        }
+    }
+
+    impl<const HAS_FEATURE: bool> Chain<1> for Checksum<HAS_FEATURE> {
+        type In<'a> = Vec<u8>;
+        type Out<'a> = u64;
+
+        fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+            input.iter().map(|byte| *byte as u64).sum()
+        }
+    }
+
+    /// Sums 8 lanes at a time.
+    fn lanes(input: Vec<u8>) -> u64 {
+        ACCELERATED.fetch_add(1, Ordering::SeqCst);
+        let mut lanes = [0u64; 8];
+        for chunk in input.chunks(8) {
+            for (lane, byte) in lanes.iter_mut().zip(chunk) {
+                *lane += *byte as u64;
+            }
+        }
+        lanes.iter().sum()
+    }
+
+    // one impl each, since a static in a generic impl would be shared between both
+    macro_rules! accelerated {
+        ($has_feature:literal) => {
+            impl Accelerated<1> for Checksum<$has_feature> {
+                fn detected() -> bool {
+                    static DETECTION: Detection = Detection::new();
+                    DETECTION.get(|| {
+                        DETECTS.fetch_add(1, Ordering::SeqCst);
+                        $has_feature
+                    })
+                }
+
+                fn accelerated(input: Self::In<'_>) -> Self::Out<'_> {
+                    lanes(input)
+                }
+            }
+        };
+    }
+
+    accelerated!(false);
+    accelerated!(true);
+
+    impl<const HAS_FEATURE: bool> Length for Checksum<HAS_FEATURE> {
+        type Len = L<2>;
+    }
+
+    #[test]
+    fn feature_selected_fallback() {
+        let text = "feature detection picks the fastest stage";
+        let expected = Checksum::<false>::cascade(text);
+
+        // forced onto the scalar path, as on a CPU without the feature
+        for _ in 0..3 {
+            assert_eq!(FeatureSelected::<Checksum<false>, 1>::cascade(text), expected);
+        }
+        assert_eq!(ACCELERATED.load(Ordering::SeqCst), 0);
+        assert_eq!(DETECTS.load(Ordering::SeqCst), 1);
+
+        for _ in 0..3 {
+            assert_eq!(FeatureSelected::<Checksum<true>, 1>::cascade(text), expected);
+        }
+        assert_eq!(ACCELERATED.load(Ordering::SeqCst), 3);
+        assert_eq!(DETECTS.load(Ordering::SeqCst), 2);
+    }
+}
@@ -0,0 +1,223 @@
+#[cfg(test)]
+pub mod tests {
+
+    use chain_link::*;
+
+    /// Parses a number, drops it if it's odd, then formats it. Wrapped in `DefaultIfNone` at
+    /// link 1, odd numbers become 0 instead of stopping the cascade, but unparsable input from
+    /// link 0 is still dropped.
+    #[test]
+    fn default_if_none() {
+
+        struct Evens;
+
+        impl FilterChain<0> for Evens {
+            type In<'a> = &'a str;
+            type Out<'a> = u32;
+
+            fn filter(input: Self::In<'_>) -> Option<Self::Out<'_>> {
+                input.parse().ok()
+            }
+        }
+
+        impl FilterChain<1> for Evens {
+            type In<'a> = u32;
+            type Out<'a> = u32;
+
+            fn filter(input: Self::In<'_>) -> Option<Self::Out<'_>> {
+                (input % 2 == 0).then_some(input)
+            }
+        }
+
+        impl FilterChain<2> for Evens {
+            type In<'a> = u32;
+            type Out<'a> = String;
+
+            fn filter(input: Self::In<'_>) -> Option<Self::Out<'_>> {
+                Some(format!("#{input}"))
+            }
+        }
+
+        impl Length for Evens {
+            type Len = L<3>;
+        }
+
+        assert_eq!(Evens::filter_cascade("4"), Some("#4".to_owned()));
+        assert_eq!(Evens::filter_cascade("5"), None);
+        assert_eq!(Evens::filter_cascade("x"), None);
+
+        type Defaulted = DefaultIfNone<Evens, 1>;
+        assert_eq!(Defaulted::filter_cascade("4"), Some("#4".to_owned()));
+        assert_eq!(Defaulted::filter_cascade("5"), Some("#0".to_owned()));
+        assert_eq!(Defaulted::filter_cascade("x"), None);
+    }
+
+    /// Out of 0..100, link 0 drops the 50 odd numbers, then link 1 drops the 40 of the 50 even
+    /// ones that aren't multiples of 10.
+    #[test]
+    fn selectivity() {
+
+        struct Sieve;
+
+        impl FilterChain<0> for Sieve {
+            type In<'a> = u32;
+            type Out<'a> = u32;
+
+            fn filter(input: Self::In<'_>) -> Option<Self::Out<'_>> {
+                (input % 2 == 0).then_some(input)
+            }
+        }
+
+        impl FilterChain<1> for Sieve {
+            type In<'a> = u32;
+            type Out<'a> = u32;
+
+            fn filter(input: Self::In<'_>) -> Option<Self::Out<'_>> {
+                (input % 10 == 0).then_some(input)
+            }
+        }
+
+        impl Length for Sieve {
+            type Len = L<2>;
+        }
+
+        let (outputs, selectivity) = Sieve::cascade_iter_with_selectivity(0..100);
+        assert_eq!(outputs, [0, 10, 20, 30, 40, 50, 60, 70, 80, 90]);
+        assert_eq!(selectivity.stages, [
+            StageSelectivity { passed: 50, dropped: 50 },
+            StageSelectivity { passed: 10, dropped: 40 },
+        ]);
+        assert_eq!(selectivity.stages[1].pass_rate(), Some(0.2));
+
+        let (outputs, selectivity) = Sieve::cascade_iter_with_selectivity([1, 3]);
+        assert!(outputs.is_empty());
+        assert_eq!(selectivity.stages[1].pass_rate(), None);
+    }
+
+    /// Orders can be delivered more than once, but each id should only be charged once. The key
+    /// set holds two ids, so the oldest one is forgotten once a third comes along.
+    #[test]
+    fn idempotent() {
+        use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+        use std::{hint, panic, thread};
+
+        static CHARGED: AtomicU32 = AtomicU32::new(0);
+        static DECLINE: AtomicBool = AtomicBool::new(false);
+        static CHARGING: AtomicBool = AtomicBool::new(false);
+        static HOLD: AtomicBool = AtomicBool::new(false);
+
+        struct Orders;
+
+        impl FilterChain<0> for Orders {
+            type In<'a> = (u32, u32);
+            type Out<'a> = u32;
+
+            fn filter((_, amount): Self::In<'_>) -> Option<Self::Out<'_>> {
+                if DECLINE.swap(false, Ordering::Relaxed) {
+                    panic!("card declined");
+                }
+                CHARGING.store(true, Ordering::SeqCst);
+                while HOLD.load(Ordering::SeqCst) {
+                    hint::spin_loop();
+                }
+                CHARGED.fetch_add(amount, Ordering::Relaxed);
+                Some(amount)
+            }
+        }
+
+        impl IdempotencyKey<0> for Orders {
+            type Key = u32;
+
+            fn key((id, _): &Self::In<'_>) -> u32 {
+                *id
+            }
+
+            fn seen() -> &'static SeenKeys<u32> {
+                static SEEN: SeenKeys<u32> = SeenKeys::new(2);
+                &SEEN
+            }
+        }
+
+        impl Length for Orders {
+            type Len = L<1>;
+        }
+
+        type Once = Idempotent<Orders, 0>;
+        assert_eq!(Once::filter_cascade((1, 10)), Some(10));
+        assert_eq!(Once::filter_cascade((1, 10)), None);
+        assert_eq!(Once::filter_cascade((2, 5)), Some(5));
+        assert_eq!(CHARGED.load(Ordering::Relaxed), 15);
+        assert_eq!(Orders::seen().len(), 2);
+
+        assert_eq!(Once::filter_cascade((3, 1)), Some(1));
+        assert_eq!(Once::filter_cascade((1, 10)), Some(10));
+        assert_eq!(Once::filter_cascade((3, 1)), None);
+        assert_eq!(CHARGED.load(Ordering::Relaxed), 26);
+
+        // the first attempt never finished, so the redelivery still gets charged
+        DECLINE.store(true, Ordering::Relaxed);
+        assert!(panic::catch_unwind(|| Once::filter_cascade((4, 7))).is_err());
+        assert_eq!(Once::filter_cascade((4, 7)), Some(7));
+        assert_eq!(Once::filter_cascade((4, 7)), None);
+        assert_eq!(CHARGED.load(Ordering::Relaxed), 33);
+
+        // a redelivery while the first attempt is still charging is dropped too
+        HOLD.store(true, Ordering::SeqCst);
+        CHARGING.store(false, Ordering::SeqCst);
+        let first = thread::spawn(|| Once::filter_cascade((5, 2)));
+        while !CHARGING.load(Ordering::SeqCst) {
+            thread::yield_now();
+        }
+        assert_eq!(Once::filter_cascade((5, 2)), None);
+        HOLD.store(false, Ordering::SeqCst);
+        assert_eq!(first.join().unwrap(), Some(2));
+        assert_eq!(CHARGED.load(Ordering::Relaxed), 35);
+
+        assert!(panic::catch_unwind(|| SeenKeys::<u32>::new(0)).is_err());
+    }
+
+    /// The worker goes down after charging two orders, before the queue learned they were done, so
+    /// the queue redelivers them. The store outlived the worker, so the restarted one only charges
+    /// the order it hadn't seen.
+    #[test]
+    fn exactly_once_across_restarts() {
+        use std::sync::atomic::{AtomicU32, Ordering};
+
+        static CHARGED: AtomicU32 = AtomicU32::new(0);
+
+        struct Charge;
+
+        impl Chain<0> for Charge {
+            type In<'a> = (u32, u32);
+            type Out<'a> = u32;
+
+            fn chain((_, amount): Self::In<'_>) -> Self::Out<'_> {
+                CHARGED.fetch_add(amount, Ordering::Relaxed);
+                amount
+            }
+        }
+
+        impl Length for Charge {
+            type Len = L<1>;
+        }
+
+        impl ExactlyOnce for Charge {
+            type Key = u32;
+
+            fn key((id, _): &Self::In<'_>) -> u32 {
+                *id
+            }
+        }
+
+        fn worker(store: &mut MemoryStore<u32>, queue: &[(u32, u32)]) -> Vec<Option<u32>> {
+            let charge = |order| Charge::cascade_exactly_once(store, order).unwrap_or_else(|never| match never {});
+            queue.iter().copied().map(charge).collect()
+        }
+
+        let mut store = MemoryStore::new();
+        assert_eq!(worker(&mut store, &[(1, 10), (2, 5)]), [Some(10), Some(5)]);
+        assert_eq!(worker(&mut store, &[(1, 10), (2, 5), (3, 1)]), [None, None, Some(1)]);
+        assert_eq!(CHARGED.load(Ordering::Relaxed), 16);
+        assert_eq!(store.len(), 3);
+    }
+}
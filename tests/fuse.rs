@@ -0,0 +1,140 @@
+#[cfg(test)]
+pub mod tests {
+
+    use chain_link::*;
+
+    /// The middle three stages of `Unfused` are pulled out into `Segment` and fused into stage 1
+    /// of `Fused`, which only needs 3 links instead of 5 to produce the same output.
+    #[test]
+    fn fuse_pure_segment() {
+
+        struct Segment;
+
+        impl Chain<0> for Segment {
+            type In<'a> = i32;
+            type Out<'a> = i64;
+
+            fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+                input as i64 * 3
+            }
+        }
+
+        impl Chain<1> for Segment {
+            type In<'a> = i64;
+            type Out<'a> = i64;
+
+            fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+                input - 7
+            }
+        }
+
+        impl Chain<2> for Segment {
+            type In<'a> = i64;
+            type Out<'a> = u64;
+
+            fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+                input.unsigned_abs()
+            }
+        }
+
+        impl Pure<0> for Segment {}
+        impl Pure<1> for Segment {}
+        impl Pure<2> for Segment {}
+
+        impl Length for Segment {
+            type Len = L<3>;
+        }
+
+        struct Unfused;
+
+        impl Chain<0> for Unfused {
+            type In<'a> = &'a str;
+            type Out<'a> = i32;
+
+            fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+                input.parse().unwrap()
+            }
+        }
+
+        impl Chain<1> for Unfused {
+            type In<'a> = i32;
+            type Out<'a> = i64;
+
+            fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+                <Segment as Chain<0>>::chain(input)
+            }
+        }
+
+        impl Chain<2> for Unfused {
+            type In<'a> = i64;
+            type Out<'a> = i64;
+
+            fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+                <Segment as Chain<1>>::chain(input)
+            }
+        }
+
+        impl Chain<3> for Unfused {
+            type In<'a> = i64;
+            type Out<'a> = u64;
+
+            fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+                <Segment as Chain<2>>::chain(input)
+            }
+        }
+
+        impl Chain<4> for Unfused {
+            type In<'a> = u64;
+            type Out<'a> = String;
+
+            fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+                format!("|{input}|")
+            }
+        }
+
+        impl Length for Unfused {
+            type Len = L<5>;
+        }
+
+        struct Fused;
+
+        impl Chain<0> for Fused {
+            type In<'a> = &'a str;
+            type Out<'a> = i32;
+
+            fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+                input.parse().unwrap()
+            }
+        }
+
+        impl Chain<1> for Fused {
+            type In<'a> = i32;
+            type Out<'a> = u64;
+
+            fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+                Fuse::<Segment>::cascade(input)
+            }
+        }
+
+        impl Chain<2> for Fused {
+            type In<'a> = u64;
+            type Out<'a> = String;
+
+            fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+                format!("|{input}|")
+            }
+        }
+
+        impl Length for Fused {
+            type Len = L<3>;
+        }
+
+        assert_eq!(Fuse::<Segment>::len(), 1);
+        assert_eq!(Unfused::len(), 5);
+        assert_eq!(Fused::len(), 3);
+        for input in ["-4", "0", "2", "1000"] {
+            assert_eq!(Unfused::cascade(input), Fused::cascade(input));
+        }
+        assert_eq!(Fused::cascade("-4"), "|19|");
+    }
+}
@@ -0,0 +1,58 @@
+#[cfg(test)]
+pub mod tests {
+
+    use chain_link::*;
+
+    struct Pipeline;
+
+    impl Chain<0> for Pipeline {
+        type In<'a> = String;
+        type Out<'a> = Vec<u32>;
+
+        fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+            input.split(',').filter_map(|n| n.trim().parse().ok()).collect()
+        }
+    }
+
+    impl Chain<1> for Pipeline {
+        type In<'a> = Vec<u32>;
+        type Out<'a> = u32;
+
+        fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+            input.iter().sum()
+        }
+    }
+
+    impl Chain<2> for Pipeline {
+        type In<'a> = u32;
+        type Out<'a> = String;
+
+        fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+            format!("total {input}")
+        }
+    }
+
+    impl Length for Pipeline {
+        type Len = L<3>;
+    }
+
+    #[test]
+    fn stage_generator() {
+        let mut generator = Pipeline::into_generator("1, 2, x, 4".to_owned());
+        assert_eq!(generator.intermediate::<String>().map(String::as_str), Some("1, 2, x, 4"));
+
+        assert_eq!(generator.resume(), Some(0));
+        assert_eq!(generator.intermediate::<Vec<u32>>(), Some(&vec![1, 2, 4]));
+        assert_eq!(generator.intermediate::<u32>(), None);
+
+        assert_eq!(generator.resume(), Some(1));
+        assert_eq!(generator.intermediate::<u32>(), Some(&7));
+        let mut generator = generator.into_output().unwrap_err();
+        assert_eq!(generator.completed(), 2);
+
+        assert_eq!(generator.resume(), Some(2));
+        assert_eq!(generator.resume(), None);
+        assert!(generator.is_complete());
+        assert_eq!(generator.into_output().ok(), Some("total 7".to_owned()));
+    }
+}
@@ -0,0 +1,69 @@
+#[cfg(test)]
+pub mod tests {
+
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    use chain_link::*;
+
+    /// Stand-in for someone changing the rounding in link 1.
+    static ROUND_UP: AtomicBool = AtomicBool::new(false);
+
+    /// Tokenizes prices, converts them to cents, then totals them.
+    struct Checkout;
+
+    impl Chain<0> for Checkout {
+        type In<'a> = String;
+        type Out<'a> = Vec<f64>;
+
+        fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+            input.split(',').filter_map(|price| price.trim().parse().ok()).collect()
+        }
+    }
+
+    impl Chain<1> for Checkout {
+        type In<'a> = Vec<f64>;
+        type Out<'a> = Vec<u64>;
+
+        fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+            let round = match ROUND_UP.load(Ordering::SeqCst) {
+                true => f64::ceil,
+                false => f64::round,
+            };
+            input.into_iter().map(|price| round(price * 100.0) as u64).collect()
+        }
+    }
+
+    impl Chain<2> for Checkout {
+        type In<'a> = Vec<u64>;
+        type Out<'a> = u64;
+
+        fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+            input.iter().sum()
+        }
+    }
+
+    impl Length for Checkout {
+        type Len = L<3>;
+    }
+
+    // writes the vectors to a temp file, which Miri's isolation doesn't allow
+    #[cfg_attr(miri, ignore)]
+    #[test]
+    fn golden_vectors() {
+        let (total, vectors) = Checkout::record_golden("1.20, 3.331, x".to_owned());
+        assert_eq!(total, 453);
+        assert_eq!(vectors.stages.len(), 3);
+
+        let path = std::env::temp_dir().join(format!("chain_link_golden_{}.bin", std::process::id()));
+        vectors.save(&path).unwrap();
+        let vectors = GoldenVectors::load(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert!(Checkout::verify_golden(&vectors).is_empty());
+
+        ROUND_UP.store(true, Ordering::SeqCst);
+        assert_eq!(Checkout::verify_golden(&vectors), [1]);
+
+        let truncated = GoldenVectors { stages: vectors.stages[..2].to_vec() };
+        assert_eq!(Checkout::verify_golden(&truncated), [1, 2]);
+    }
+}
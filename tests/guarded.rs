@@ -0,0 +1,66 @@
+#[cfg(test)]
+pub mod tests {
+
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use chain_link::*;
+
+    static COLLAPSED: AtomicUsize = AtomicUsize::new(0);
+
+    /// Trims, collapses runs of whitespace, then counts the words. Collapsing allocates a new
+    /// string, which is wasted work when there's nothing to collapse.
+    struct Words;
+
+    impl Chain<0> for Words {
+        type In<'a> = &'a str;
+        type Out<'a> = String;
+
+        fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+            input.trim().to_owned()
+        }
+    }
+
+    impl Chain<1> for Words {
+        type In<'a> = String;
+        type Out<'a> = String;
+
+        fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+            COLLAPSED.fetch_add(1, Ordering::Relaxed);
+            input.split_whitespace().collect::<Vec<_>>().join(" ")
+        }
+    }
+
+    impl GuardedStage<1> for Words {
+        fn needed(input: &String) -> bool {
+            input.contains("  ") || input.contains(['\t', '\n'])
+        }
+    }
+
+    impl Chain<2> for Words {
+        type In<'a> = String;
+        type Out<'a> = (String, usize);
+
+        fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+            let count = input.split(' ').filter(|word| !word.is_empty()).count();
+            (input, count)
+        }
+    }
+
+    impl Length for Words {
+        type Len = L<3>;
+    }
+
+    #[test]
+    fn guarded_stage() {
+        type Cheap = Guarded<Words, 1>;
+        assert_eq!(Cheap::cascade(" a b c "), ("a b c".to_owned(), 3));
+        assert_eq!(Cheap::cascade("one"), ("one".to_owned(), 1));
+        assert_eq!(COLLAPSED.load(Ordering::Relaxed), 0);
+
+        assert_eq!(Cheap::cascade("a  b\tc"), ("a b c".to_owned(), 3));
+        assert_eq!(COLLAPSED.load(Ordering::Relaxed), 1);
+
+        assert_eq!(Words::cascade("one"), ("one".to_owned(), 1));
+        assert_eq!(COLLAPSED.load(Ordering::Relaxed), 2);
+    }
+}
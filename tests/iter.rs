@@ -0,0 +1,116 @@
+#[cfg(test)]
+pub mod tests {
+
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use chain_link::*;
+
+    static LOOKUPS: AtomicUsize = AtomicUsize::new(0);
+
+    /// Looks up a country code, which is expensive enough to be worth not repeating.
+    struct Country;
+
+    impl Chain<0> for Country {
+        type In<'a> = &'a str;
+        type Out<'a> = String;
+
+        fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+            LOOKUPS.fetch_add(1, Ordering::Relaxed);
+            input.to_uppercase()
+        }
+    }
+
+    impl Chain<1> for Country {
+        type In<'a> = String;
+        type Out<'a> = String;
+
+        fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+            match input.as_str() {
+                "DE" => "Germany".to_owned(),
+                "FR" => "France".to_owned(),
+                _ => "unknown".to_owned(),
+            }
+        }
+    }
+
+    impl Length for Country {
+        type Len = L<2>;
+    }
+
+    #[test]
+    fn cascade_iter_cached() {
+        let codes = ["de", "fr", "de", "de", "xx", "fr"];
+
+        let uncached: Vec<_> = Country::cascade_iter(codes).collect();
+        assert_eq!(LOOKUPS.swap(0, Ordering::Relaxed), 6);
+
+        let cached: Vec<_> = Country::cascade_iter_cached(codes).collect();
+        assert_eq!(LOOKUPS.swap(0, Ordering::Relaxed), 3);
+        assert_eq!(cached, uncached);
+        assert_eq!(cached, ["Germany", "France", "Germany", "Germany", "unknown", "France"]);
+
+        // lazy, so nothing runs until it's advanced
+        let mut iter = Country::cascade_iter_cached(codes);
+        assert_eq!(LOOKUPS.load(Ordering::Relaxed), 0);
+        assert_eq!(iter.next().as_deref(), Some("Germany"));
+        assert_eq!(LOOKUPS.load(Ordering::Relaxed), 1);
+    }
+
+    /// Each input says how many seconds its lookup takes on the clock, so the stream runs at a
+    /// known rate: three outputs in the first second, none in the next two, one after that.
+    #[test]
+    fn cascade_iter_monitored() {
+        use std::time::Duration;
+
+        static CLOCK: ManualClock = ManualClock::new();
+
+        struct Slow;
+
+        impl Chain<0> for Slow {
+            type In<'a> = u64;
+            type Out<'a> = u64;
+
+            fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+                CLOCK.advance(Duration::from_secs(input));
+                input
+            }
+        }
+
+        impl Length for Slow {
+            type Len = L<1>;
+        }
+
+        let (outputs, monitor) = Slow::cascade_iter_monitored([0, 0, 0, 3], &CLOCK);
+        assert!(monitor.samples().is_empty());
+        let mut outputs = outputs.peekable();
+        outputs.peek();
+        assert_eq!(monitor.samples(), [1]);
+
+        assert_eq!(outputs.count(), 4);
+        assert_eq!(monitor.samples(), [3, 0, 0, 1]);
+        assert_eq!(monitor.sparkline(), "@__:");
+    }
+
+    /// Lookups from the express queue jump ahead of the ones already waiting in the standard
+    /// queue, while round robin takes turns between the two.
+    #[test]
+    fn cascade_merge() {
+        use std::sync::mpsc::channel;
+
+        let (express, express_rx) = channel();
+        let (standard, standard_rx) = channel();
+        standard.send("de").unwrap();
+        standard.send("xx").unwrap();
+
+        let streams = [express_rx.try_iter(), standard_rx.try_iter()];
+        let mut lookups = Country::cascade_merge(streams, MergeStrategy::Priority);
+        assert_eq!(lookups.next().unwrap(), "Germany");
+        express.send("fr").unwrap();
+        express.send("fr").unwrap();
+        assert_eq!(lookups.collect::<Vec<_>>(), ["France", "France", "unknown"]);
+
+        let streams = [vec!["fr", "fr", "fr"], vec!["de", "xx"]];
+        let lookups: Vec<_> = Country::cascade_merge(streams, MergeStrategy::RoundRobin).collect();
+        assert_eq!(lookups, ["France", "Germany", "France", "unknown", "France"]);
+    }
+}
@@ -3,61 +3,66 @@ pub mod tests {
     
     use chain_link::*;
 
-    /// Each link in a chain has input corresponding to the lower link's output.
-    /// Cascade is: -1.5f32 -> -1i32 -> 4_294_967_295u32 -> "4,294,967,295".
-    /// TODO it's suboptimal that we need to specify `Self::In<'_>` and `Self::Out<'_>`.
-    ///      for some reason naming the types explicitly won't work here despite not having lifetimes.
-    #[test]
-    fn chain_link_cascade() {
-
-        struct Pipeline;
+    struct Pipeline;
 
-        impl Chain<0> for Pipeline {
-            type In<'a> = f32;
-            type Out<'a> = i32;
-        
-            fn chain(input: Self::In<'_>) -> Self::Out<'_> {
-                assert_eq!(input, -1.5);
-                input as i32
-            }
+    impl Chain<0> for Pipeline {
+        type In<'a> = f32;
+        type Out<'a> = i32;
+    
+        fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+            assert_eq!(input, -1.5);
+            input as i32
         }
+    }
 
-        impl Chain<1> for Pipeline {
-            type In<'a> = i32;
-            type Out<'a> = u32;
-        
-            fn chain(input: Self::In<'_>) -> Self::Out<'_> {
-                assert_eq!(input, -1);
-                input as u32
-            }
+    impl Chain<1> for Pipeline {
+        type In<'a> = i32;
+        type Out<'a> = u32;
+    
+        fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+            assert_eq!(input, -1);
+            input as u32
         }
+    }
 
-        impl Chain<2> for Pipeline {
-            type In<'a> = u32;
-            type Out<'a> = String;
+    impl Chain<2> for Pipeline {
+        type In<'a> = u32;
+        type Out<'a> = String;
 
-            fn chain(input: Self::In<'_>) -> Self::Out<'_> {
-                assert_eq!(input, u32::MAX);
-                let mut output = String::new();
-                let mut n = input;
-                while n >= 1_000 {
-                    output = format!(",{:03}{}", n % 1_000, output);
-                    n /= 1_000;
-                }
-                format!("{n}{}", output)
+        fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+            assert_eq!(input, u32::MAX);
+            let mut output = String::new();
+            let mut n = input;
+            while n >= 1_000 {
+                output = format!(",{:03}{}", n % 1_000, output);
+                n /= 1_000;
             }
+            format!("{n}{}", output)
         }
+    }
 
-        impl Length for Pipeline {
-            type Len = L<3>;
-        }
-        
+    impl Length for Pipeline {
+        type Len = L<3>;
+    }
+
+    /// Each link in a chain has input corresponding to the lower link's output.
+    /// Cascade is: -1.5f32 -> -1i32 -> 4_294_967_295u32 -> "4,294,967,295".
+    /// TODO it's suboptimal that we need to specify `Self::In<'_>` and `Self::Out<'_>`.
+    ///      for some reason naming the types explicitly won't work here despite not having lifetimes.
+    #[test]
+    fn chain_link_cascade() {
         let input = -1.5;
         let actual = Pipeline::cascade(input);
         let expected = "4,294,967,295";
         assert_eq!(actual, expected);
     }
 
+    #[test]
+    fn type_flow() {
+        assert_eq!(Pipeline::type_flow(), "f32 -> i32 -> u32 -> String");
+        assert_eq!(Pipeline::stage_infos()[2].output, std::any::type_name::<String>());
+    }
+
     #[test]
     fn portability_example() {
 
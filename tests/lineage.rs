@@ -0,0 +1,94 @@
+#[cfg(test)]
+#[cfg(feature = "openlineage")]
+pub mod tests {
+
+    use chain_link::*;
+    use serde_json::Value;
+
+    /// Same conversions as the `chain_link_cascade` test: f32 -> i32 -> u32 -> "4,294,967,295".
+    struct Pipeline;
+
+    impl Chain<0> for Pipeline {
+        type In<'a> = f32;
+        type Out<'a> = i32;
+
+        fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+            input as i32
+        }
+    }
+
+    impl Chain<1> for Pipeline {
+        type In<'a> = i32;
+        type Out<'a> = u32;
+
+        fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+            input as u32
+        }
+    }
+
+    impl Chain<2> for Pipeline {
+        type In<'a> = u32;
+        type Out<'a> = String;
+
+        fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+            let mut output = String::new();
+            let mut n = input;
+            while n >= 1_000 {
+                output = format!(",{:03}{}", n % 1_000, output);
+                n /= 1_000;
+            }
+            format!("{n}{output}")
+        }
+    }
+
+    impl Length for Pipeline {
+        type Len = L<3>;
+    }
+
+    impl Named for Pipeline {
+        fn stage_name(index: usize) -> Option<&'static str> {
+            ["truncate", "reinterpret", "group_digits"].get(index).copied()
+        }
+    }
+
+    fn string<'a>(value: &'a Value, key: &str) -> &'a str {
+        value[key].as_str().unwrap_or_else(|| panic!("`{key}` should be a string in {value}"))
+    }
+
+    /// Checks the properties the OpenLineage spec requires of a dataset and its schema facet.
+    fn assert_dataset(dataset: &Value) -> (String, String) {
+        string(dataset, "namespace");
+        let schema = &dataset["facets"]["schema"];
+        assert!(string(schema, "_producer").starts_with("https://"));
+        assert!(string(schema, "_schemaURL").ends_with("#/$defs/SchemaDatasetFacet"));
+        let fields = schema["fields"].as_array().unwrap();
+        assert_eq!(fields.len(), 1);
+        (string(dataset, "name").to_owned(), string(&fields[0], "type").to_owned())
+    }
+
+    /// Checks the properties the OpenLineage spec requires of a `JobEvent`, returning the job
+    /// name and its input and output datasets.
+    fn assert_job_event(event: &Value) -> (String, (String, String), (String, String)) {
+        assert_eq!(string(event, "eventTime"), "2024-01-01T00:00:00Z");
+        assert!(string(event, "producer").starts_with("https://"));
+        assert!(string(event, "schemaURL").ends_with("/OpenLineage.json#/$defs/JobEvent"));
+        assert_eq!(string(&event["job"], "namespace"), "numbers");
+        assert!(event.get("run").is_none());
+        let inputs = event["inputs"].as_array().unwrap();
+        let outputs = event["outputs"].as_array().unwrap();
+        assert_eq!((inputs.len(), outputs.len()), (1, 1));
+        (string(&event["job"], "name").to_owned(), assert_dataset(&inputs[0]), assert_dataset(&outputs[0]))
+    }
+
+    #[test]
+    fn openlineage_events() {
+        let events = Pipeline::openlineage_events("numbers", "2024-01-01T00:00:00Z");
+        let events: Vec<_> = events.iter().map(assert_job_event).collect();
+        let dataset = |name: &str, ty: &str| (name.to_owned(), ty.to_owned());
+        assert_eq!(events, [
+            ("Pipeline.truncate".to_owned(), dataset("Pipeline.in", "f32"), dataset("Pipeline.truncate.out", "i32")),
+            ("Pipeline.reinterpret".to_owned(), dataset("Pipeline.truncate.out", "i32"), dataset("Pipeline.reinterpret.out", "u32")),
+            ("Pipeline.group_digits".to_owned(), dataset("Pipeline.reinterpret.out", "u32"), dataset("Pipeline.group_digits.out", "String")),
+        ]);
+    }
+}
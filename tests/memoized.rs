@@ -0,0 +1,92 @@
+#[cfg(test)]
+pub mod tests {
+
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    use chain_link::*;
+
+    static CLOCK: ManualClock = ManualClock::new();
+    static LOOKUPS: AtomicUsize = AtomicUsize::new(0);
+
+    /// Quotes the exchange rate of a currency. Looking the rate up is slow and it only changes
+    /// every so often, so it's fine to reuse for a minute.
+    struct Quote;
+
+    impl Chain<0> for Quote {
+        type In<'a> = &'a str;
+        type Out<'a> = String;
+
+        fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+            input.trim().to_uppercase()
+        }
+    }
+
+    impl Chain<1> for Quote {
+        type In<'a> = String;
+        type Out<'a> = f64;
+
+        fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+            LOOKUPS.fetch_add(1, Ordering::Relaxed);
+            match input.as_str() {
+                "EUR" => 1.25,
+                "GBP" => 1.5,
+                _ => 1.0,
+            }
+        }
+    }
+
+    impl TtlMemoize<1> for Quote {
+        type Key = String;
+        type Value = f64;
+        type Clock = &'static ManualClock;
+
+        fn key(input: &String) -> String {
+            input.clone()
+        }
+
+        fn cache() -> &'static TtlCache<String, f64, &'static ManualClock> {
+            static CACHE: TtlCache<String, f64, &ManualClock> = TtlCache::new(Duration::from_secs(60), &CLOCK);
+            &CACHE
+        }
+    }
+
+    impl Chain<2> for Quote {
+        type In<'a> = f64;
+        type Out<'a> = String;
+
+        fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+            format!("{input:.2}")
+        }
+    }
+
+    impl Length for Quote {
+        type Len = L<3>;
+    }
+
+    #[test]
+    fn ttl_memoized_stage() {
+        type Cached = TtlMemoized<Quote, 1>;
+        assert!(Quote::cache().is_empty());
+
+        assert_eq!(Cached::cascade("eur"), "1.25");
+        assert_eq!(Cached::cascade(" EUR "), "1.25");
+        assert_eq!(LOOKUPS.load(Ordering::Relaxed), 1);
+
+        assert_eq!(Cached::cascade("gbp"), "1.50");
+        assert_eq!(LOOKUPS.load(Ordering::Relaxed), 2);
+
+        CLOCK.advance(Duration::from_secs(59));
+        assert_eq!(Cached::cascade("eur"), "1.25");
+        assert_eq!(LOOKUPS.load(Ordering::Relaxed), 2);
+
+        // both entries are stale now, so the lookup runs again and the other one is dropped
+        CLOCK.advance(Duration::from_secs(1));
+        assert_eq!(Cached::cascade("eur"), "1.25");
+        assert_eq!(LOOKUPS.load(Ordering::Relaxed), 3);
+        assert_eq!(Quote::cache().len(), 1);
+
+        assert_eq!(Quote::cascade("eur"), "1.25");
+        assert_eq!(LOOKUPS.load(Ordering::Relaxed), 4);
+    }
+}
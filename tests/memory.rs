@@ -0,0 +1,139 @@
+#[cfg(test)]
+pub mod tests {
+
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    use chain_link::*;
+
+    static FORMATTED: AtomicUsize = AtomicUsize::new(0);
+
+    /// Repeats a word, splits it into chars, then joins them back with dashes. Each link is
+    /// modest on its own, but together they add up.
+    struct Blowup;
+
+    impl Chain<0> for Blowup {
+        type In<'a> = (String, usize);
+        type Out<'a> = String;
+
+        fn chain((word, times): Self::In<'_>) -> Self::Out<'_> {
+            word.repeat(times)
+        }
+    }
+
+    impl Chain<1> for Blowup {
+        type In<'a> = String;
+        type Out<'a> = Vec<char>;
+
+        fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+            input.chars().collect()
+        }
+    }
+
+    impl Chain<2> for Blowup {
+        type In<'a> = Vec<char>;
+        type Out<'a> = String;
+
+        fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+            FORMATTED.fetch_add(1, Ordering::SeqCst);
+            input.iter().map(char::to_string).collect::<Vec<_>>().join("-")
+        }
+    }
+
+    impl Length for Blowup {
+        type Len = L<3>;
+    }
+
+    #[test]
+    fn memory_budget() {
+        assert_eq!(
+            Blowup::cascade_budgeted(("ab".to_owned(), 2), 1_000),
+            Ok("a-b-a-b".to_owned()),
+        );
+        assert_eq!(FORMATTED.load(Ordering::SeqCst), 1);
+
+        // 100 bytes of string, then 400 bytes of chars, each fine alone but not together
+        let exceeded = Blowup::cascade_budgeted(("ab".to_owned(), 50), 500).unwrap_err();
+        assert_eq!(exceeded.stage, 1);
+        assert_eq!(exceeded.used, size_of::<String>() + 100);
+        // collecting may over-allocate, which counts too
+        assert!(exceeded.requested >= size_of::<Vec<char>>() + 400);
+        assert_eq!(FORMATTED.load(Ordering::SeqCst), 1);
+
+        let mut budget = MemoryBudget::new(10);
+        assert_eq!(budget.charge(0, 6), Ok(()));
+        assert!(budget.charge(1, 6).is_err());
+        assert_eq!(budget.remaining(), 4);
+    }
+
+    /// Repeats a word, then splits it into chars, only keeping a preview of the first few once
+    /// memory runs low.
+    struct Preview;
+
+    impl Chain<0> for Preview {
+        type In<'a> = (String, usize);
+        type Out<'a> = String;
+
+        fn chain((word, times): Self::In<'_>) -> Self::Out<'_> {
+            assert!(times > 0, "nothing to preview");
+            word.repeat(times)
+        }
+    }
+
+    impl Chain<1> for Preview {
+        type In<'a> = String;
+        type Out<'a> = Vec<char>;
+
+        fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+            input.chars().collect()
+        }
+    }
+
+    impl Length for Preview {
+        type Len = L<2>;
+    }
+
+    impl Degradable<1> for Preview {
+        fn degraded(input: Self::In<'_>) -> Self::Out<'_> {
+            input.chars().take(4).collect()
+        }
+    }
+
+    #[test]
+    fn governed() {
+        let generous = Budgets::new(10_000, Duration::from_secs(60));
+        let (out, degraded) = Degraded::<Preview, 1>::cascade_governed(&generous, ("ab".to_owned(), 3)).unwrap();
+        assert_eq!(out.into_iter().collect::<String>(), "ababab");
+        assert!(degraded.is_empty());
+
+        // 24 bytes of string header and 240 of text pass half of the budget, so only a preview of
+        // the chars is kept instead of running out
+        let tight = Budgets::new(500, Duration::from_secs(60)).degrade_at(0.5);
+        assert!(Blowup::cascade_budgeted(("ab".to_owned(), 120), 500).is_err());
+        let (out, degraded) = Degraded::<Preview, 1>::cascade_governed(&tight, ("ab".to_owned(), 120)).unwrap();
+        assert_eq!(out.into_iter().collect::<String>(), "abab");
+        assert_eq!(degraded, [1]);
+
+        // degrading doesn't help when the first link alone is too big
+        let exceeded = Degraded::<Preview, 1>::cascade_governed(&tight, ("ab".to_owned(), 300)).unwrap_err();
+        assert!(matches!(exceeded, OverBudget::Memory(BudgetExceeded { stage: 0, .. })));
+
+        // no time to spare degrades from the start, but only the link that can
+        static CLOCK: ManualClock = ManualClock::new();
+        let hurried = Budgets::new(10_000, Duration::ZERO).with_clock(&CLOCK);
+        let (_, degraded) = Degraded::<Preview, 1>::cascade_governed(&hurried, ("ab".to_owned(), 3)).unwrap();
+        assert_eq!(degraded, [1]);
+
+        // a link that panics under pressure doesn't leave it behind for the next cascade
+        let empty = || Degraded::<Preview, 1>::cascade_governed(&hurried, ("ab".to_owned(), 0));
+        assert!(std::panic::catch_unwind(empty).is_err());
+
+        // outside a governed cascade the link always runs in full
+        let out = Degraded::<Preview, 1>::cascade(("ab".to_owned(), 120));
+        assert_eq!(out.len(), 240);
+
+        let budgets = Budgets::new(500, Duration::from_secs(60));
+        assert!(std::panic::catch_unwind(|| budgets.degrade_at(1.5)).is_err());
+        assert!(std::panic::catch_unwind(|| budgets.degrade_at(f64::NAN)).is_err());
+    }
+}
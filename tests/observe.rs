@@ -0,0 +1,418 @@
+#[cfg(test)]
+pub mod tests {
+
+    use chain_link::*;
+
+    struct Pipeline;
+
+    impl Chain<0> for Pipeline {
+        type In<'a> = &'a str;
+        type Out<'a> = u32;
+
+        fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+            input.parse().unwrap()
+        }
+    }
+
+    impl Chain<1> for Pipeline {
+        type In<'a> = u32;
+        type Out<'a> = u64;
+
+        fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+            std::thread::sleep(std::time::Duration::from_millis(5));
+            input as u64 * 1_000
+        }
+    }
+
+    impl Chain<2> for Pipeline {
+        type In<'a> = u64;
+        type Out<'a> = String;
+
+        fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+            format!("{input}ms")
+        }
+    }
+
+    impl Length for Pipeline {
+        type Len = L<3>;
+    }
+
+    #[test]
+    fn cascade_profiled() {
+        let (out, timings) = Pipeline::cascade_profiled("3");
+        assert_eq!(out, "3000ms");
+        let stages: Vec<_> = timings.iter().map(|timing| timing.stage).collect();
+        assert_eq!(stages, [
+            StageInfo { index: 0, input: "&str", output: "u32" },
+            StageInfo { index: 1, input: "u32", output: "u64" },
+            StageInfo { index: 2, input: "u64", output: "alloc::string::String" },
+        ]);
+        assert!(timings[1].elapsed >= std::time::Duration::from_millis(5));
+    }
+
+    #[test]
+    fn cascade_iter_with_percentiles() {
+        let inputs = ["1", "2", "3", "4", "5", "6", "7", "8", "9", "10"];
+        let (outs, report) = Pipeline::cascade_iter_with_percentiles(inputs);
+        assert_eq!(outs.len(), 10);
+        assert_eq!(outs[9], "10000ms");
+        let indices: Vec<_> = report.iter().map(|stage| stage.stage.index).collect();
+        assert_eq!(indices, [0, 1, 2]);
+        for stage in &report {
+            assert_eq!(stage.samples, 10);
+            assert!(stage.p50 <= stage.p90 && stage.p90 <= stage.p99);
+        }
+        assert!(report[1].p50 >= std::time::Duration::from_millis(5));
+
+        let timings = (1..=100).rev().map(|millis| StageTiming {
+            stage: report[0].stage,
+            elapsed: std::time::Duration::from_millis(millis),
+        });
+        let [only] = stage_percentiles(timings)[..] else { panic!("expected a single stage") };
+        assert_eq!(only.samples, 100);
+        assert_eq!(only.p50.as_millis(), 50);
+        assert_eq!(only.p90.as_millis(), 90);
+        assert_eq!(only.p99.as_millis(), 99);
+    }
+
+    /// Link 1 sleeps for 5ms, well over its 1ms SLA, while the others are nowhere near theirs.
+    #[test]
+    fn cascade_with_sla() {
+        use std::time::Duration;
+
+        let slas = Slas::new()
+            .stage(0, Duration::from_secs(1))
+            .stage(1, Duration::from_millis(1))
+            .stage(2, Duration::from_secs(1));
+        let mut violations = Vec::new();
+        let out = Pipeline::cascade_with_sla(&slas, |violation| violations.push(violation), "3");
+        assert_eq!(out, "3000ms");
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].stage.index, 1);
+        assert_eq!(violations[0].limit, Duration::from_millis(1));
+        assert!(violations[0].elapsed >= Duration::from_millis(5));
+
+        let lenient = Slas::new().stage(1, Duration::from_secs(1));
+        Pipeline::cascade_with_sla(&lenient, |violation| panic!("unexpected {violation:?}"), "3");
+    }
+
+    impl Named for Pipeline {
+        fn stage_name(index: usize) -> Option<&'static str> {
+            match index {
+                0 => Some("parse"),
+                2 => Some("format"),
+                _ => None,
+            }
+        }
+    }
+
+    #[test]
+    fn cascade_report() {
+        let (out, report) = Pipeline::cascade_report("3");
+        assert_eq!(out, "3000ms");
+        let lines: Vec<_> = report.lines().collect();
+        assert_eq!(lines.len(), 4);
+        assert!(lines[0].starts_with("stage 0 (parse): &str -> u32 in "));
+        assert!(lines[1].starts_with("stage 1: u32 -> u64 in "));
+        assert!(lines[2].starts_with("stage 2 (format): u64 -> alloc::string::String in "));
+        assert!(lines[3].starts_with("total: "));
+    }
+
+    #[test]
+    fn checkpoints_by_name() {
+        let mut checkpoints = Checkpoints::<Pipeline>::from_config("format, missing,");
+        assert_eq!(Pipeline::observed_cascade("3", &mut checkpoints), "3000ms");
+        assert_eq!(checkpoints.dumps.len(), 1);
+        assert!(checkpoints.dumps[0].starts_with("checkpoint format: stage 2 u64 -> alloc::string::String in "));
+
+        checkpoints.disable("format");
+        checkpoints.enable("parse");
+        checkpoints.dumps.clear();
+        Pipeline::observed_cascade("4", &mut checkpoints);
+        assert_eq!(checkpoints.dumps.len(), 1);
+        assert!(checkpoints.dumps[0].starts_with("checkpoint parse: stage 0 "));
+    }
+
+    #[test]
+    fn sampled_instrumentation() {
+
+        // `Pipeline` sleeps, which adds up over thousands of runs
+        struct Cheap;
+
+        impl Chain<0> for Cheap {
+            type In<'a> = u32;
+            type Out<'a> = u32;
+
+            fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+                input + 1
+            }
+        }
+
+        impl Chain<1> for Cheap {
+            type In<'a> = u32;
+            type Out<'a> = u32;
+
+            fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+                input * 2
+            }
+        }
+
+        impl Length for Cheap {
+            type Len = L<2>;
+        }
+
+        let mut sampled = Sampled::<Profiler, 25>::new(Profiler::new());
+        for _ in 0..4_000 {
+            Cheap::observed_cascade(1, &mut sampled);
+        }
+        let timings = &sampled.inner().timings;
+        // every sampled run is profiled in full
+        assert_eq!(timings.len() % 2, 0);
+        let runs = timings.len() / 2;
+        assert!((800..1_200).contains(&runs), "sampled {runs} of 4000 runs");
+
+        let mut never = Sampled::<Profiler, 0>::new(Profiler::new());
+        let mut always = Sampled::<Profiler, 100>::new(Profiler::new());
+        for _ in 0..10 {
+            Cheap::observed_cascade(1, &mut (&mut never, &mut always));
+        }
+        assert_eq!(never.into_inner().timings.len(), 0);
+        assert_eq!(always.into_inner().timings.len(), 20);
+    }
+
+    /// `Outer` hands its middle link off to `Inner`, which should show up as frames nested under
+    /// that link rather than as siblings of it.
+    // compares real time against a sleep, while Miri's clock also ticks for every step it
+    // interprets, which easily outweighs the sleep
+    #[cfg_attr(miri, ignore)]
+    #[test]
+    fn folded_profile() {
+        struct Inner;
+
+        impl Chain<0> for Inner {
+            type In<'a> = u32;
+            type Out<'a> = u32;
+
+            fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+                std::thread::sleep(std::time::Duration::from_millis(2));
+                input + 1
+            }
+        }
+
+        impl Chain<1> for Inner {
+            type In<'a> = u32;
+            type Out<'a> = u32;
+
+            fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+                input * 2
+            }
+        }
+
+        impl Length for Inner {
+            type Len = L<2>;
+        }
+
+        struct Outer;
+
+        impl Chain<0> for Outer {
+            type In<'a> = &'a str;
+            type Out<'a> = u32;
+
+            fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+                input.parse().unwrap()
+            }
+        }
+
+        impl Chain<1> for Outer {
+            type In<'a> = u32;
+            type Out<'a> = u32;
+
+            fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+                Inner::cascade_nested(input)
+            }
+        }
+
+        impl Chain<2> for Outer {
+            type In<'a> = u32;
+            type Out<'a> = String;
+
+            fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+                input.to_string()
+            }
+        }
+
+        impl Length for Outer {
+            type Len = L<3>;
+        }
+
+        let (out, folded) = Outer::cascade_folded_profile("3");
+        assert_eq!(out, "8");
+        let samples: Vec<(&str, u128)> = folded
+            .lines()
+            .map(|line| {
+                let (stack, nanos) = line.rsplit_once(' ').unwrap();
+                (stack, nanos.parse().unwrap())
+            })
+            .collect();
+        let stacks: Vec<_> = samples.iter().map(|(stack, _)| *stack).collect();
+        assert_eq!(stacks, ["Outer[0]", "Outer[1]", "Outer[1];Inner[0]", "Outer[1];Inner[1]", "Outer[2]"]);
+        // the sleep is charged to the nested frame, not the link that called it
+        assert!(samples[2].1 >= 2_000_000);
+        assert!(samples[1].1 < samples[2].1);
+
+        // outside of a profile, nested cascades are just cascades
+        assert_eq!(Outer::cascade("3"), "8");
+    }
+
+    /// Linux only, since that's where the kernel says which cpu a thread last ran on.
+    #[cfg(all(feature = "core_affinity", target_os = "linux"))]
+    #[test]
+    fn cascade_pinned() {
+        use std::fs;
+
+        fn current_cpu() -> Option<usize> {
+            let stat = fs::read_to_string("/proc/thread-self/stat").ok()?;
+            // the command name can contain spaces, so count fields from after it
+            let fields: Vec<_> = stat.rsplit_once(')')?.1.split_whitespace().collect();
+            fields.get(36)?.parse().ok()
+        }
+
+        struct Where;
+
+        impl Chain<0> for Where {
+            type In<'a> = ();
+            type Out<'a> = Option<usize>;
+
+            fn chain(_: Self::In<'_>) -> Self::Out<'_> {
+                current_cpu()
+            }
+        }
+
+        impl Chain<1> for Where {
+            type In<'a> = Option<usize>;
+            type Out<'a> = (Option<usize>, Option<usize>);
+
+            fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+                (input, current_cpu())
+            }
+        }
+
+        impl Length for Where {
+            type Len = L<2>;
+        }
+
+        let cores = core_affinity::get_core_ids().unwrap();
+        let (first, last) = (cores[0], cores[cores.len() - 1]);
+        assert_eq!(Where::cascade_pinned(&[last, first], ()), (Some(last.id), Some(first.id)));
+    }
+
+    #[cfg(feature = "json_log")]
+    #[test]
+    fn json_log() {
+        use serde_json::Value;
+
+        let mut log = Vec::new();
+        assert_eq!(Pipeline::cascade_json_logged("3", &mut log), "3000ms");
+        let records: Vec<Value> = String::from_utf8(log)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(records.len(), 3);
+        assert_eq!(records[0]["stage"], 0);
+        assert_eq!(records[0]["name"], "parse");
+        assert_eq!(records[1]["name"], Value::Null);
+        assert_eq!(records[2]["output"], "alloc::string::String");
+        assert!(records.iter().all(|record| record["ok"] == true));
+        assert!(records[1]["duration_ns"].as_u64().unwrap() >= 5_000_000);
+
+        // "x" doesn't parse, so link 0 panics
+        let mut log = Vec::new();
+        let panicked = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            Pipeline::cascade_json_logged("x", &mut log);
+        }));
+        assert!(panicked.is_err());
+        let record: Value = serde_json::from_slice(&log).unwrap();
+        assert_eq!(record["stage"], 0);
+        assert_eq!(record["ok"], false);
+    }
+
+    #[cfg(feature = "opentelemetry")]
+    #[test]
+    fn otel_metrics() {
+        use opentelemetry::metrics::MeterProvider;
+        use opentelemetry_sdk::metrics::data::{AggregatedMetrics, MetricData};
+        use opentelemetry_sdk::metrics::{InMemoryMetricExporter, PeriodicReader, SdkMeterProvider};
+
+        let exporter = InMemoryMetricExporter::default();
+        let provider = SdkMeterProvider::builder()
+            .with_reader(PeriodicReader::builder(exporter.clone()).build())
+            .build();
+        let mut observer = OtelObserver::new(&provider.meter("pipeline"));
+        for input in ["1", "2"] {
+            Pipeline::observed_cascade(input, &mut observer);
+        }
+        provider.force_flush().unwrap();
+
+        let metrics = exporter.get_finished_metrics().unwrap();
+        let metrics: Vec<_> = metrics
+            .iter()
+            .flat_map(|resource| resource.scope_metrics())
+            .flat_map(|scope| scope.metrics())
+            .collect();
+
+        let invocations = metrics.iter().find(|m| m.name() == "chain_link.stage.invocations").unwrap();
+        let AggregatedMetrics::U64(MetricData::Sum(sum)) = invocations.data() else {
+            panic!("invocations should be a u64 sum");
+        };
+        let mut counts: Vec<_> = sum
+            .data_points()
+            .map(|point| (point.attributes().next().unwrap().value.as_str().into_owned(), point.value()))
+            .collect();
+        counts.sort();
+        assert_eq!(counts, [("0".into(), 2), ("1".into(), 2), ("2".into(), 2)]);
+
+        let duration = metrics.iter().find(|m| m.name() == "chain_link.stage.duration").unwrap();
+        assert_eq!(duration.unit(), "s");
+        let AggregatedMetrics::F64(MetricData::Histogram(histogram)) = duration.data() else {
+            panic!("duration should be a f64 histogram");
+        };
+        assert_eq!(histogram.data_points().count(), 3);
+        assert!(histogram.data_points().all(|point| point.count() == 2));
+    }
+
+    /// Reports to a registry of its own, which is then scraped like Prometheus would. The third
+    /// input doesn't parse, so link 0 panics and the links after it never run.
+    #[cfg(feature = "prometheus")]
+    #[test]
+    fn prometheus_metrics() {
+        use std::panic::{self, AssertUnwindSafe};
+
+        use prometheus::{Encoder, Registry, TextEncoder};
+
+        let registry = Registry::new();
+        let metrics = StageMetrics::register(&registry).unwrap();
+        assert!(StageMetrics::register(&registry).is_err());
+
+        assert_eq!(Pipeline::cascade_with_metrics("1", &metrics), "1000ms");
+        assert_eq!(Pipeline::cascade_with_metrics("2", &metrics), "2000ms");
+        let failed = panic::catch_unwind(AssertUnwindSafe(|| Pipeline::cascade_with_metrics("x", &metrics)));
+        assert!(failed.is_err());
+
+        let mut scraped = Vec::new();
+        TextEncoder::new().encode(&registry.gather(), &mut scraped).unwrap();
+        let scraped = String::from_utf8(scraped).unwrap();
+        let lines: Vec<_> = scraped.lines().collect();
+        for expected in [
+            r#"chain_link_stage_invocations_total{pipeline="Pipeline",stage="0"} 3"#,
+            r#"chain_link_stage_invocations_total{pipeline="Pipeline",stage="1"} 2"#,
+            r#"chain_link_stage_invocations_total{pipeline="Pipeline",stage="2"} 2"#,
+            r#"chain_link_stage_errors_total{pipeline="Pipeline",stage="0"} 1"#,
+            r#"chain_link_stage_duration_seconds_count{pipeline="Pipeline",stage="0"} 2"#,
+            r#"chain_link_stage_duration_seconds_count{pipeline="Pipeline",stage="1"} 2"#,
+        ] {
+            assert!(lines.contains(&expected), "missing `{expected}` in:\n{scraped}");
+        }
+        assert!(!scraped.contains(r#"chain_link_stage_errors_total{pipeline="Pipeline",stage="1"}"#));
+    }
+}
@@ -0,0 +1,72 @@
+#[cfg(test)]
+pub mod tests {
+
+    use std::sync::Mutex;
+
+    use chain_link::*;
+
+    static SKIPPED: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+    #[derive(Clone, Debug, PartialEq)]
+    struct User {
+        name: String,
+        country: Option<&'static str>,
+    }
+
+    /// Normalizes a user's name, looks up their country (which only knows about some users) and
+    /// then formats them. Link 1 isn't a regular `Chain<1>`, only wrapping in `Optional` makes it
+    /// one.
+    struct Users;
+
+    impl Homogeneous for Users {
+        type Item = User;
+    }
+
+    impl Chain<0> for Users {
+        type In<'a> = &'a str;
+        type Out<'a> = User;
+
+        fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+            User { name: input.trim().to_lowercase(), country: None }
+        }
+    }
+
+    impl Fallible<1> for Users {
+        type Error = String;
+
+        fn try_step(user: &User) -> Result<User, String> {
+            match user.name.as_str() {
+                "ada" => Ok(User { country: Some("GB"), ..user.clone() }),
+                name => Err(format!("no country for {name}")),
+            }
+        }
+
+        fn skipped(error: String) {
+            SKIPPED.lock().unwrap().push(error);
+        }
+    }
+
+    impl Chain<2> for Users {
+        type In<'a> = User;
+        type Out<'a> = String;
+
+        fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+            format!("{} ({})", input.name, input.country.unwrap_or("??"))
+        }
+    }
+
+    impl Length for Users {
+        type Len = L<3>;
+    }
+
+    #[test]
+    fn optional_stage_skipped_on_failure() {
+        type Pipeline = Optional<Users, 1>;
+        assert_eq!(Pipeline::cascade(" Ada "), "ada (GB)");
+        assert!(SKIPPED.lock().unwrap().is_empty());
+
+        // the lookup fails, so the user makes it through exactly as link 0 produced them
+        assert_eq!(Pipeline::cascade("Grace"), "grace (??)");
+        assert_eq!(*SKIPPED.lock().unwrap(), ["no country for grace"]);
+    }
+}
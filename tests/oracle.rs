@@ -0,0 +1,75 @@
+#[cfg(test)]
+#[cfg(feature = "proptest")]
+pub mod tests {
+
+    use std::panic;
+
+    use chain_link::*;
+    use proptest::num::f32;
+
+    /// Same conversions as the `chain_link_cascade` test: f32 -> i32 -> u32 -> "4,294,967,295".
+    struct Pipeline;
+
+    impl Chain<0> for Pipeline {
+        type In<'a> = f32;
+        type Out<'a> = i32;
+
+        fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+            input as i32
+        }
+    }
+
+    impl Chain<1> for Pipeline {
+        type In<'a> = i32;
+        type Out<'a> = u32;
+
+        fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+            input as u32
+        }
+    }
+
+    impl Chain<2> for Pipeline {
+        type In<'a> = u32;
+        type Out<'a> = String;
+
+        fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+            let mut output = String::new();
+            let mut n = input;
+            while n >= 1_000 {
+                output = format!(",{:03}{}", n % 1_000, output);
+                n /= 1_000;
+            }
+            format!("{n}{output}")
+        }
+    }
+
+    impl Length for Pipeline {
+        type Len = L<3>;
+    }
+
+    /// Groups the digits from the right instead of dividing.
+    fn reference(input: f32) -> String {
+        let digits = (input as i32 as u32).to_string();
+        let mut output = String::new();
+        for (i, digit) in digits.chars().enumerate() {
+            if i > 0 && (digits.len() - i).is_multiple_of(3) {
+                output.push(',');
+            }
+            output.push(digit);
+        }
+        output
+    }
+
+    // hundreds of cases with no unsafe in sight, far too slow under Miri
+    #[cfg_attr(miri, ignore)]
+    #[test]
+    fn matches_oracle() {
+        assert_matches_oracle::<Pipeline, _, _>(f32::ANY, reference);
+
+        // an oracle that forgot about the wrapping conversion to u32 gets caught
+        let wrong = panic::catch_unwind(|| {
+            assert_matches_oracle::<Pipeline, _, _>(f32::NEGATIVE, |x| reference(x.abs()));
+        });
+        assert!(wrong.is_err());
+    }
+}
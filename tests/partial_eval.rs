@@ -0,0 +1,73 @@
+#[cfg(test)]
+pub mod tests {
+
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::OnceLock;
+
+    use chain_link::*;
+
+    static PARSED: AtomicUsize = AtomicUsize::new(0);
+    static PRICED: AtomicUsize = AtomicUsize::new(0);
+
+    /// Parses a hardcoded price table and sorts it, then picks a price off it. Only the last
+    /// link has anything left to do once the table is built.
+    struct Prices;
+
+    impl Chain<0> for Prices {
+        type In<'a> = &'a str;
+        type Out<'a> = Vec<u32>;
+
+        fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+            PARSED.fetch_add(1, Ordering::Relaxed);
+            input.split(',').map(|price| price.parse().unwrap()).collect()
+        }
+    }
+
+    impl Chain<1> for Prices {
+        type In<'a> = Vec<u32>;
+        type Out<'a> = Vec<u32>;
+
+        fn chain(mut input: Self::In<'_>) -> Self::Out<'_> {
+            input.sort();
+            input
+        }
+    }
+
+    impl Chain<2> for Prices {
+        type In<'a> = Vec<u32>;
+        type Out<'a> = Option<u32>;
+
+        fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+            PRICED.fetch_add(1, Ordering::Relaxed);
+            input.get(input.len() / 2).copied()
+        }
+    }
+
+    impl Length for Prices {
+        type Len = L<3>;
+    }
+
+    impl ConstInput for Prices {
+        const INPUT: &'static str = "40,10,30,20,50";
+    }
+
+    impl FoldedPrefix<2> for Prices {
+        fn folded() -> &'static OnceLock<Vec<u32>> {
+            static FOLDED: OnceLock<Vec<u32>> = OnceLock::new();
+            &FOLDED
+        }
+    }
+
+    #[test]
+    fn partial_eval() {
+        assert_eq!(<Prices as PartialEval<2>>::runtime_links(), 1);
+        for _ in 0..3 {
+            assert_eq!(<Prices as PartialEval<2>>::cascade_partial(), Some(30));
+        }
+        assert_eq!(PARSED.load(Ordering::Relaxed), 1);
+        assert_eq!(PRICED.load(Ordering::Relaxed), 3);
+
+        assert_eq!(Prices::cascade(Prices::INPUT), Some(30));
+        assert_eq!(PARSED.load(Ordering::Relaxed), 2);
+    }
+}
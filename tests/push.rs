@@ -0,0 +1,60 @@
+#[cfg(test)]
+pub mod tests {
+
+    use chain_link::*;
+
+    /// Parses `key=value` records, one per line, and greets whoever they're about.
+    struct Greeting;
+
+    impl Chain<0> for Greeting {
+        type In<'a> = String;
+        type Out<'a> = Option<String>;
+
+        fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+            input
+                .split(';')
+                .find_map(|field| field.strip_prefix("name="))
+                .map(str::to_owned)
+        }
+    }
+
+    impl Chain<1> for Greeting {
+        type In<'a> = Option<String>;
+        type Out<'a> = String;
+
+        fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+            format!("hello {}", input.as_deref().unwrap_or("stranger"))
+        }
+    }
+
+    impl Length for Greeting {
+        type Len = L<2>;
+    }
+
+    #[test]
+    fn push_parser() {
+        let mut parser = PushParser::<Greeting, Lines>::new();
+        assert_eq!(parser.feed(b"age=3;na"), Ok(vec![]));
+        assert_eq!(parser.pending(), b"age=3;na");
+        assert_eq!(parser.feed(b"me=ann\r\n"), Ok(vec!["hello ann".to_owned()]));
+        assert!(parser.pending().is_empty());
+
+        assert_eq!(parser.feed(b"name=bo\n\nname=c"), Ok(vec!["hello bo".to_owned(), "hello stranger".to_owned()]));
+        assert_eq!(parser.finish(), Some("hello c".to_owned()));
+    }
+
+    /// A peer that never ends its line is cut off once the line passes the max, however it's
+    /// split up.
+    #[test]
+    fn unit_too_long() {
+        let mut parser = PushParser::<Greeting, Lines>::new().with_max_unit(8);
+        for byte in b"name=ann" {
+            assert_eq!(parser.feed(&[*byte]), Ok(vec![]));
+        }
+        assert_eq!(parser.feed(b"e"), Err(UnitTooLong { max: 8 }));
+        assert!(parser.pending().is_empty());
+
+        // a line that's done in time is fine, even if more than the max arrives at once
+        assert_eq!(parser.feed(b"name=bo\nname=c\n"), Ok(vec!["hello bo".to_owned(), "hello c".to_owned()]));
+    }
+}
@@ -0,0 +1,67 @@
+#[cfg(test)]
+pub mod tests {
+
+    use std::panic::{self, AssertUnwindSafe};
+
+    use chain_link::*;
+
+    /// Splits a line into fields, parses the second one, then averages the rest by it. Lines
+    /// with a 0 divisor blow up in the last link.
+    struct Averages;
+
+    impl Chain<0> for Averages {
+        type In<'a> = String;
+        type Out<'a> = Vec<String>;
+
+        fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+            input.split(',').map(str::to_owned).collect()
+        }
+    }
+
+    impl Chain<1> for Averages {
+        type In<'a> = Vec<String>;
+        type Out<'a> = (u32, Vec<u32>);
+
+        fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+            let fields: Vec<u32> = input.iter().map(|f| f.parse().unwrap()).collect();
+            (fields[0], fields[1..].to_vec())
+        }
+    }
+
+    impl Chain<2> for Averages {
+        type In<'a> = (u32, Vec<u32>);
+        type Out<'a> = u32;
+
+        fn chain((count, values): Self::In<'_>) -> Self::Out<'_> {
+            values.iter().sum::<u32>() / count
+        }
+    }
+
+    impl Length for Averages {
+        type Len = L<3>;
+    }
+
+    #[test]
+    fn minimize_and_replay() {
+        let mut log = ExecutionLog::new();
+        assert_eq!(Averages::cascade_recorded("2,3,5".to_owned(), &mut log), 4);
+        assert_eq!(log.failed_stage(), None);
+        assert_eq!(minimize(log), None);
+
+        let mut log = ExecutionLog::new();
+        let crashed = panic::catch_unwind(AssertUnwindSafe(|| {
+            Averages::cascade_recorded("0,3,5".to_owned(), &mut log)
+        }));
+        assert!(crashed.is_err());
+        assert_eq!(log.failed_stage(), Some(2));
+
+        // the log would come attached to a bug report
+        let log = ExecutionLog::from_bytes(&log.to_bytes()).unwrap();
+        let minimal = minimize(log).unwrap();
+        assert_eq!(minimal.stage, 2);
+        assert_eq!(minimal.replay::<1, Averages>(), Err(DecodeError));
+
+        let replayed = panic::catch_unwind(|| minimal.replay::<2, Averages>());
+        assert!(replayed.is_err());
+    }
+}
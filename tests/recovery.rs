@@ -0,0 +1,200 @@
+#[cfg(test)]
+pub mod tests {
+
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    use chain_link::*;
+
+    static LOOKUPS: AtomicU32 = AtomicU32::new(0);
+
+    /// Prices an order: parses the quantity, looks up a flaky price service, applies a discount
+    /// that only some orders qualify for, ships it, then adds a loyalty bonus. Each link recovers
+    /// from failure in its own way.
+    struct Order;
+
+    impl TryChain<0> for Order {
+        type Error = String;
+        type In<'a> = &'a str;
+        type Out<'a> = u32;
+
+        fn try_chain(input: Self::In<'_>) -> Result<Self::Out<'_>, String> {
+            input.parse().map_err(|_| format!("bad quantity `{input}`"))
+        }
+    }
+
+    impl RecoveryPolicy<0> for Order {
+        type Policy = Propagate;
+    }
+
+    impl TryChain<1> for Order {
+        type Error = String;
+        type In<'a> = u32;
+        type Out<'a> = u32;
+
+        /// Times out on every other call.
+        fn try_chain(quantity: Self::In<'_>) -> Result<Self::Out<'_>, String> {
+            match LOOKUPS.fetch_add(1, Ordering::Relaxed) % 2 {
+                0 => Err("price service timed out".to_owned()),
+                _ => Ok(quantity * 40),
+            }
+        }
+    }
+
+    impl RecoveryPolicy<1> for Order {
+        type Policy = Retry<2>;
+    }
+
+    impl TryChain<2> for Order {
+        type Error = String;
+        type In<'a> = u32;
+        type Out<'a> = u32;
+
+        fn try_chain(price: Self::In<'_>) -> Result<Self::Out<'_>, String> {
+            match price >= 100 {
+                true => Ok(price - 20),
+                false => Err("order too small for a discount".to_owned()),
+            }
+        }
+    }
+
+    impl RecoveryPolicy<2> for Order {
+        type Policy = Skip;
+    }
+
+    impl TryChain<3> for Order {
+        type Error = String;
+        type In<'a> = u32;
+        type Out<'a> = u32;
+
+        fn try_chain(price: Self::In<'_>) -> Result<Self::Out<'_>, String> {
+            match price <= 150 {
+                true => Ok(price + 5),
+                false => Err("too heavy for the courier".to_owned()),
+            }
+        }
+    }
+
+    impl AlternateChain<3> for Order {
+        fn alternate(price: Self::In<'_>) -> Result<Self::Out<'_>, String> {
+            Ok(price + 30)
+        }
+    }
+
+    impl RecoveryPolicy<3> for Order {
+        type Policy = Alternate;
+    }
+
+    impl TryChain<4> for Order {
+        type Error = String;
+        type In<'a> = u32;
+        type Out<'a> = (u32, u32);
+
+        fn try_chain(price: Self::In<'_>) -> Result<Self::Out<'_>, String> {
+            match price > 200 {
+                true => Ok((price, price / 100)),
+                false => Err("not eligible for loyalty points".to_owned()),
+            }
+        }
+    }
+
+    impl RecoveryPolicy<4> for Order {
+        type Policy = UseDefault;
+    }
+
+    impl Length for Order {
+        type Len = L<5>;
+    }
+
+    #[test]
+    fn recovery_policies() {
+        // retried past the timeout, too small for a discount, couriered, no points
+        assert_eq!(Order::cascade_recovering("2"), Ok((0, 0)));
+        // discounted, too heavy so shipped the other way, earns points
+        LOOKUPS.store(0, Ordering::Relaxed);
+        assert_eq!(Order::cascade_recovering("10"), Ok((410, 4)));
+        assert_eq!(LOOKUPS.load(Ordering::Relaxed), 2);
+        // retried past the timeout, discounted, couriered, no points
+        assert_eq!(Order::cascade_recovering("3"), Ok((0, 0)));
+        assert_eq!(Order::cascade_recovering("x"), Err("bad quantity `x`".to_owned()));
+    }
+
+    /// Charges a card through a provider that sometimes times out after the charge went through,
+    /// and dedupes charges by idempotency key the way payment APIs do.
+    #[test]
+    fn idempotent_retry() {
+        use std::cell::RefCell;
+        use std::collections::HashSet;
+
+        thread_local! {
+            static ATTEMPTS: RefCell<Vec<u32>> = const { RefCell::new(Vec::new()) };
+            static CHARGED: RefCell<HashSet<u32>> = RefCell::new(HashSet::new());
+        }
+        static KEYS: AtomicU32 = AtomicU32::new(0);
+
+        struct Payment;
+
+        impl TryChain<0> for Payment {
+            type Error = String;
+            type In<'a> = &'a str;
+            type Out<'a> = u32;
+
+            fn try_chain(input: Self::In<'_>) -> Result<Self::Out<'_>, String> {
+                input.parse().map_err(|_| format!("bad amount `{input}`"))
+            }
+        }
+
+        impl RecoveryPolicy<0> for Payment {
+            type Policy = Propagate;
+        }
+
+        impl TryChain<1> for Payment {
+            type Error = String;
+            type In<'a> = u32;
+            type Out<'a> = u32;
+
+            fn try_chain(amount: Self::In<'_>) -> Result<Self::Out<'_>, String> {
+                Self::try_chain_keyed(amount, &Self::idempotency_key(&amount))
+            }
+        }
+
+        impl IdempotentChain<1> for Payment {
+            type Key = u32;
+
+            fn idempotency_key(_: &u32) -> u32 {
+                KEYS.fetch_add(1, Ordering::Relaxed)
+            }
+
+            /// The first two attempts time out, the first one only after charging.
+            fn try_chain_keyed<'a>(amount: Self::In<'a>, key: &u32) -> Result<Self::Out<'a>, String> {
+                let attempt = ATTEMPTS.with_borrow_mut(|attempts| {
+                    attempts.push(*key);
+                    attempts.len()
+                });
+                if attempt == 2 {
+                    return Err("provider unreachable".to_owned());
+                }
+                CHARGED.with_borrow_mut(|charged| charged.insert(*key));
+                match attempt {
+                    1 => Err("provider timed out".to_owned()),
+                    _ => Ok(amount),
+                }
+            }
+        }
+
+        impl RecoveryPolicy<1> for Payment {
+            type Policy = IdempotentRetry<3>;
+        }
+
+        impl Length for Payment {
+            type Len = L<2>;
+        }
+
+        assert_eq!(Payment::cascade_recovering("25"), Ok(25));
+        assert_eq!(ATTEMPTS.with_borrow(Clone::clone), [0, 0, 0]);
+        assert_eq!(CHARGED.with_borrow(HashSet::len), 1);
+
+        assert_eq!(Payment::cascade_recovering("40"), Ok(40));
+        assert_eq!(ATTEMPTS.with_borrow(Clone::clone), [0, 0, 0, 1]);
+        assert_eq!(CHARGED.with_borrow(HashSet::len), 2);
+    }
+}
@@ -0,0 +1,163 @@
+#[cfg(test)]
+pub mod tests {
+
+    use chain_link::*;
+
+    /// Homogeneous pipeline over `String`, cascaded both at compile time via `Cascade` and at
+    /// runtime via the collected `Sequential::steps`.
+    #[test]
+    fn sequence_cascade() {
+
+        struct Shout;
+
+        impl Homogeneous for Shout {
+            type Item = String;
+        }
+
+        impl Sequence<0> for Shout {
+            fn step(item: String) -> String {
+                item.trim().to_owned()
+            }
+        }
+
+        impl Sequence<1> for Shout {
+            fn step(item: String) -> String {
+                item.to_uppercase()
+            }
+        }
+
+        impl Sequence<2> for Shout {
+            fn step(item: String) -> String {
+                format!("{item}!")
+            }
+        }
+
+        impl Length for Shout {
+            type Len = L<3>;
+        }
+
+        let actual = Shout::cascade("  hello ".to_owned());
+        assert_eq!(actual, "HELLO!");
+
+        let steps = Shout::steps();
+        assert_eq!(steps.len(), 3);
+        let actual = steps.iter().fold("  hello ".to_owned(), |item, step| step(item));
+        assert_eq!(actual, "HELLO!");
+    }
+
+    /// Every step leaves stray whitespace behind, which `Squash` cleans up after each one.
+    #[test]
+    fn map_each_step() {
+
+        struct Template;
+
+        impl Homogeneous for Template {
+            type Item = String;
+        }
+
+        impl Sequence<0> for Template {
+            fn step(item: String) -> String {
+                format!("  Dear {item} ,")
+            }
+        }
+
+        impl Sequence<1> for Template {
+            fn step(item: String) -> String {
+                item.replace(',', " ,  thanks  ")
+            }
+        }
+
+        impl Length for Template {
+            type Len = L<2>;
+        }
+
+        struct Squash;
+
+        impl Transform<String> for Squash {
+            fn apply(item: String) -> String {
+                item.split_whitespace().collect::<Vec<_>>().join(" ")
+            }
+        }
+
+        assert_eq!(Template::cascade("Ann".to_owned()), "  Dear Ann  ,  thanks  ");
+        type Squashed = MapEach<Template, Squash>;
+        assert_eq!(Squashed::cascade("Ann".to_owned()), "Dear Ann , thanks");
+        assert_eq!(Squashed::steps()[0]("Bo".to_owned()), "Dear Bo ,");
+    }
+
+    /// Each pass of Heron's method gets closer to the square root of 2, with the number of
+    /// passes only picked at runtime.
+    #[test]
+    fn cascade_repeat() {
+
+        struct Heron;
+
+        impl Homogeneous for Heron {
+            type Item = f64;
+        }
+
+        impl Sequence<0> for Heron {
+            fn step(x: f64) -> f64 {
+                (x + 2.0 / x) / 2.0
+            }
+        }
+
+        impl Length for Heron {
+            type Len = L<1>;
+        }
+
+        assert_eq!(Heron::cascade_repeat(1.0, 0), 1.0);
+        assert_eq!(Heron::cascade_repeat(1.0, 1), 1.5);
+        let errors: Vec<f64> = (1..5)
+            .map(|times| (Heron::cascade_repeat(1.0, times) - 2f64.sqrt()).abs())
+            .collect();
+        assert!(errors.windows(2).all(|pair| pair[1] < pair[0]));
+        assert!((Heron::cascade_repeat(1.0, 6) - 2f64.sqrt()).abs() < 1e-12);
+    }
+
+    #[cfg(feature = "tokio")]
+    #[test]
+    fn cascade_broadcast() {
+        use tokio::sync::broadcast;
+
+        struct Shout;
+
+        impl Homogeneous for Shout {
+            type Item = String;
+        }
+
+        impl Sequence<0> for Shout {
+            fn step(item: String) -> String {
+                item.trim().to_owned()
+            }
+        }
+
+        impl Sequence<1> for Shout {
+            fn step(item: String) -> String {
+                item.to_uppercase()
+            }
+        }
+
+        impl Sequence<2> for Shout {
+            fn step(item: String) -> String {
+                format!("{item}!")
+            }
+        }
+
+        impl Length for Shout {
+            type Len = L<3>;
+        }
+
+        let (sender, mut receiver) = broadcast::channel(8);
+        let actual = Shout::cascade_broadcast("  hello ".to_owned(), &sender);
+        assert_eq!(actual, "HELLO!");
+        let mut seen = Vec::new();
+        while let Ok(item) = receiver.try_recv() {
+            seen.push(item);
+        }
+        assert_eq!(seen, ["hello", "HELLO", "HELLO!"]);
+
+        drop(receiver);
+        assert_eq!(Shout::cascade_broadcast("quiet".to_owned(), &sender), "QUIET!");
+    }
+}
@@ -0,0 +1,385 @@
+#[cfg(test)]
+pub mod tests {
+
+    use chain_link::*;
+
+    /// Parses and doubles.
+    struct Double;
+
+    impl Chain<0> for Double {
+        type In<'a> = String;
+        type Out<'a> = i64;
+
+        fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+            input.trim().parse().unwrap_or(0)
+        }
+    }
+
+    impl Chain<1> for Double {
+        type In<'a> = i64;
+        type Out<'a> = String;
+
+        fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+            (input * 2).to_string()
+        }
+    }
+
+    impl Length for Double {
+        type Len = L<2>;
+    }
+
+    /// Same shape, one link and nothing to do with numbers.
+    struct Reverse;
+
+    impl Chain<0> for Reverse {
+        type In<'a> = String;
+        type Out<'a> = String;
+
+        fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+            input.chars().rev().collect()
+        }
+    }
+
+    impl Length for Reverse {
+        type Len = L<1>;
+    }
+
+    /// Runs any `String -> String` pipeline over every line.
+    fn per_line<P: CascadeOf<String, String>>(text: &str) -> Vec<String> {
+        text.lines().map(|line| P::cascade(line.to_owned())).collect()
+    }
+
+    /// Runs two pipelines of the same shape one after the other.
+    fn then<P: CascadeOf<String, String>, Q: CascadeOf<String, String>>(input: String) -> String {
+        Q::cascade(P::cascade(input))
+    }
+
+    assert_thread_safe!(Double);
+    assert_thread_safe!(Reverse);
+
+    #[test]
+    fn generic_over_shape() {
+        assert_eq!(per_line::<Double>("1\n21"), ["2", "42"]);
+        assert_eq!(per_line::<Reverse>("ab\ncd"), ["ba", "dc"]);
+        assert_eq!(then::<Double, Reverse>("256".to_owned()), "215");
+    }
+
+    /// `Double` is being replaced with a version that also handles hex, which agrees on plain
+    /// numbers but not on anything else.
+    #[test]
+    fn shadow_mode() {
+        use std::sync::mpsc;
+
+        struct DoubleHex;
+
+        impl Chain<0> for DoubleHex {
+            type In<'a> = String;
+            type Out<'a> = String;
+
+            fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+                let input = input.trim();
+                let n = match input.strip_prefix("0x") {
+                    Some(hex) => i64::from_str_radix(hex, 16).unwrap_or(0),
+                    None => input.parse().unwrap_or(0),
+                };
+                (n * 2).to_string()
+            }
+        }
+
+        impl Length for DoubleHex {
+            type Len = L<1>;
+        }
+
+        let worker = ShadowWorker::new(16);
+        let (sender, divergences) = mpsc::channel();
+        for input in ["21", "0x10", "7"] {
+            let sender = sender.clone();
+            let old = cascade_shadow::<Double, DoubleHex, _, _>(&worker, input.to_owned(), move |divergence| {
+                sender.send(divergence).unwrap();
+            });
+            // the caller always gets the old pipeline's answer
+            assert_eq!(old, Double::cascade(input.to_owned()));
+        }
+        drop(sender);
+
+        // every queued comparison holds a sender until it's done, so this waits for all of them
+        let logged: Vec<_> = divergences.iter().collect();
+        assert_eq!(logged, [Divergence { input: "0x10".to_owned(), old: "0".to_owned(), new: "32".to_owned() }]);
+        assert_eq!(worker.dropped(), 0);
+    }
+
+    #[test]
+    fn ab_split() {
+        let mut a = 0;
+        for n in 0..2_000 {
+            let (out, variant) = cascade_ab::<Double, Reverse, _, _>(0.3, n.to_string());
+            match variant {
+                Variant::A => {
+                    assert_eq!(out, (n * 2).to_string());
+                    a += 1;
+                }
+                Variant::B => assert_eq!(out, n.to_string().chars().rev().collect::<String>()),
+            }
+        }
+        assert!((500..700).contains(&a), "{a} of 2000 went to A");
+
+        let inputs: Vec<_> = (0..2_000).map(|n: u32| n.to_string()).collect();
+        let variants: Vec<_> = inputs
+            .iter()
+            .map(|input| cascade_ab_seeded::<Double, Reverse, _, _>(7, 0.3, input.clone()).1)
+            .collect();
+        let a = variants.iter().filter(|variant| **variant == Variant::A).count();
+        assert!((500..700).contains(&a), "{a} of 2000 went to A");
+        // the same input always gets the same variant for a given seed
+        for (input, variant) in inputs.iter().zip(&variants) {
+            assert_eq!(cascade_ab_seeded::<Double, Reverse, _, _>(7, 0.3, input.clone()).1, *variant);
+        }
+
+        assert_eq!(cascade_ab::<Double, Reverse, _, _>(1.0, "1".to_owned()).1, Variant::A);
+        assert_eq!(cascade_ab::<Double, Reverse, _, _>(0.0, "1".to_owned()).1, Variant::B);
+
+        let split = |ratio| move || cascade_ab_seeded::<Double, Reverse, _, _>(7, ratio, "1".to_owned());
+        assert!(std::panic::catch_unwind(split(1.5)).is_err());
+        assert!(std::panic::catch_unwind(split(f64::NAN)).is_err());
+    }
+
+    #[test]
+    fn definition_hash() {
+        /// Same links as `Double`, so it's the same definition as far as the hash can tell.
+        struct Twice;
+
+        impl Chain<0> for Twice {
+            type In<'a> = String;
+            type Out<'a> = i64;
+
+            fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+                input.trim().parse().unwrap_or(0)
+            }
+        }
+
+        impl Chain<1> for Twice {
+            type In<'a> = i64;
+            type Out<'a> = String;
+
+            fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+                (input + input).to_string()
+            }
+        }
+
+        impl Length for Twice {
+            type Len = L<2>;
+        }
+
+        /// `Double` with the number widened to an `i128`.
+        struct Wide;
+
+        impl Chain<0> for Wide {
+            type In<'a> = String;
+            type Out<'a> = i128;
+
+            fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+                input.trim().parse().unwrap_or(0)
+            }
+        }
+
+        impl Chain<1> for Wide {
+            type In<'a> = i128;
+            type Out<'a> = String;
+
+            fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+                (input * 2).to_string()
+            }
+        }
+
+        impl Length for Wide {
+            type Len = L<2>;
+        }
+
+        assert_eq!(Double::definition_hash(), Double::definition_hash());
+        assert_eq!(Double::definition_hash(), Twice::definition_hash());
+        assert_ne!(Double::definition_hash(), Wide::definition_hash());
+        assert_ne!(Double::definition_hash(), Reverse::definition_hash());
+        assert_ne!(Double::definition_hash_with("v2"), Double::definition_hash());
+        assert_eq!(Double::definition_hash_with("v2"), Twice::definition_hash_with("v2"));
+    }
+
+    /// Hex numbers need an extra link to strip their prefix, while decimal ones go straight
+    /// through `Double`, but both come out the other end as the same doubled decimal string.
+    #[test]
+    fn by_category() {
+        struct Hex;
+
+        impl Chain<0> for Hex {
+            type In<'a> = String;
+            type Out<'a> = String;
+
+            fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+                input.trim_start_matches("0x").to_owned()
+            }
+        }
+
+        impl Chain<1> for Hex {
+            type In<'a> = String;
+            type Out<'a> = i64;
+
+            fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+                i64::from_str_radix(&input, 16).unwrap_or(0)
+            }
+        }
+
+        impl Chain<2> for Hex {
+            type In<'a> = i64;
+            type Out<'a> = String;
+
+            fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+                (input * 2).to_string()
+            }
+        }
+
+        impl Length for Hex {
+            type Len = L<3>;
+        }
+
+        struct Number;
+
+        impl Categorized for Number {
+            type In = String;
+            type Out = String;
+
+            fn select(input: &String) -> SubPipeline<String, String> {
+                match input.starts_with("0x") {
+                    true => SubPipeline::of::<Hex>(),
+                    false => SubPipeline::of::<Double>(),
+                }
+            }
+        }
+
+        assert_eq!(per_line::<ByCategory<Number>>("21\n0x15\n0xff"), ["42", "42", "510"]);
+        assert_eq!(ByCategory::<Number>::stages(&"21".to_owned()), 2);
+        assert_eq!(ByCategory::<Number>::stages(&"0x15".to_owned()), 3);
+    }
+
+    /// `per_line` and `then` are tested without `Double` or `Reverse`, through mocks of the same
+    /// shape.
+    #[test]
+    fn mock_cascade() {
+        type Mock = MockCascade<String, String>;
+        struct Second;
+
+        {
+            let mock = Mock::returning("x".to_owned());
+            assert_eq!(per_line::<Mock>("a\nb\nc"), ["x", "x", "x"]);
+            assert_eq!(mock.calls(), 3);
+
+            let _first = Mock::with(|line| format!("<{line}>"));
+            let _second = MockCascade::<String, String, Second>::with(|line| line.to_uppercase());
+            assert_eq!(then::<Mock, MockCascade<String, String, Second>>("ab".to_owned()), "<AB>");
+            assert_eq!(mock.calls(), 3);
+        }
+
+        let unset = std::panic::catch_unwind(|| Mock::cascade("a".to_owned()));
+        assert!(unset.is_err());
+    }
+
+    /// Runs any pipeline on its own output. `P::Out` and `P::In` are unrelated as far as the
+    /// compiler knows here, so `P::cascade(P::cascade(input))` doesn't type check, and it's up to
+    /// the caller to supply the witness that they line up.
+    fn twice<P, W>(input: P::In<'_>) -> P::Out<'_>
+    where
+        P: Cascade,
+        W: for<'a> Witness<P::Out<'a>, P::In<'a>>,
+    {
+        Composed::<P, P, W>::cascade(input)
+    }
+
+    #[test]
+    fn composed_with_witness() {
+        assert_eq!(twice::<Double, Refl>("21".to_owned()), "84");
+        assert_eq!(Composed::<Double, Reverse>::cascade("21".to_owned()), "24");
+
+        /// Only takes lines, which plain strings are once they've had their newlines removed.
+        struct Line(String);
+
+        struct Lines;
+
+        impl Chain<0> for Lines {
+            type In<'a> = Line;
+            type Out<'a> = String;
+
+            fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+                format!("{}\n", input.0)
+            }
+        }
+
+        impl Length for Lines {
+            type Len = L<1>;
+        }
+
+        struct Unbroken;
+
+        impl Witness<String, Line> for Unbroken {
+            fn coerce(a: String) -> Line {
+                Line(a.replace('\n', " "))
+            }
+        }
+
+        assert_eq!(Composed::<Reverse, Lines, Unbroken>::cascade("a\nb".to_owned()), "b a\n");
+    }
+
+    /// Logging wraps any pipeline, while the auth check only makes sense for string requests, and
+    /// turns away anything not signed before it reaches the pipeline.
+    #[test]
+    fn middleware_stack() {
+        use std::cell::RefCell;
+
+        thread_local! {
+            static LOG: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+        }
+
+        fn log(entry: String) {
+            LOG.with_borrow_mut(|log| log.push(entry));
+        }
+
+        struct Logging;
+
+        impl<P: Cascade> Middleware<P> for Logging
+        where
+            for<'a> P::In<'a>: std::fmt::Debug,
+            for<'a> P::Out<'a>: std::fmt::Debug,
+        {
+            fn wrap<'a>(input: P::In<'a>, next: impl FnOnce(P::In<'a>) -> P::Out<'a>) -> P::Out<'a> {
+                log(format!("in {input:?}"));
+                let out = next(input);
+                log(format!("out {out:?}"));
+                out
+            }
+        }
+
+        struct Auth;
+
+        impl<P: CascadeOf<String, String>> Middleware<P> for Auth {
+            fn wrap<'a>(input: P::In<'a>, next: impl FnOnce(P::In<'a>) -> P::Out<'a>) -> P::Out<'a> {
+                match input.strip_prefix("signed:") {
+                    Some(request) => {
+                        log("authorized".to_owned());
+                        next(request.to_owned())
+                    }
+                    None => "denied".to_owned(),
+                }
+            }
+        }
+
+        type Served = Wrapped<Double, (Logging, Auth)>;
+        assert_eq!(Served::cascade("signed:21".to_owned()), "42");
+        assert_eq!(Served::cascade("21".to_owned()), "denied");
+        assert_eq!(LOG.with_borrow(Clone::clone), [
+            "in \"signed:21\"",
+            "authorized",
+            "out \"42\"",
+            "in \"21\"",
+            "out \"denied\"",
+        ]);
+
+        assert_eq!(per_line::<Wrapped<Reverse, Auth>>("signed:ab\ncd"), ["ba", "denied"]);
+    }
+}
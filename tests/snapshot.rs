@@ -0,0 +1,58 @@
+#[cfg(test)]
+#[cfg(feature = "insta")]
+pub mod tests {
+
+    use chain_link::*;
+
+    /// Same conversions as the `chain_link_cascade` test: f32 -> i32 -> u32 -> "4,294,967,295".
+    struct Pipeline;
+
+    impl Chain<0> for Pipeline {
+        type In<'a> = f32;
+        type Out<'a> = i32;
+
+        fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+            input as i32
+        }
+    }
+
+    impl Chain<1> for Pipeline {
+        type In<'a> = i32;
+        type Out<'a> = u32;
+
+        fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+            input as u32
+        }
+    }
+
+    impl Chain<2> for Pipeline {
+        type In<'a> = u32;
+        type Out<'a> = String;
+
+        fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+            let mut output = String::new();
+            let mut n = input;
+            while n >= 1_000 {
+                output = format!(",{:03}{}", n % 1_000, output);
+                n /= 1_000;
+            }
+            format!("{n}{output}")
+        }
+    }
+
+    impl Length for Pipeline {
+        type Len = L<3>;
+    }
+
+    #[test]
+    fn inline_snapshot() {
+        assert_cascade_snapshot!(Pipeline, -1.5, @r#""4,294,967,295""#);
+    }
+
+    // the snapshot lives in tests/snapshots, which Miri isn't allowed to read
+    #[cfg_attr(miri, ignore)]
+    #[test]
+    fn file_snapshot() {
+        assert_cascade_snapshot!(Pipeline, 1_234_567.8);
+    }
+}
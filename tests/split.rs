@@ -0,0 +1,129 @@
+#[cfg(test)]
+pub mod tests {
+
+    use chain_link::*;
+
+    /// Tokenizes a document, counts the words, then picks the most common one. The word list in
+    /// the middle is what gets sent across.
+    struct WordCount;
+
+    impl Chain<0> for WordCount {
+        type In<'a> = String;
+        type Out<'a> = Vec<String>;
+
+        fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+            input.split_whitespace().map(str::to_lowercase).collect()
+        }
+    }
+
+    impl Chain<1> for WordCount {
+        type In<'a> = Vec<String>;
+        type Out<'a> = Vec<(String, u32)>;
+
+        fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+            let mut counts: Vec<(String, u32)> = Vec::new();
+            for word in input {
+                match counts.iter_mut().find(|(seen, _)| *seen == word) {
+                    Some((_, count)) => *count += 1,
+                    None => counts.push((word, 1)),
+                }
+            }
+            counts
+        }
+    }
+
+    impl Chain<2> for WordCount {
+        type In<'a> = Vec<(String, u32)>;
+        type Out<'a> = Option<String>;
+
+        fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+            input.into_iter().max_by_key(|(_, count)| *count).map(|(word, _)| word)
+        }
+    }
+
+    impl Length for WordCount {
+        type Len = L<3>;
+    }
+
+    fn document() -> String {
+        "the cat and the hat ".repeat(50)
+    }
+
+    #[test]
+    fn split_at_pivot() {
+        let expected = WordCount::cascade(document());
+
+        let pivot = <WordCount as SplitCascade<1>>::cascade_to_pivot(document());
+        assert_eq!(<WordCount as SplitCascade<1>>::resume_from_pivot(&pivot), Ok(expected.clone()));
+
+        let pivot = <WordCount as SplitCascade<2>>::cascade_to_pivot(document());
+        assert_eq!(<WordCount as SplitCascade<2>>::resume_from_pivot(&pivot), Ok(expected));
+
+        assert_eq!(<WordCount as SplitCascade<2>>::resume_from_pivot(&[1, 2, 3]), Err(DecodeError));
+    }
+
+    #[cfg(feature = "flate2")]
+    #[test]
+    fn compressed_pivot() {
+        let pivot = <WordCount as SplitCascade<1>>::cascade_to_pivot(document());
+        let compressed = <WordCount as CompressedSplitCascade<1>>::cascade_to_pivot_compressed(document());
+        assert!(compressed.len() < pivot.len() / 10);
+
+        let resumed = <WordCount as CompressedSplitCascade<1>>::resume_from_pivot_compressed(&compressed);
+        assert_eq!(resumed, Ok(WordCount::cascade(document())));
+        assert_eq!(
+            <WordCount as CompressedSplitCascade<1>>::resume_from_pivot_compressed(&pivot),
+            Err(DecodeError),
+        );
+
+        // a pivot that inflates past the limit is refused, however small it is compressed
+        let within = |limit| {
+            <WordCount as CompressedSplitCascade<1>>::resume_from_pivot_compressed_within(&compressed, limit)
+        };
+        assert_eq!(within(pivot.len() - 1), Err(DecodeError));
+        assert_eq!(within(pivot.len()), Ok(WordCount::cascade(document())));
+    }
+
+    /// A worker thread on the other end of a pair of channels stands in for a second node.
+    #[test]
+    fn distributed_cascade() {
+        use std::sync::mpsc::{channel, Receiver, Sender};
+        use std::thread;
+
+        struct Channels {
+            requests: Sender<Vec<u8>>,
+            responses: Receiver<Vec<u8>>,
+            sent: usize,
+        }
+
+        impl Transport for Channels {
+            type Error = &'static str;
+
+            fn send(&mut self, request: Vec<u8>) -> Result<Vec<u8>, Self::Error> {
+                self.sent += request.len();
+                self.requests.send(request).map_err(|_| "worker is gone")?;
+                self.responses.recv().map_err(|_| "worker is gone")
+            }
+        }
+
+        let (requests, worker_requests) = channel::<Vec<u8>>();
+        let (worker_responses, responses) = channel();
+        let worker = thread::spawn(move || {
+            for request in worker_requests {
+                worker_responses.send(serve::<WordCount, 2>(&request)).unwrap();
+            }
+        });
+
+        let transport = Channels { requests, responses, sent: 0 };
+        let mut distributed = DistributedCascade::<WordCount, 2, _>::new(transport);
+        assert_eq!(distributed.cascade(document()), Ok(WordCount::cascade(document())));
+        assert_eq!(distributed.cascade("b a b".to_owned()), Ok(Some("b".to_owned())));
+        // only the word counts crossed over, not the whole document
+        assert!(distributed.transport().sent < document().len() / 2);
+
+        drop(distributed);
+        worker.join().unwrap();
+
+        assert_eq!(Option::<Option<String>>::from_bytes(&serve::<WordCount, 2>(&[9])), Ok(None));
+    }
+}
@@ -0,0 +1,63 @@
+#[cfg(test)]
+pub mod tests {
+
+    use chain_link::*;
+
+    struct Pipeline;
+
+    impl Chain<0> for Pipeline {
+        type In<'a> = &'a str;
+        type Out<'a> = u32;
+
+        fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+            input.parse().unwrap()
+        }
+    }
+
+    impl Chain<1> for Pipeline {
+        type In<'a> = u32;
+        type Out<'a> = u32;
+
+        fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+            input * 2
+        }
+    }
+
+    impl Chain<2> for Pipeline {
+        type In<'a> = u32;
+        type Out<'a> = String;
+
+        fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+            input.to_string()
+        }
+    }
+
+    impl Length for Pipeline {
+        type Len = L<3>;
+    }
+
+    mod pipeline_stages {
+        use super::Pipeline;
+
+        use chain_link::Length;
+
+        chain_link::gen_stage_tests!(Pipeline, 3);
+
+        /// One stub per stage, named after its index.
+        #[test]
+        fn stubs_generated() {
+            assert_eq!(STAGE_TESTS, Pipeline::len());
+            let stubs: [fn(); 3] = [stage_0, stage_1, stage_2];
+            for stub in stubs {
+                assert!(std::panic::catch_unwind(stub).is_err());
+            }
+        }
+    }
+
+    #[test]
+    fn run_stage() {
+        assert_eq!(chain_link::run_stage::<0, Pipeline>("21"), 21);
+        assert_eq!(chain_link::run_stage::<1, Pipeline>(21), 42);
+        assert_eq!(chain_link::run_stage::<2, Pipeline>(42), "42");
+    }
+}
@@ -0,0 +1,135 @@
+#[cfg(test)]
+pub mod tests {
+
+    use chain_link::*;
+
+    /// Link 0 numbers each input it sees and link 1 keeps a running total, so outputs depend on
+    /// everything that ran before them.
+    struct Tally;
+
+    impl StatefulChain<0> for Tally {
+        type State = u32;
+        type In<'a> = i64;
+        type Out<'a> = (u32, i64);
+
+        fn chain<'a>(count: &mut u32, input: Self::In<'a>) -> Self::Out<'a> {
+            *count += 1;
+            (*count, input)
+        }
+    }
+
+    impl StatefulChain<1> for Tally {
+        type State = Vec<i64>;
+        type In<'a> = (u32, i64);
+        type Out<'a> = String;
+
+        fn chain<'a>(history: &mut Vec<i64>, (count, input): Self::In<'a>) -> Self::Out<'a> {
+            history.push(input);
+            format!("#{count}: {}", history.iter().sum::<i64>())
+        }
+    }
+
+    impl Length for Tally {
+        type Len = L<2>;
+    }
+
+    #[test]
+    fn snapshot_restore() {
+        let mut tally = Stateful::<Tally>::new();
+        assert_eq!(tally.cascade(5), "#1: 5");
+        assert_eq!(tally.cascade(-2), "#2: 3");
+        let snapshot = tally.snapshot();
+
+        let after: Vec<_> = [10, 1].map(|n| tally.cascade(n)).into();
+        assert_eq!(after, ["#3: 13", "#4: 14"]);
+
+        tally.restore(&snapshot).unwrap();
+        assert_eq!(tally.states(), &(((), 2), vec![5, -2]));
+        let replayed: Vec<_> = [10, 1].map(|n| tally.cascade(n)).into();
+        assert_eq!(replayed, after);
+
+        let mut fresh = Stateful::<Tally>::default();
+        fresh.restore(&snapshot).unwrap();
+        assert_eq!(fresh.cascade(10), "#3: 13");
+
+        assert_eq!(fresh.restore(&snapshot[..snapshot.len() - 1]), Err(DecodeError));
+        assert_eq!(fresh.cascade(1), "#4: 14");
+    }
+
+    /// Lengths are checked against what's left to decode, rather than trusted.
+    #[test]
+    fn hostile_lengths() {
+        assert_eq!(Vec::<u64>::from_bytes(&(usize::MAX, 7u8).to_bytes()), Err(DecodeError));
+        assert_eq!(Vec::<()>::from_bytes(&u64::MAX.to_bytes()), Err(DecodeError));
+        let units = vec![(); MAX_ZERO_SIZED_ITEMS];
+        assert_eq!(Vec::<()>::from_bytes(&units.to_bytes()), Ok(units));
+    }
+
+    /// Collects berries from baskets until there are at least 100, counting the baskets opened
+    /// along the way. Once the total crosses the threshold the run stops, without labelling that
+    /// basket or opening any more.
+    #[test]
+    fn fold_flow_cascade() {
+        use std::ops::ControlFlow;
+
+        #[derive(Debug, Default, PartialEq)]
+        struct Harvest {
+            baskets: u32,
+            berries: u32,
+        }
+
+        struct Collect;
+
+        impl FoldFlowChain<0> for Collect {
+            type Acc = Harvest;
+            type Break = u32;
+            type In<'a> = &'a str;
+            type Out<'a> = u32;
+
+            fn fold_flow<'a>(harvest: &mut Harvest, input: Self::In<'a>) -> ControlFlow<u32, Self::Out<'a>> {
+                harvest.baskets += 1;
+                ControlFlow::Continue(input.parse().unwrap())
+            }
+        }
+
+        impl FoldFlowChain<1> for Collect {
+            type Acc = Harvest;
+            type Break = u32;
+            type In<'a> = u32;
+            type Out<'a> = u32;
+
+            fn fold_flow<'a>(harvest: &mut Harvest, berries: Self::In<'a>) -> ControlFlow<u32, Self::Out<'a>> {
+                harvest.berries += berries;
+                match harvest.berries >= 100 {
+                    true => ControlFlow::Break(harvest.berries),
+                    false => ControlFlow::Continue(berries),
+                }
+            }
+        }
+
+        impl FoldFlowChain<2> for Collect {
+            type Acc = Harvest;
+            type Break = u32;
+            type In<'a> = u32;
+            type Out<'a> = String;
+
+            fn fold_flow<'a>(harvest: &mut Harvest, berries: Self::In<'a>) -> ControlFlow<u32, Self::Out<'a>> {
+                ControlFlow::Continue(format!("basket {}: {berries}", harvest.baskets))
+            }
+        }
+
+        impl Length for Collect {
+            type Len = L<3>;
+        }
+
+        let mut harvest = Harvest::default();
+        let mut labels = Vec::new();
+        let stopped = ["30", "40", "50", "10"].into_iter().try_for_each(|basket| {
+            labels.push(Collect::fold_flow_cascade(&mut harvest, basket)?);
+            ControlFlow::Continue(())
+        });
+        assert_eq!(stopped, ControlFlow::Break(120));
+        assert_eq!(labels, ["basket 1: 30", "basket 2: 40"]);
+        assert_eq!(harvest, Harvest { baskets: 3, berries: 120 });
+    }
+}
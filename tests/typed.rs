@@ -0,0 +1,52 @@
+#[cfg(test)]
+pub mod tests {
+
+    use chain_link::*;
+
+    struct Trim;
+
+    impl StageDef for Trim {
+        type In = String;
+        type Out = String;
+
+        fn run(input: String) -> String {
+            input.trim().to_owned()
+        }
+    }
+
+    struct Words;
+
+    impl StageDef for Words {
+        type In = String;
+        type Out = Vec<String>;
+
+        fn run(input: String) -> Vec<String> {
+            input.split_whitespace().map(str::to_owned).collect()
+        }
+    }
+
+    struct Count;
+
+    impl StageDef for Count {
+        type In = Vec<String>;
+        type Out = usize;
+
+        fn run(input: Vec<String>) -> usize {
+            input.len()
+        }
+    }
+
+    /// The same markers make up different pipelines just by reordering the tuple.
+    #[test]
+    fn typed_pipeline() {
+        type WordCount = TypedPipeline<(Trim, Words, Count)>;
+        assert_eq!(WordCount::cascade("  the quick brown fox ".to_owned()), 4);
+        assert_eq!(<WordCount as Chain<1>>::chain("a b".to_owned()), ["a", "b"]);
+
+        type TrimTwice = TypedPipeline<(Trim, Trim)>;
+        assert_eq!(TrimTwice::cascade(" x ".to_owned()), "x");
+
+        type JustCount = TypedPipeline<(Words, Count)>;
+        assert_eq!(JustCount::cascade(" a  b ".to_owned()), 2);
+    }
+}
@@ -0,0 +1,139 @@
+#[cfg(test)]
+pub mod tests {
+
+    use std::mem::MaybeUninit;
+    use std::panic::{self, AssertUnwindSafe};
+    use std::rc::Rc;
+
+    use chain_link::*;
+
+    /// Computes the parameters of a ramp, then writes as many samples as the caller has room for.
+    struct Ramp;
+
+    impl Chain<0> for Ramp {
+        type In<'a> = (f32, f32);
+        type Out<'a> = (f32, f32);
+
+        fn chain((start, end): Self::In<'_>) -> Self::Out<'_> {
+            (start, end - start)
+        }
+    }
+
+    impl Length for Ramp {
+        type Len = L<1>;
+    }
+
+    impl Fill for Ramp {
+        type Elem = f32;
+
+        fn fill((start, span): Self::Out<'_>, buf: &mut OutBuf<'_, f32>) {
+            let steps = (buf.capacity() - 1) as f32;
+            for i in 0..buf.capacity() {
+                buf.push(start + span * i as f32 / steps);
+            }
+        }
+    }
+
+    /// Forgets to write the last element.
+    struct Short;
+
+    impl Chain<0> for Short {
+        type In<'a> = Rc<()>;
+        type Out<'a> = Rc<()>;
+
+        fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+            input
+        }
+    }
+
+    impl Length for Short {
+        type Len = L<1>;
+    }
+
+    impl Fill for Short {
+        type Elem = Rc<()>;
+
+        fn fill(output: Self::Out<'_>, buf: &mut OutBuf<'_, Rc<()>>) {
+            while buf.remaining() > 1 {
+                buf.push(output.clone());
+            }
+        }
+    }
+
+    #[test]
+    fn cascade_into_uninit() {
+        let mut buf = [MaybeUninit::uninit(); 5];
+        let out = Ramp::cascade_into((1.0, 3.0), &mut buf);
+        assert_eq!(out, [1.0, 1.5, 2.0, 2.5, 3.0]);
+
+        let counter = Rc::new(());
+        let mut buf: Vec<MaybeUninit<Rc<()>>> = (0..4).map(|_| MaybeUninit::uninit()).collect();
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            Short::cascade_into(counter.clone(), &mut buf);
+        }));
+        assert!(result.is_err());
+        // everything the stage managed to write was dropped rather than leaked
+        assert_eq!(Rc::strong_count(&counter), 1);
+
+        let mut buf = [const { MaybeUninit::uninit() }; 2];
+        let mut out = OutBuf::new(&mut buf);
+        out.push(String::from("a"));
+        out.push(String::from("b"));
+        assert!(out.is_full());
+        let out = out.finish();
+        out[0].push('!');
+        assert_eq!(out, ["a!", "b"]);
+        for s in out {
+            // the backing array is still `MaybeUninit`, so it won't drop the strings itself
+            drop(std::mem::take(s));
+        }
+    }
+
+    /// The unsafe paths of `OutBuf` on their own, meant to be run under `cargo miri test`.
+    #[test]
+    fn out_buf_edge_cases() {
+        // nothing to write, so it's full from the start
+        let mut buf: [MaybeUninit<String>; 0] = [];
+        let out: &mut [String] = OutBuf::new(&mut buf).finish();
+        assert!(out.is_empty());
+
+        // zero-sized elements never touch memory, but still have to be counted
+        let mut buf = [MaybeUninit::<()>::uninit(); 3];
+        let mut out = OutBuf::new(&mut buf);
+        out.push(());
+        out.push(());
+        out.push(());
+        assert_eq!(out.finish().len(), 3);
+
+        // dropped early without a panic, which has to drop only the written prefix
+        let counter = Rc::new(());
+        let mut buf: [MaybeUninit<Rc<()>>; 4] = [const { MaybeUninit::uninit() }; 4];
+        let mut out = OutBuf::new(&mut buf);
+        out.push(counter.clone());
+        out.push(counter.clone());
+        assert_eq!(out.filled(), 2);
+        assert_eq!(Rc::strong_count(&counter), 3);
+        drop(out);
+        assert_eq!(Rc::strong_count(&counter), 1);
+
+        // the same buffer can be reused once the view is gone
+        let mut out = OutBuf::new(&mut buf);
+        while !out.is_full() {
+            out.push(counter.clone());
+        }
+        let written = out.finish();
+        assert_eq!(Rc::strong_count(&counter), 5);
+        // the buffer is still `MaybeUninit`, so whatever was handed out has to be dropped by hand
+        // SAFETY: `finish` handed out every element initialized, and nothing reads them after
+        unsafe { std::ptr::drop_in_place(written) };
+        assert_eq!(Rc::strong_count(&counter), 1);
+
+        let overflow = panic::catch_unwind(AssertUnwindSafe(|| {
+            let mut buf = [MaybeUninit::uninit(); 1];
+            let mut out = OutBuf::new(&mut buf);
+            out.push(1u8);
+            out.push(2u8);
+        }));
+        assert!(overflow.is_err());
+    }
+}
@@ -0,0 +1,136 @@
+#[cfg(test)]
+pub mod tests {
+
+    use std::sync::Mutex;
+
+    use chain_link::*;
+
+    static RAN: Mutex<Vec<&str>> = Mutex::new(Vec::new());
+
+    struct Signup {
+        name: String,
+        email: String,
+        age: u32,
+    }
+
+    /// Declared in an order that's neither alphabetical nor meaningful.
+    struct SignupRules;
+
+    impl Homogeneous for SignupRules {
+        type Item = Signup;
+    }
+
+    impl Validate<0> for SignupRules {
+        const NAME: &'static str = "name";
+
+        fn validate(item: &Signup) -> Result<(), String> {
+            RAN.lock().unwrap().push(<Self as Validate<0>>::NAME);
+            match item.name.trim().is_empty() {
+                true => Err("is blank".to_owned()),
+                false => Ok(()),
+            }
+        }
+    }
+
+    impl Validate<1> for SignupRules {
+        const NAME: &'static str = "age";
+
+        fn validate(item: &Signup) -> Result<(), String> {
+            RAN.lock().unwrap().push(<Self as Validate<1>>::NAME);
+            match item.age >= 13 {
+                true => Ok(()),
+                false => Err(format!("{} is under 13", item.age)),
+            }
+        }
+    }
+
+    impl Validate<2> for SignupRules {
+        const NAME: &'static str = "email";
+
+        fn validate(item: &Signup) -> Result<(), String> {
+            RAN.lock().unwrap().push(<Self as Validate<2>>::NAME);
+            match item.email.contains('@') {
+                true => Ok(()),
+                false => Err("has no @".to_owned()),
+            }
+        }
+    }
+
+    impl Length for SignupRules {
+        type Len = L<3>;
+    }
+
+    #[test]
+    fn validate_all_order_independent() {
+        let good = Signup { name: "Ann".to_owned(), email: "ann@example.com".to_owned(), age: 30 };
+        let bad = Signup { name: " ".to_owned(), email: "ann".to_owned(), age: 9 };
+
+        RAN.lock().unwrap().clear();
+        assert_eq!(SignupRules::validate_all(&good), Ok(()));
+        assert_eq!(*RAN.lock().unwrap(), ["age", "email", "name"]);
+
+        let canonical = SignupRules::validate_all(&bad);
+        let validators: Vec<_> = canonical.as_ref().unwrap_err().iter().map(|e| e.validator).collect();
+        assert_eq!(validators, ["age", "email", "name"]);
+        assert_eq!(canonical.as_ref().unwrap_err()[0].to_string(), "age: 9 is under 13");
+
+        let mut orders = Vec::new();
+        for seed in 0..16 {
+            RAN.lock().unwrap().clear();
+            assert_eq!(SignupRules::validate_all_shuffled(&bad, seed), canonical);
+            assert_eq!(SignupRules::validate_all_shuffled(&good, seed), Ok(()));
+            orders.push(RAN.lock().unwrap()[..3].to_vec());
+        }
+        orders.sort();
+        orders.dedup();
+        // the shuffle did actually try different orders
+        assert!(orders.len() > 1);
+    }
+
+    /// Records are CSV lines of a name and an age. A line with the wrong number of fields is
+    /// rejected before any link runs, and an age that comes out of range is rejected on the way
+    /// out.
+    #[test]
+    fn cascade_schema_validated() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        static PARSED: AtomicUsize = AtomicUsize::new(0);
+
+        struct Record;
+
+        impl Chain<0> for Record {
+            type In<'a> = &'a str;
+            type Out<'a> = (String, u32);
+
+            fn chain(input: Self::In<'_>) -> Self::Out<'_> {
+                PARSED.fetch_add(1, Ordering::Relaxed);
+                let (name, age) = input.split_once(',').unwrap();
+                (name.trim().to_owned(), age.trim().parse().unwrap_or(0))
+            }
+        }
+
+        impl Length for Record {
+            type Len = L<1>;
+        }
+
+        let two_fields = |line: &&str| match line.split(',').count() {
+            2 => Ok(()),
+            count => Err(format!("expected 2 fields, found {count}")),
+        };
+        let plausible_age = |(_, age): &(String, u32)| match age {
+            1..=150 => Ok(()),
+            _ => Err(format!("age {age} out of range")),
+        };
+
+        let validated = |line| Record::cascade_schema_validated(&two_fields, &plausible_age, line);
+        assert_eq!(validated("ada, 36"), Ok(("ada".to_owned(), 36)));
+
+        let rejected = validated("ada, 36, london");
+        assert_eq!(rejected, Err(SchemaError::Input("expected 2 fields, found 3".to_owned())));
+        assert_eq!(PARSED.load(Ordering::Relaxed), 1);
+
+        let rejected = validated("ada, old");
+        assert_eq!(rejected.unwrap_err().to_string(), "output doesn't match its schema: age 0 out of range");
+        assert_eq!(PARSED.load(Ordering::Relaxed), 2);
+    }
+}